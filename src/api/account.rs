@@ -1,4 +1,4 @@
-use crate::api::FuturesAccountInfo;
+use crate::api::{FuturesAccountInfo, MarketType, SpotAccountInfo};
 use crate::utils::hmac_sha256;
 use crate::Message;
 use crate::Trade;
@@ -9,6 +9,16 @@ use std::env;
 
 //binance 계정 연결
 pub fn binance_account_connection() -> impl Stream<Item = Message> {
+    futures_account_stream(MarketType::Futures)
+}
+
+//binance COIN-M 계정 연결
+pub fn binance_coin_m_account_connection() -> impl Stream<Item = Message> {
+    futures_account_stream(MarketType::CoinM)
+}
+
+//선물 계정 조회 (USDⓈ-M / COIN-M 응답 구조가 같음)
+fn futures_account_stream(market_type: MarketType) -> impl Stream<Item = Message> {
     stream! {
 
         let api_key = match env::var("BINANCE_API_KEY") {
@@ -41,10 +51,18 @@ pub fn binance_account_connection() -> impl Stream<Item = Message> {
             let signature = hmac_sha256(&api_secret, &query);
 
             // 퓨처스 계정 정보 엔드포인트로 변경
-            let url = format!(
-                "https://fapi.binance.com/fapi/v2/account?{}&signature={}",
-                query, signature
-            );
+            let url = match market_type {
+                MarketType::CoinM => format!(
+                    "{}/account?{}&signature={}",
+                    market_type.rest_address(),
+                    query,
+                    signature
+                ),
+                _ => format!(
+                    "https://fapi.binance.com/fapi/v2/account?{}&signature={}",
+                    query, signature
+                ),
+            };
 
 
             match client
@@ -69,8 +87,10 @@ pub fn binance_account_connection() -> impl Stream<Item = Message> {
                                         );
                                         let trades_signature = hmac_sha256(&api_secret, &trades_query);
                                         let trades_url = format!(
-                                            "https://fapi.binance.com/fapi/v1/userTrades?{}&signature={}",
-                                            trades_query, trades_signature
+                                            "{}/userTrades?{}&signature={}",
+                                            market_type.rest_address(),
+                                            trades_query,
+                                            trades_signature
                                         );
 
                                         if let Ok(trades_response) = client
@@ -94,17 +114,82 @@ pub fn binance_account_connection() -> impl Stream<Item = Message> {
 
                                                 if total_buy_quantity > 0.0 {
                                                     let avg_price = total_buy_amount / total_buy_quantity;
-                                                    let symbol = position.symbol.replace("USDT", "");
+                                                    let symbol = position.symbol.clone();
                                                     yield Message::UpdateAveragePrice(symbol, avg_price);
                                                 }
                                             }
                                         }
                                     }
                                 }
-                                yield Message::UpdateAccountInfo(account_info);
+                                match market_type {
+                                    MarketType::CoinM => yield Message::UpdateCoinMAccountInfo(account_info),
+                                    _ => yield Message::UpdateAccountInfo(account_info),
+                                }
+                            }
+                            Err(e) => {
+                                println!("Failed to parse {} account info: {} \nResponse: {}", market_type, e, text);
+                                yield Message::FetchError(format!("Parse error: {}", e));
+                            }
+                        }
+                    } else {
+                        let error = response.text().await.unwrap_or_default();
+                        println!("API error response: {}", error);
+                        yield Message::FetchError(format!("API error: {}", error));
+                    }
+                }
+                Err(e) => {
+                    println!("Request error: {}", e);
+                    yield Message::FetchError(format!("Request failed: {}", e));
+                }
+            }
+
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        }
+    }
+}
+
+//binance 현물 계정 연결
+pub fn binance_spot_account_connection() -> impl Stream<Item = Message> {
+    stream! {
+        let (api_key, api_secret) = match (env::var("BINANCE_API_KEY"), env::var("BINANCE_API_SECRET")) {
+            (Ok(key), Ok(secret)) => (key, secret),
+            _ => {
+                println!("API KEY/SECRET not found");
+                yield Message::FetchError("API KEY not found".to_string());
+                return;
+            }
+        };
+
+        let client = reqwest::Client::new();
+
+        loop {
+            let timestamp = chrono::Utc::now().timestamp_millis();
+            let query = format!("timestamp={}&omitZeroBalances=true", timestamp);
+            let signature = hmac_sha256(&api_secret, &query);
+
+            let url = format!(
+                "{}/account?{}&signature={}",
+                MarketType::Spot.rest_address(),
+                query,
+                signature
+            );
+
+            match client
+                .get(&url)
+                .header("X-MBX-APIKEY", &api_key)
+                .send()
+                .await
+            {
+                Ok(response) => {
+                    if response.status().is_success() {
+                        let text = response.text().await.unwrap_or_default();
+
+                        match serde_json::from_str::<SpotAccountInfo>(&text) {
+                            Ok(account_info) => {
+                                yield Message::UpdateSpotAccountInfo(account_info);
                             }
                             Err(e) => {
-                                println!("Failed to parse futures account info: {} \nResponse: {}", e, text);
+                                println!("Failed to parse spot account info: {} \nResponse: {}", e, text);
                                 yield Message::FetchError(format!("Parse error: {}", e));
                             }
                         }
@@ -1,10 +1,14 @@
-use crate::api::BinanceCandle;
+use crate::api::{bybit, okx, BinanceCandle, MarketType};
+use crate::models::{ContractType, Symbol};
+use crate::store::resample::resample;
+use crate::store::tape::{TapeEvent, TapeRecorder};
 use crate::uc;
+use crate::utils::step_precision;
 use crate::BinanceTrade;
 use crate::Message;
 use crate::{CandleType, Candlestick};
 use async_stream::stream;
-use futures_util::Stream;
+use futures_util::{SinkExt, Stream};
 use iced::futures::{channel::mpsc, StreamExt};
 use iced::time::Duration;
 use std::collections::{BTreeMap, HashMap};
@@ -16,35 +20,61 @@ pub fn binance_connection() -> impl Stream<Item = Message> {
         //buffer size 100
         let (tx, mut rx) = mpsc::channel(100);
         let mut current_coin = "btcusdt".to_string();
+        let mut current_market = MarketType::Futures;
         let mut last_prices: HashMap<String, f64> = HashMap::new();
+        let mut recorder = TapeRecorder::from_env();
 
         yield Message::WebSocketInit(tx.clone());
 
         loop {
             let url_string = format!(
                 "{}/{}@aggTrade",
-                uc::BINANCE_FWSS_ADDRESS,
+                current_market.ws_address(),
                 current_coin.to_lowercase()
             );
 
 
             match connect_async(url_string).await {
                 Ok((mut ws_stream, _)) => {
-                    println!("Connected to futures stream for {}", current_coin);
+                    println!("Connected to {} stream for {}", current_market, current_coin);
+                    yield Message::StreamConnected;
+
+                    // 기록기가 호가 / 마크 가격도 기록하면 같은 연결에 추가 구독
+                    if let Some(recorder) = &recorder {
+                        let mut params = vec![];
+                        if recorder.subscribes(TapeEvent::Depth) {
+                            params.push(format!("{}@depth@100ms", current_coin));
+                        }
+                        if recorder.subscribes(TapeEvent::MarkPrice) && current_market != MarketType::Spot {
+                            params.push(format!("{}@markPrice@1s", current_coin));
+                        }
+                        if !params.is_empty() {
+                            let subscribe = serde_json::json!({ "method": "SUBSCRIBE", "params": params, "id": 1 });
+                            if let Err(e) = ws_stream.send(ME::Text(subscribe.to_string().into())).await {
+                                println!("Tape subscribe error: {}", e);
+                            }
+                        }
+                    }
 
                     loop {
                         tokio::select! {
-                            Some(new_coin) = rx.next() => {
-                                println!("Switching to futures coin: {}", new_coin);
-                                current_coin = format!("{}usdt", new_coin.to_lowercase());
+                            Some(new_symbol) = rx.next() => {
+                                println!("Switching to {} coin: {}", new_symbol.market, new_symbol);
+                                current_market = new_symbol.market;
+                                current_coin = new_symbol.exchange_symbol.to_lowercase();
                                 break;
                             }
                             Some(msg) = ws_stream.next() => {
                                 match msg {
                                     Ok(ME::Text(text)) => {
                                         // println!("Received message: {}", text);  // 디버그용
+                                        if let Some(recorder) = recorder.as_mut() {
+                                            if let Some(event) = tape_event(&text) {
+                                                recorder.record(current_market, event, &text);
+                                            }
+                                        }
                                         if let Ok(trade) = serde_json::from_str::<BinanceTrade>(&text) {
-                                            let symbol = trade.symbol.replace("USDT", "");
+                                            let symbol = trade.symbol.clone();
 
                                             if let Ok(price) = trade.price.parse::<f64>() {
                                                 let prev_price = *last_prices.get(&symbol).unwrap_or(&price);
@@ -70,7 +100,7 @@ pub fn binance_connection() -> impl Stream<Item = Message> {
                                         }
                                     }
                                     Err(e) => {
-                                        println!("Futures WebSocket error: {}", e);
+                                        println!("{} WebSocket error: {}", current_market, e);
                                         break;
                                     }
                                     _ => {}
@@ -81,7 +111,7 @@ pub fn binance_connection() -> impl Stream<Item = Message> {
                     let _ = ws_stream.close(None).await;
                 }
                 Err(e) => {
-                    println!("Futures connection error: {}", e);
+                    println!("{} connection error: {}", current_market, e);
                     yield Message::Error;
                     tokio::time::sleep(Duration::from_secs(5)).await;
                 }
@@ -90,36 +120,61 @@ pub fn binance_connection() -> impl Stream<Item = Message> {
     }
 }
 
-//
+//바이낸스 이벤트 종류 (e 필드)
+fn tape_event(text: &str) -> Option<TapeEvent> {
+    let json: serde_json::Value = serde_json::from_str(text).ok()?;
+    match json["e"].as_str()? {
+        "aggTrade" => Some(TapeEvent::Trade),
+        "depthUpdate" => Some(TapeEvent::Depth),
+        "markPriceUpdate" => Some(TapeEvent::MarkPrice),
+        _ => None,
+    }
+}
+
+//캔들 조회 (end_time 이 있으면 그 시각 이전 캔들)
+//사용자 지정 타임프레임은 기준 캔들을 받아 리샘플링
 pub async fn fetch_candles_async(
-    market: &str,
+    symbol: &Symbol,
     candle_type: &CandleType,
-    to_date: Option<String>,
+    end_time: Option<u64>,
 ) -> Result<BTreeMap<u64, Candlestick>, Box<dyn std::error::Error>> {
-    let count = match candle_type {
-        CandleType::Day => 1000,
-        CandleType::Minute1 => 1000,
-        CandleType::Minute3 => 1000,
-    };
+    match candle_type.resample_base(symbol.market) {
+        Some(base) => {
+            let candles = fetch_native_candles_async(symbol, &base, end_time).await?;
+            Ok(resample(&candles, candle_type))
+        }
+        None => fetch_native_candles_async(symbol, candle_type, end_time).await,
+    }
+}
 
-    let binance_symbol = match market.split('-').last() {
-        Some(symbol) => format!("{}USDT", symbol),
-        None => "BTCUSDT".to_string(),
-    };
+//거래소 interval 캔들 조회
+pub async fn fetch_native_candles_async(
+    symbol: &Symbol,
+    candle_type: &CandleType,
+    end_time: Option<u64>,
+) -> Result<BTreeMap<u64, Candlestick>, Box<dyn std::error::Error>> {
+    match symbol.market {
+        MarketType::BybitLinear => {
+            return bybit::fetch_candles_async(symbol, candle_type, end_time).await
+        }
+        MarketType::OkxSwap => {
+            return okx::fetch_candles_async(symbol, candle_type, end_time).await
+        }
+        _ => {}
+    }
 
-    let interval = match candle_type {
-        CandleType::Minute1 => "1m",
-        CandleType::Minute3 => "3m",
-        CandleType::Day => "1d",
-    };
+    let count = 1000;
 
-    let url = format!(
+    let mut url = format!(
         "{}/klines?symbol={}&interval={}&limit={}",
-        uc::BINANCE_FAPI_ADDRESS,
-        binance_symbol,
-        interval,
+        symbol.market.rest_address(),
+        symbol.exchange_symbol,
+        candle_type.interval(),
         count
     );
+    if let Some(end_time) = end_time {
+        url.push_str(&format!("&endTime={}", end_time));
+    }
 
     let client = reqwest::Client::new();
     let response = client.get(&url).send().await?;
@@ -136,10 +191,10 @@ pub async fn fetch_candles_async(
     let result: BTreeMap<u64, Candlestick> = candles
         .into_iter()
         .filter(|candle| {
-            candle.open.parse::<f32>().unwrap_or(0.0) > 0.0
-                && candle.high.parse::<f32>().unwrap_or(0.0) > 0.0
-                && candle.low.parse::<f32>().unwrap_or(0.0) > 0.0
-                && candle.close.parse::<f32>().unwrap_or(0.0) > 0.0
+            candle.open.parse::<f64>().unwrap_or(0.0) > 0.0
+                && candle.high.parse::<f64>().unwrap_or(0.0) > 0.0
+                && candle.low.parse::<f64>().unwrap_or(0.0) > 0.0
+                && candle.close.parse::<f64>().unwrap_or(0.0) > 0.0
         })
         .map(|candle| {
             (
@@ -150,6 +205,9 @@ pub async fn fetch_candles_async(
                     low: candle.low.parse().unwrap_or(0.0),
                     close: candle.close.parse().unwrap_or(0.0),
                     volume: candle.volume.parse().unwrap_or(0.0),
+                    quote_volume: candle.quote_asset_volume.parse().unwrap_or(0.0),
+                    trades: candle.number_of_trades,
+                    taker_buy_volume: candle.taker_buy_base_asset_volume.parse().unwrap_or(0.0),
                 },
             )
         })
@@ -161,56 +219,118 @@ pub async fn fetch_candles_async(
         Ok(result)
     }
 }
-pub fn fetch_candles(
-    market: &str,
-    candle_type: &CandleType,
-    to_date: Option<String>, // 추가
-) -> Result<BTreeMap<u64, Candlestick>, Box<dyn std::error::Error>> {
-    let rt = tokio::runtime::Runtime::new()?;
-    rt.block_on(fetch_candles_async(market, candle_type, to_date))
-}
-pub async fn get_top_volume_pairs() -> Result<Vec<(String, f64)>, Box<dyn std::error::Error>> {
-    let url = format!("{}/ticker/24hr", uc::BINANCE_FAPI_ADDRESS);
+//24시간 거래대금 순 심볼 목록 (COIN-M 은 기초 자산 거래량)
+pub async fn get_top_volume_pairs(
+    market_type: MarketType,
+) -> Result<Vec<(String, f64)>, Box<dyn std::error::Error>> {
+    match market_type {
+        MarketType::BybitLinear => return bybit::get_top_volume_pairs().await,
+        MarketType::OkxSwap => return okx::get_top_volume_pairs().await,
+        _ => {}
+    }
+
+    let url = format!("{}/ticker/24hr", market_type.rest_address());
 
     let client = reqwest::Client::new();
     let response = client.get(url).send().await?;
     let data: Vec<serde_json::Value> = response.json().await?;
 
+    let volume_key = match market_type {
+        MarketType::CoinM => "baseVolume",
+        _ => "quoteVolume",
+    };
+
     let mut pairs: Vec<(String, f64)> = data
         .into_iter()
-        .filter(|item| {
-            item["symbol"]
-                .as_str()
-                .map(|s| s.ends_with("USDT"))
-                .unwrap_or(false)
-        })
         .filter_map(|item| {
             let symbol = item["symbol"].as_str()?.to_string();
-            let volume = item["quoteVolume"].as_str()?.parse::<f64>().ok()?;
+            let volume = item[volume_key].as_str()?.parse::<f64>().ok()?;
             Some((symbol, volume))
         })
         .collect();
 
     pairs.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
 
-    Ok(pairs.into_iter().take(20).collect())
+    Ok(pairs)
 }
 
-pub async fn get_symbol_info(symbol: &str) -> Result<(u32, u32), Box<dyn std::error::Error>> {
-    let url = format!("{}/exchangeInfo", uc::BINANCE_FAPI_ADDRESS);
+//exchangeInfo 의 거래 가능 심볼 목록
+pub async fn get_exchange_symbols(
+    market_type: MarketType,
+) -> Result<Vec<Symbol>, Box<dyn std::error::Error>> {
+    match market_type {
+        MarketType::BybitLinear => return bybit::get_exchange_symbols().await,
+        MarketType::OkxSwap => return okx::get_exchange_symbols().await,
+        _ => {}
+    }
+
+    let url = format!("{}/exchangeInfo", market_type.rest_address());
     let response = reqwest::get(url).await?;
     let info: serde_json::Value = response.json().await?;
 
-    if let Some(symbols) = info["symbols"].as_array() {
-        for symbol_info in symbols {
-            if symbol_info["symbol"].as_str() == Some(symbol) {
-                let quantity_precision =
-                    symbol_info["quantityPrecision"].as_u64().unwrap_or(3) as u32;
-                let price_precision = symbol_info["pricePrecision"].as_u64().unwrap_or(2) as u32;
-                return Ok((quantity_precision, price_precision));
-            }
-        }
+    let symbols = info["symbols"]
+        .as_array()
+        .ok_or("Invalid exchangeInfo response")?
+        .iter()
+        .filter_map(|item| parse_symbol(market_type, item))
+        .collect();
+
+    Ok(symbols)
+}
+
+//exchangeInfo 심볼 항목 -> Symbol
+fn parse_symbol(market_type: MarketType, item: &serde_json::Value) -> Option<Symbol> {
+    let status_key = match market_type {
+        MarketType::CoinM => "contractStatus",
+        _ => "status",
+    };
+    if item[status_key].as_str() != Some("TRADING") {
+        return None;
+    }
+
+    let quote = item["quoteAsset"].as_str()?;
+    if market_type != MarketType::CoinM && !uc::QUOTE_ASSETS.contains(&quote) {
+        return None;
+    }
+
+    let contract_type = match market_type {
+        MarketType::Spot => ContractType::Spot,
+        _ => ContractType::from_binance(item["contractType"].as_str().unwrap_or_default()),
+    };
+
+    let mut symbol = Symbol::new(
+        market_type,
+        item["baseAsset"].as_str()?,
+        quote,
+        contract_type,
+        item["symbol"].as_str()?,
+    );
+
+    let filter = |filter_type: &str, key: &str| {
+        item["filters"]
+            .as_array()
+            .and_then(|filters| {
+                filters
+                    .iter()
+                    .find(|f| f["filterType"].as_str() == Some(filter_type))
+            })
+            .and_then(|f| f[key].as_str())
+            .map(step_precision)
+    };
+    // 가격 자릿수는 PRICE_FILTER 의 tickSize 기준 (선물 pricePrecision 은 tick 보다 클 수 있음)
+    symbol.price_precision = filter("PRICE_FILTER", "tickSize").unwrap_or(2);
+    symbol.quantity_precision = match market_type {
+        // 현물은 precision 필드가 없으므로 LOT_SIZE 의 step 으로 계산
+        MarketType::Spot => filter("LOT_SIZE", "stepSize").unwrap_or(3),
+        _ => item["quantityPrecision"].as_u64().unwrap_or(3) as u32,
+    };
+
+    if market_type == MarketType::CoinM {
+        symbol.contract_size = item["contractSize"].as_f64().unwrap_or(1.0);
+    }
+    if contract_type == ContractType::Delivery {
+        symbol.delivery_date = item["deliveryDate"].as_u64();
     }
 
-    Err("Symbol not found".into())
+    Some(symbol)
 }
@@ -0,0 +1,646 @@
+use crate::api::{FuturesAccountInfo, FuturesAsset, FuturesPosition, MarketType};
+use crate::models::{ContractType, Symbol};
+use crate::store::tape::{TapeEvent, TapeRecorder};
+use crate::uc;
+use crate::ul;
+use crate::utils::{adjust_precision, hmac_sha256, step_precision};
+use crate::AlertType;
+use crate::BinanceTrade;
+use crate::Message;
+use crate::TradeType;
+use crate::{CandleType, Candlestick};
+use async_stream::stream;
+use futures_util::{SinkExt, Stream};
+use iced::futures::{channel::mpsc, StreamExt};
+use iced::time::Duration;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message as ME};
+
+const RECV_WINDOW: &str = "5000";
+
+/*
+Bybit v5 서명
+- GET : timestamp + api_key + recv_window + query string
+- POST : timestamp + api_key + recv_window + json body
+- X-BAPI-* 헤더로 전송
+*/
+fn sign_request(
+    request: reqwest::RequestBuilder,
+    api_key: &str,
+    api_secret: &str,
+    payload: &str,
+) -> reqwest::RequestBuilder {
+    let timestamp = chrono::Utc::now().timestamp_millis().to_string();
+    let signature = hmac_sha256(
+        api_secret,
+        &format!("{}{}{}{}", timestamp, api_key, RECV_WINDOW, payload),
+    );
+
+    request
+        .header("X-BAPI-API-KEY", api_key)
+        .header("X-BAPI-TIMESTAMP", timestamp)
+        .header("X-BAPI-RECV-WINDOW", RECV_WINDOW)
+        .header("X-BAPI-SIGN", signature)
+}
+
+//Bybit 응답의 retCode 확인 후 result 반환
+async fn bybit_result(
+    response: reqwest::Response,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let text = response.text().await?;
+    let json: serde_json::Value = serde_json::from_str(&text)?;
+
+    if json["retCode"].as_i64() != Some(0) {
+        return Err(format!("Bybit API error: {}", text).into());
+    }
+    Ok(json["result"].clone())
+}
+
+//bybit 공개 API 조회 (서명 없음)
+async fn public_get(
+    path: &str,
+    query: &str,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let url = format!("{}{}?{}", uc::BYBIT_API_ADDRESS, path, query);
+    let response = reqwest::Client::new().get(&url).send().await?;
+    bybit_result(response).await
+}
+
+//bybit 개인 API 조회 (서명 포함)
+async fn private_get(
+    client: &reqwest::Client,
+    api_key: &str,
+    api_secret: &str,
+    path: &str,
+    query: &str,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let url = format!("{}{}?{}", uc::BYBIT_API_ADDRESS, path, query);
+    let response = sign_request(client.get(&url), api_key, api_secret, query)
+        .send()
+        .await?;
+    bybit_result(response).await
+}
+
+/*
+bybit publicTrade connection
+- binance_connection 과 같은 메세지(UpdatePrice / AddCandlestick)를 보냄
+- 체결 데이터는 BinanceTrade 로 변환
+*/
+pub fn bybit_connection() -> impl Stream<Item = Message> {
+    stream! {
+        let (tx, mut rx) = mpsc::channel(100);
+        let mut current_coin = MarketType::BybitLinear.default_symbol().to_string();
+        let mut last_prices: HashMap<String, f64> = HashMap::new();
+        let mut recorder = TapeRecorder::from_env();
+
+        yield Message::WebSocketInit(tx.clone());
+
+        loop {
+            match connect_async(MarketType::BybitLinear.ws_address()).await {
+                Ok((mut ws_stream, _)) => {
+                    println!("Connected to Bybit stream for {}", current_coin);
+                    yield Message::StreamConnected;
+
+                    let subscribe = serde_json::json!({
+                        "op": "subscribe",
+                        "args": [format!("publicTrade.{}", current_coin)],
+                    });
+                    if let Err(e) = ws_stream.send(ME::Text(subscribe.to_string().into())).await {
+                        println!("Bybit subscribe error: {}", e);
+                    }
+
+                    // bybit 는 20초마다 ping 을 보내야 연결이 유지됨
+                    let mut ping = tokio::time::interval(Duration::from_secs(20));
+
+                    loop {
+                        tokio::select! {
+                            Some(new_symbol) = rx.next() => {
+                                println!("Switching to Bybit coin: {}", new_symbol);
+                                current_coin = new_symbol.exchange_symbol.clone();
+                                break;
+                            }
+                            _ = ping.tick() => {
+                                let ping_message = serde_json::json!({ "op": "ping" });
+                                if let Err(e) = ws_stream.send(ME::Text(ping_message.to_string().into())).await {
+                                    println!("Bybit ping error: {}", e);
+                                    break;
+                                }
+                            }
+                            Some(msg) = ws_stream.next() => {
+                                match msg {
+                                    Ok(ME::Text(text)) => {
+                                        let trades = parse_public_trades(&text);
+                                        if let (Some(recorder), false) = (recorder.as_mut(), trades.is_empty()) {
+                                            recorder.record(MarketType::BybitLinear, TapeEvent::Trade, &text);
+                                        }
+                                        for trade in trades {
+                                            let symbol = trade.symbol.clone();
+
+                                            if let Ok(price) = trade.price.parse::<f64>() {
+                                                let prev_price = *last_prices.get(&symbol).unwrap_or(&price);
+                                                let change_percent = if prev_price != 0.0 {
+                                                    ((price - prev_price) / prev_price) * 100.0
+                                                } else {
+                                                    0.0
+                                                };
+
+                                                last_prices.insert(symbol.clone(), price);
+
+                                                yield Message::UpdatePrice(
+                                                    symbol.clone(),
+                                                    price,
+                                                    change_percent
+                                                );
+                                                yield Message::AddCandlestick((
+                                                    trade.transaction_time as u64,
+                                                    trade
+                                                ));
+                                            }
+                                        }
+                                    }
+                                    Err(e) => {
+                                        println!("Bybit WebSocket error: {}", e);
+                                        break;
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                    }
+                    let _ = ws_stream.close(None).await;
+                }
+                Err(e) => {
+                    println!("Bybit connection error: {}", e);
+                    yield Message::Error;
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                }
+            }
+        }
+    }
+}
+
+/*
+publicTrade 메세지 -> BinanceTrade
+- S 는 taker 방향이므로 Sell 이면 매수자가 maker
+*/
+pub fn parse_public_trades(text: &str) -> Vec<BinanceTrade> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(text) else {
+        return vec![];
+    };
+    if !json["topic"]
+        .as_str()
+        .is_some_and(|topic| topic.starts_with("publicTrade."))
+    {
+        return vec![];
+    }
+    let event_time = json["ts"].as_i64().unwrap_or_default();
+
+    json["data"]
+        .as_array()
+        .map(|trades| {
+            trades
+                .iter()
+                .filter_map(|trade| {
+                    Some(BinanceTrade {
+                        event_time,
+                        symbol: trade["s"].as_str()?.to_string(),
+                        price: trade["p"].as_str()?.to_string(),
+                        quantity: trade["v"].as_str()?.to_string(),
+                        transaction_time: trade["T"].as_i64()?,
+                        is_buyer_maker: trade["S"].as_str() == Some("Sell"),
+                        first_trade_id: 0,
+                        last_trade_id: 0,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+//bybit 캔들 조회 (최신순 응답, end_time 이 있으면 그 시각 이전 캔들)
+pub async fn fetch_candles_async(
+    symbol: &Symbol,
+    candle_type: &CandleType,
+    end_time: Option<u64>,
+) -> Result<BTreeMap<u64, Candlestick>, Box<dyn std::error::Error>> {
+    // Bybit 은 8시간 / 3일봉 없음 (4시간 / 일봉을 리샘플링, MarketType::supports)
+    let interval = match candle_type {
+        CandleType::Minute1 => "1",
+        CandleType::Minute3 => "3",
+        CandleType::Minute5 => "5",
+        CandleType::Minute15 => "15",
+        CandleType::Minute30 => "30",
+        CandleType::Hour1 => "60",
+        CandleType::Hour2 => "120",
+        CandleType::Hour4 => "240",
+        CandleType::Hour6 => "360",
+        CandleType::Hour12 => "720",
+        CandleType::Day => "D",
+        CandleType::Week => "W",
+        CandleType::Month => "M",
+        CandleType::Hour8 | CandleType::Day3 | CandleType::Custom(_) => {
+            return Err(format!("Bybit does not support {} candles", candle_type).into())
+        }
+    };
+
+    let mut query = format!(
+        "category=linear&symbol={}&interval={}&limit=1000",
+        symbol.exchange_symbol, interval
+    );
+    if let Some(end_time) = end_time {
+        query.push_str(&format!("&end={}", end_time));
+    }
+    let result = public_get("/market/kline", &query).await?;
+
+    //[startTime, open, high, low, close, volume, turnover]
+    let result: BTreeMap<u64, Candlestick> = result["list"]
+        .as_array()
+        .ok_or("Invalid kline response")?
+        .iter()
+        .filter_map(|candle| {
+            let field = |i: usize| candle[i].as_str();
+            let candlestick = Candlestick {
+                open: field(1)?.parse().ok()?,
+                high: field(2)?.parse().ok()?,
+                low: field(3)?.parse().ok()?,
+                close: field(4)?.parse().ok()?,
+                volume: field(5)?.parse().ok()?,
+                quote_volume: field(6)?.parse().ok()?,
+                ..Default::default() // 체결 수 / 시장가 매수 거래량은 제공하지 않음
+            };
+            Some((field(0)?.parse::<u64>().ok()?, candlestick))
+        })
+        .filter(|(_, candle)| {
+            candle.open > 0.0 && candle.high > 0.0 && candle.low > 0.0 && candle.close > 0.0
+        })
+        .collect();
+
+    if result.is_empty() {
+        Err("No valid candles returned".into())
+    } else {
+        Ok(result)
+    }
+}
+
+//24시간 거래대금 순 심볼 목록
+pub async fn get_top_volume_pairs() -> Result<Vec<(String, f64)>, Box<dyn std::error::Error>> {
+    let result = public_get("/market/tickers", "category=linear").await?;
+
+    let mut pairs: Vec<(String, f64)> = result["list"]
+        .as_array()
+        .ok_or("Invalid tickers response")?
+        .iter()
+        .filter_map(|item| {
+            let symbol = item["symbol"].as_str()?.to_string();
+            let volume = item["turnover24h"].as_str()?.parse::<f64>().ok()?;
+            Some((symbol, volume))
+        })
+        .collect();
+
+    pairs.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    Ok(pairs)
+}
+
+//거래 가능한 USDT 무기한 심볼 목록
+pub async fn get_exchange_symbols() -> Result<Vec<Symbol>, Box<dyn std::error::Error>> {
+    let result = public_get("/market/instruments-info", "category=linear&limit=1000").await?;
+
+    let symbols = result["list"]
+        .as_array()
+        .ok_or("Invalid instruments-info response")?
+        .iter()
+        .filter_map(|item| {
+            if item["status"].as_str() != Some("Trading")
+                || item["contractType"].as_str() != Some("LinearPerpetual")
+            {
+                return None;
+            }
+            let quote = item["quoteCoin"].as_str()?;
+            if !uc::QUOTE_ASSETS.contains(&quote) {
+                return None;
+            }
+
+            let mut symbol = Symbol::new(
+                MarketType::BybitLinear,
+                item["baseCoin"].as_str()?,
+                quote,
+                ContractType::Perpetual,
+                item["symbol"].as_str()?,
+            );
+            if let Some(step) = item["lotSizeFilter"]["qtyStep"].as_str() {
+                symbol.quantity_precision = step_precision(step);
+            }
+            if let Some(tick) = item["priceFilter"]["tickSize"].as_str() {
+                symbol.price_precision = step_precision(tick);
+            }
+            Some(symbol)
+        })
+        .collect();
+
+    Ok(symbols)
+}
+
+/*
+bybit 계정 연결
+- 통합계좌(UNIFIED) 잔고 + linear 포지션을 FuturesAccountInfo 로 변환
+- 포지션 평균가는 avgPrice 를 그대로 사용
+*/
+pub fn bybit_account_connection() -> impl Stream<Item = Message> {
+    stream! {
+        let (api_key, api_secret) = match (env::var("BYBIT_API_KEY"), env::var("BYBIT_API_SECRET")) {
+            (Ok(key), Ok(secret)) => (key, secret),
+            _ => {
+                println!("BYBIT API KEY/SECRET not found");
+                yield Message::FetchError("BYBIT API KEY not found".to_string());
+                return;
+            }
+        };
+
+        let client = reqwest::Client::new();
+
+        loop {
+            let wallet = private_get(
+                &client,
+                &api_key,
+                &api_secret,
+                "/account/wallet-balance",
+                "accountType=UNIFIED",
+            )
+            .await
+            .map_err(|e| e.to_string());
+            let positions = linear_positions(&client, &api_key, &api_secret)
+                .await
+                .map_err(|e| e.to_string());
+
+            match (wallet, positions) {
+                (Ok(wallet), Ok(positions)) => {
+                    let account_info = to_account_info(&wallet, &positions);
+                    for position in &positions["list"].as_array().cloned().unwrap_or_default() {
+                        let size = value_f64(&position["size"]);
+                        let avg_price = value_f64(&position["avgPrice"]);
+                        if size != 0.0 && avg_price > 0.0 {
+                            if let Some(symbol) = position["symbol"].as_str() {
+                                yield Message::UpdateAveragePrice(symbol.to_string(), avg_price);
+                            }
+                        }
+                    }
+                    yield Message::UpdateBybitAccountInfo(account_info);
+                }
+                (Err(e), _) | (_, Err(e)) => {
+                    println!("Bybit account error: {}", e);
+                    yield Message::FetchError(format!("Bybit account error: {}", e));
+                }
+            }
+
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        }
+    }
+}
+
+//정산 자산별 (USDT, USDC) 선물 포지션을 하나의 list 로 합침
+async fn linear_positions(
+    client: &reqwest::Client,
+    api_key: &str,
+    api_secret: &str,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let mut list = Vec::new();
+    for settle_coin in uc::QUOTE_ASSETS {
+        let positions = private_get(
+            client,
+            api_key,
+            api_secret,
+            "/position/list",
+            &format!("category=linear&settleCoin={}", settle_coin),
+        )
+        .await?;
+        list.extend(positions["list"].as_array().cloned().unwrap_or_default());
+    }
+    Ok(serde_json::json!({ "list": list }))
+}
+
+//bybit 숫자 문자열 필드 (빈 문자열은 0)
+fn value_f64(value: &serde_json::Value) -> f64 {
+    value
+        .as_str()
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(0.0)
+}
+
+fn value_string(value: &serde_json::Value) -> String {
+    value.as_str().unwrap_or("0").to_string()
+}
+
+//wallet-balance / position/list 응답 -> FuturesAccountInfo
+fn to_account_info(
+    wallet: &serde_json::Value,
+    positions: &serde_json::Value,
+) -> FuturesAccountInfo {
+    let account = &wallet["list"][0];
+
+    let assets = account["coin"]
+        .as_array()
+        .map(|coins| {
+            coins
+                .iter()
+                .map(|coin| {
+                    // 통합계좌는 코인별 사용 가능 금액이 없으므로 지갑 잔고 - 증거금으로 계산
+                    let available = value_f64(&coin["walletBalance"])
+                        - value_f64(&coin["totalPositionIM"])
+                        - value_f64(&coin["totalOrderIM"]);
+                    FuturesAsset {
+                        asset: value_string(&coin["coin"]),
+                        wallet_balance: value_string(&coin["walletBalance"]),
+                        unrealized_profit: value_string(&coin["unrealisedPnl"]),
+                        margin_balance: value_string(&coin["equity"]),
+                        maint_margin: value_string(&coin["totalPositionMM"]),
+                        initial_margin: value_string(&coin["totalPositionIM"]),
+                        position_initial_margin: value_string(&coin["totalPositionIM"]),
+                        open_order_initial_margin: value_string(&coin["totalOrderIM"]),
+                        available_balance: available.max(0.0).to_string(),
+                        ..Default::default()
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let positions = positions["list"]
+        .as_array()
+        .map(|list| {
+            list.iter()
+                .map(|position| {
+                    // size 는 항상 양수이므로 side 로 방향 표시
+                    let size = value_f64(&position["size"]);
+                    let position_amt = match position["side"].as_str() {
+                        Some("Sell") => -size,
+                        _ => size,
+                    };
+                    FuturesPosition {
+                        symbol: value_string(&position["symbol"]),
+                        initial_margin: value_string(&position["positionIM"]),
+                        maint_margin: value_string(&position["positionMM"]),
+                        unrealized_profit: value_string(&position["unrealisedPnl"]),
+                        position_initial_margin: value_string(&position["positionIM"]),
+                        leverage: value_string(&position["leverage"]),
+                        isolated: position["tradeMode"].as_i64() == Some(1),
+                        entry_price: value_string(&position["avgPrice"]),
+                        position_side: match position["positionIdx"].as_i64() {
+                            Some(1) => "LONG",
+                            Some(2) => "SHORT",
+                            _ => "BOTH",
+                        }
+                        .to_string(),
+                        position_amt: position_amt.to_string(),
+                        ..Default::default()
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    FuturesAccountInfo {
+        can_trade: true,
+        update_time: chrono::Utc::now().timestamp_millis(),
+        total_initial_margin: value_string(&account["totalInitialMargin"]),
+        total_maint_margin: value_string(&account["totalMaintenanceMargin"]),
+        total_wallet_balance: value_string(&account["totalWalletBalance"]),
+        total_unrealized_profit: value_string(&account["totalPerpUPL"]),
+        total_margin_balance: value_string(&account["totalMarginBalance"]),
+        available_balance: value_string(&account["totalAvailableBalance"]),
+        assets,
+        positions,
+        ..Default::default()
+    }
+}
+
+/*
+헤지 모드 주문의 positionIdx (단방향 모드면 None)
+- 헤지 모드는 심볼 포지션이 positionIdx 1(Buy) / 2(Sell) 두 개로 조회됨
+- 반대 방향 포지션이 있으면 그 포지션을 줄이고, 없으면 주문 방향으로 진입
+*/
+async fn position_idx(
+    client: &reqwest::Client,
+    api_key: &str,
+    api_secret: &str,
+    symbol: &str,
+    trade_type: TradeType,
+) -> Result<Option<i64>, Box<dyn std::error::Error>> {
+    let positions = private_get(
+        client,
+        api_key,
+        api_secret,
+        "/position/list",
+        &format!("category=linear&symbol={}", symbol),
+    )
+    .await?;
+    let list = positions["list"].as_array().cloned().unwrap_or_default();
+    if !list
+        .iter()
+        .any(|position| position["positionIdx"].as_i64().unwrap_or(0) != 0)
+    {
+        return Ok(None);
+    }
+
+    let (opposite, same) = match trade_type {
+        TradeType::Buy => (2, 1),
+        TradeType::Sell => (1, 2),
+    };
+    let has_opposite = list.iter().any(|position| {
+        position["positionIdx"].as_i64() == Some(opposite) && value_f64(&position["size"]) != 0.0
+    });
+    Ok(Some(if has_opposite { opposite } else { same }))
+}
+
+/*
+bybit 시장가 주문 (linear)
+- 헤지 모드 계정은 positionIdx 포함
+*/
+pub async fn execute_trade(
+    symbol: Symbol,
+    trade_type: TradeType,
+    price: f64,
+    amount: f64,
+    mut alert_sender: mpsc::Sender<(String, AlertType)>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let api_key = env::var("BYBIT_API_KEY")?;
+    let api_secret = env::var("BYBIT_API_SECRET")?;
+
+    let client = reqwest::Client::new();
+
+    let amount = adjust_precision(amount, symbol.quantity_precision);
+    let mut body = serde_json::json!({
+        "category": "linear",
+        "symbol": symbol.exchange_symbol,
+        "side": match trade_type {
+            TradeType::Buy => "Buy",
+            TradeType::Sell => "Sell",
+        },
+        "orderType": "Market",
+        "qty": amount.to_string(),
+    });
+    if let Some(idx) = position_idx(
+        &client,
+        &api_key,
+        &api_secret,
+        &symbol.exchange_symbol,
+        trade_type,
+    )
+    .await?
+    {
+        body["positionIdx"] = idx.into();
+    }
+    let body = body.to_string();
+
+    println!("Sending Bybit order: {}", body);
+
+    let url = format!("{}/order/create", uc::BYBIT_API_ADDRESS);
+    let response = sign_request(client.post(&url), &api_key, &api_secret, &body)
+        .header("Content-Type", "application/json")
+        .body(body.clone())
+        .send()
+        .await?;
+
+    match bybit_result(response).await.map_err(|e| e.to_string()) {
+        Ok(result) => {
+            // 시장가 주문 응답에는 체결가가 없으므로 주문 시점 가격으로 표시
+            let message = format!(
+                "{} order success (order id: {}):\nquantity: {} {}\nEstimated price: {} {}\nTotal amount: {:.2} {}",
+                match trade_type {
+                    TradeType::Buy => "롱",
+                    TradeType::Sell => "숏",
+                },
+                result["orderId"].as_str().unwrap_or_default(),
+                amount,
+                symbol.base,
+                price,
+                symbol.quote,
+                amount * price,
+                symbol.quote
+            );
+            println!("{}: {}", ul::ORDER_SUCCESS, message);
+
+            alert_sender
+                .send((
+                    message,
+                    match trade_type {
+                        TradeType::Buy => AlertType::Buy,
+                        TradeType::Sell => AlertType::Sell,
+                    },
+                ))
+                .await?;
+            Ok(())
+        }
+        Err(e) => {
+            let error_message = format!("{}: {}", ul::ORDER_FAIL, e);
+            println!("{}", error_message);
+
+            alert_sender
+                .send((error_message.clone(), AlertType::Error))
+                .await?;
+
+            Err(error_message.into())
+        }
+    }
+}
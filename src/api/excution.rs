@@ -1,5 +1,5 @@
-use crate::api::binance::get_symbol_info;
-use crate::uc;
+use crate::api::{bybit, okx, MarketType};
+use crate::models::Symbol;
 use crate::ul;
 use crate::utils::adjust_precision;
 use crate::utils::hmac_sha256;
@@ -10,19 +10,25 @@ use iced::futures::channel::mpsc;
 
 use std::env;
 pub async fn execute_trade(
-    selected_coin: String,
+    symbol: Symbol,
     trade_type: TradeType,
     price: f64,
     mut amount: f64,
     mut alert_sender: mpsc::Sender<(String, AlertType)>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let symbol = format!("{}USDT", selected_coin);
-
-    // 심볼 정보 가져오기
-    let (quantity_precision, _) = get_symbol_info(&symbol).await?;
+    let market_type = symbol.market;
+    match market_type {
+        MarketType::BybitLinear => {
+            return bybit::execute_trade(symbol, trade_type, price, amount, alert_sender).await
+        }
+        MarketType::OkxSwap => {
+            return okx::execute_trade(symbol, trade_type, price, amount, alert_sender).await
+        }
+        _ => {}
+    }
 
-    // 수량 정밀도 조정
-    amount = adjust_precision(amount, quantity_precision);
+    // 수량 정밀도 조정 (exchangeInfo 기준)
+    amount = adjust_precision(amount, symbol.quantity_precision);
 
     let api_key = env::var("BINANCE_API_KEY")?;
     let api_secret = env::var("BINANCE_API_SECRET")?;
@@ -35,7 +41,7 @@ pub async fn execute_trade(
 
     let params = format!(
         "symbol={}&side={}&type=MARKET&quantity={}&timestamp={}",
-        symbol, side, amount, timestamp
+        symbol.exchange_symbol, side, amount, timestamp
     );
 
     println!("Sending order with params: {}", params);
@@ -43,7 +49,7 @@ pub async fn execute_trade(
     let signature = hmac_sha256(&api_secret, &params);
     let url = format!(
         "{}/order?{}&signature={}",
-        uc::BINANCE_FAPI_ADDRESS,
+        market_type.rest_address(),
         params,
         signature
     );
@@ -83,21 +89,57 @@ pub async fn execute_trade(
         let result: serde_json::Value = serde_json::from_str(&response_text)?;
 
         let executed_qty = result["executedQty"].as_str().unwrap_or("0");
-        let avg_price = result["avgPrice"].as_str().unwrap_or("0");
         let order_id = result["orderId"].as_u64().unwrap_or(0);
-
-        let message = format!(
-            "{} order success (order number: {}):\nquantity: {} {}\nAverage price: {} USDT\nTotal amount: {:.2} USDT",
-            match trade_type {
-                TradeType::Buy => "롱",
-                TradeType::Sell => "숏",
-            },
-            order_id,
-            executed_qty,
-            selected_coin,
-            avg_price,
-            executed_qty.parse::<f64>().unwrap_or(0.0) * avg_price.parse::<f64>().unwrap_or(0.0)
-        );
+        // 현물 응답에는 avgPrice 가 없으므로 체결 금액 / 체결 수량으로 계산
+        let avg_price = match market_type {
+            MarketType::Spot => {
+                let quote_qty = result["cummulativeQuoteQty"]
+                    .as_str()
+                    .and_then(|q| q.parse::<f64>().ok())
+                    .unwrap_or(0.0);
+                let qty = executed_qty.parse::<f64>().unwrap_or(0.0);
+                if qty > 0.0 {
+                    format!("{}", quote_qty / qty)
+                } else {
+                    "0".to_string()
+                }
+            }
+            // USDⓈ-M / COIN-M (Bybit / OKX 는 위에서 각 거래소 모듈로 처리)
+            _ => result["avgPrice"].as_str().unwrap_or("0").to_string(),
+        };
+
+        let side = match (market_type, trade_type) {
+            (MarketType::Spot, TradeType::Buy) => "매수",
+            (MarketType::Spot, TradeType::Sell) => "매도",
+            (_, TradeType::Buy) => "롱",
+            (_, TradeType::Sell) => "숏",
+        };
+        // COIN-M 은 계약 수 단위이므로 체결 금액을 기초 자산(cumBase)으로 표시
+        let message = if symbol.is_inverse() {
+            format!(
+                "{} order success (order number: {}):\nContracts: {} {}\nAverage price: {} {}\nTotal amount: {} {}",
+                side,
+                order_id,
+                executed_qty,
+                symbol.display_name,
+                avg_price,
+                symbol.quote,
+                result["cumBase"].as_str().unwrap_or("0"),
+                symbol.base
+            )
+        } else {
+            format!(
+                "{} order success (order number: {}):\nquantity: {} {}\nAverage price: {} {}\nTotal amount: {:.2} {}",
+                side,
+                order_id,
+                executed_qty,
+                symbol.base,
+                avg_price,
+                symbol.quote,
+                executed_qty.parse::<f64>().unwrap_or(0.0) * avg_price.parse::<f64>().unwrap_or(0.0),
+                symbol.quote
+            )
+        };
 
         println!("{}: {}", ul::ORDER_SUCCESS, message);
 
@@ -1,7 +1,101 @@
+use crate::uc;
+use crate::CandleType;
 use serde::Deserialize;
 pub mod account;
 pub mod binance;
+pub mod bybit;
 pub mod excution;
+pub mod okx;
+pub mod replay;
+
+//마켓 종류 (거래소 + 상품)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum MarketType {
+    #[default]
+    Futures, // USDⓈ-M 선물 (fapi)
+    Spot,        // 현물 (api)
+    CoinM,       // COIN-M 선물 (dapi, 인버스 계약)
+    BybitLinear, // Bybit USDT 무기한 (v5 linear)
+    OkxSwap,     // OKX 무기한 스왑 (v5 SWAP)
+}
+
+impl MarketType {
+    pub const ALL: [MarketType; 5] = [
+        MarketType::Futures,
+        MarketType::Spot,
+        MarketType::CoinM,
+        MarketType::BybitLinear,
+        MarketType::OkxSwap,
+    ];
+
+    //REST 주소
+    pub fn rest_address(&self) -> &'static str {
+        match self {
+            MarketType::Futures => uc::BINANCE_FAPI_ADDRESS,
+            MarketType::Spot => uc::BINANCE_SPOT_API_ADDRESS,
+            MarketType::CoinM => uc::BINANCE_DAPI_ADDRESS,
+            MarketType::BybitLinear => uc::BYBIT_API_ADDRESS,
+            MarketType::OkxSwap => uc::OKX_API_ADDRESS,
+        }
+    }
+
+    //WebSocket 주소
+    pub fn ws_address(&self) -> &'static str {
+        match self {
+            MarketType::Futures => uc::BINANCE_FWSS_ADDRESS,
+            MarketType::Spot => uc::BINANCE_SPOT_WSS_ADDRESS,
+            MarketType::CoinM => uc::BINANCE_DWSS_ADDRESS,
+            MarketType::BybitLinear => uc::BYBIT_LINEAR_WSS_ADDRESS,
+            MarketType::OkxSwap => uc::OKX_PUBLIC_WSS_ADDRESS,
+        }
+    }
+
+    //로컬 저장소 키 (거래소_상품)
+    pub fn venue_key(&self) -> &'static str {
+        match self {
+            MarketType::Futures => "binance_usdm",
+            MarketType::Spot => "binance_spot",
+            MarketType::CoinM => "binance_coinm",
+            MarketType::BybitLinear => "bybit_linear",
+            MarketType::OkxSwap => "okx_swap",
+        }
+    }
+
+    //마켓 변경 시 기본 심볼
+    pub fn default_symbol(&self) -> &'static str {
+        match self {
+            MarketType::Futures | MarketType::Spot | MarketType::BybitLinear => "BTCUSDT",
+            MarketType::CoinM => "BTCUSD_PERP",
+            MarketType::OkxSwap => "BTC-USDT-SWAP",
+        }
+    }
+
+    //거래소가 제공하는 캔들 interval (나머지는 리샘플링)
+    pub fn supports(&self, candle_type: &CandleType) -> bool {
+        !matches!(
+            (self, candle_type),
+            (_, CandleType::Custom(_))
+                | (
+                    MarketType::BybitLinear,
+                    CandleType::Hour8 | CandleType::Day3
+                )
+                | (MarketType::OkxSwap, CandleType::Hour8)
+        )
+    }
+}
+
+impl std::fmt::Display for MarketType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MarketType::Futures => write!(f, "USDⓈ-M"),
+            MarketType::Spot => write!(f, "Spot"),
+            MarketType::CoinM => write!(f, "COIN-M"),
+            MarketType::BybitLinear => write!(f, "Bybit"),
+            MarketType::OkxSwap => write!(f, "OKX"),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct BinanceTrade {
     #[serde(rename = "E")]
@@ -16,8 +110,23 @@ pub struct BinanceTrade {
     pub transaction_time: i64,
     #[serde(rename = "m")]
     pub is_buyer_maker: bool,
+    #[serde(rename = "f", default)]
+    pub first_trade_id: u64, // aggTrade 에 묶인 첫 체결 id (다른 거래소는 0)
+    #[serde(rename = "l", default)]
+    pub last_trade_id: u64, // aggTrade 에 묶인 마지막 체결 id
 }
-#[derive(Debug, Deserialize, Clone)]
+
+impl BinanceTrade {
+    //묶인 체결 수 (aggTrade 가 아니면 1)
+    pub fn trade_count(&self) -> u32 {
+        if self.last_trade_id >= self.first_trade_id && self.last_trade_id > 0 {
+            (self.last_trade_id - self.first_trade_id + 1) as u32
+        } else {
+            1
+        }
+    }
+}
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct FuturesAccountInfo {
     #[serde(rename = "feeTier")]
     pub fee_tier: i32,
@@ -29,32 +138,32 @@ pub struct FuturesAccountInfo {
     pub can_withdraw: bool,
     #[serde(rename = "updateTime")]
     pub update_time: i64,
-    #[serde(rename = "totalInitialMargin")]
+    #[serde(rename = "totalInitialMargin", default)]
     pub total_initial_margin: String,
-    #[serde(rename = "totalMaintMargin")]
+    #[serde(rename = "totalMaintMargin", default)]
     pub total_maint_margin: String,
-    #[serde(rename = "totalWalletBalance")]
+    #[serde(rename = "totalWalletBalance", default)]
     pub total_wallet_balance: String,
-    #[serde(rename = "totalUnrealizedProfit")]
+    #[serde(rename = "totalUnrealizedProfit", default)]
     pub total_unrealized_profit: String,
-    #[serde(rename = "totalMarginBalance")]
+    #[serde(rename = "totalMarginBalance", default)]
     pub total_margin_balance: String,
-    #[serde(rename = "totalPositionInitialMargin")]
+    #[serde(rename = "totalPositionInitialMargin", default)]
     pub total_position_initial_margin: String,
-    #[serde(rename = "totalOpenOrderInitialMargin")]
+    #[serde(rename = "totalOpenOrderInitialMargin", default)]
     pub total_open_order_initial_margin: String,
-    #[serde(rename = "totalCrossWalletBalance")]
+    #[serde(rename = "totalCrossWalletBalance", default)]
     pub total_cross_wallet_balance: String,
-    #[serde(rename = "totalCrossUnPnl")]
+    #[serde(rename = "totalCrossUnPnl", default)]
     pub total_cross_un_pnl: String,
-    #[serde(rename = "availableBalance")]
+    #[serde(rename = "availableBalance", default)]
     pub available_balance: String,
-    #[serde(rename = "maxWithdrawAmount")]
+    #[serde(rename = "maxWithdrawAmount", default)]
     pub max_withdraw_amount: String,
     pub assets: Vec<FuturesAsset>,
     pub positions: Vec<FuturesPosition>,
 }
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct FuturesAsset {
     pub asset: String,
     #[serde(rename = "walletBalance")]
@@ -80,7 +189,7 @@ pub struct FuturesAsset {
     #[serde(rename = "availableBalance")]
     pub available_balance: String,
 }
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct FuturesPosition {
     pub symbol: String,
     #[serde(rename = "initialMargin")]
@@ -95,13 +204,41 @@ pub struct FuturesPosition {
     pub isolated: bool,
     #[serde(rename = "entryPrice")]
     pub entry_price: String,
-    #[serde(rename = "maxNotional")]
-    pub max_notional: String,
+    #[serde(rename = "maxNotional", default)]
+    pub max_notional: String, // COIN-M 응답에는 없음
     #[serde(rename = "positionSide")]
     pub position_side: String,
     #[serde(rename = "positionAmt")]
     pub position_amt: String,
-    
+}
+#[derive(Debug, Deserialize, Clone)]
+pub struct SpotAccountInfo {
+    #[serde(rename = "canTrade")]
+    pub can_trade: bool,
+    #[serde(rename = "updateTime")]
+    pub update_time: i64,
+    pub balances: Vec<SpotBalance>,
+}
+#[derive(Debug, Deserialize, Clone)]
+pub struct SpotBalance {
+    pub asset: String,
+    pub free: String,
+    pub locked: String,
+}
+impl SpotAccountInfo {
+    //자산별 잔고 (free, locked)
+    pub fn balance(&self, asset: &str) -> (f64, f64) {
+        self.balances
+            .iter()
+            .find(|b| b.asset == asset)
+            .map(|b| {
+                (
+                    b.free.parse::<f64>().unwrap_or(0.0),
+                    b.locked.parse::<f64>().unwrap_or(0.0),
+                )
+            })
+            .unwrap_or((0.0, 0.0))
+    }
 }
 #[derive(Debug, Deserialize, Clone)]
 struct BinanceCandle {
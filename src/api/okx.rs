@@ -0,0 +1,623 @@
+use crate::api::{FuturesAccountInfo, FuturesAsset, FuturesPosition, MarketType};
+use crate::models::Symbol;
+use crate::store::tape::{TapeEvent, TapeRecorder};
+use crate::uc;
+use crate::ul;
+use crate::utils::{adjust_precision, hmac_sha256_base64, step_precision};
+use crate::AlertType;
+use crate::BinanceTrade;
+use crate::Message;
+use crate::TradeType;
+use crate::{CandleType, Candlestick};
+use async_stream::stream;
+use futures_util::{SinkExt, Stream};
+use iced::futures::{channel::mpsc, StreamExt};
+use iced::time::Duration;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message as ME};
+
+/*
+OKX v5 서명
+- timestamp(ISO 8601) + method + request path(쿼리 포함) + body 를 HMAC-SHA256 후 base64
+- OK-ACCESS-* 헤더 + passphrase 로 전송
+*/
+fn sign_request(
+    request: reqwest::RequestBuilder,
+    credentials: &(String, String, String),
+    method: &str,
+    request_path: &str,
+    body: &str,
+) -> reqwest::RequestBuilder {
+    let (api_key, api_secret, passphrase) = credentials;
+    let timestamp = chrono::Utc::now()
+        .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+        .to_string();
+    let signature = hmac_sha256_base64(
+        api_secret,
+        &format!("{}{}{}{}", timestamp, method, request_path, body),
+    );
+
+    request
+        .header("OK-ACCESS-KEY", api_key)
+        .header("OK-ACCESS-SIGN", signature)
+        .header("OK-ACCESS-TIMESTAMP", timestamp)
+        .header("OK-ACCESS-PASSPHRASE", passphrase)
+}
+
+//OKX API KEY / SECRET / PASSPHRASE
+fn credentials() -> Result<(String, String, String), env::VarError> {
+    Ok((
+        env::var("OKX_API_KEY")?,
+        env::var("OKX_API_SECRET")?,
+        env::var("OKX_API_PASSPHRASE")?,
+    ))
+}
+
+//OKX 응답의 code 확인 후 data 반환
+async fn okx_data(
+    response: reqwest::Response,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let text = response.text().await?;
+    let json: serde_json::Value = serde_json::from_str(&text)?;
+
+    if json["code"].as_str() != Some("0") {
+        return Err(format!("OKX API error: {}", text).into());
+    }
+    Ok(json["data"].clone())
+}
+
+//okx 공개 API 조회 (서명 없음)
+async fn public_get(request_path: &str) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let url = format!("{}{}", uc::OKX_API_ADDRESS, request_path);
+    let response = reqwest::Client::new().get(&url).send().await?;
+    okx_data(response).await
+}
+
+//okx 개인 API 조회 (서명 포함)
+async fn private_get(
+    client: &reqwest::Client,
+    credentials: &(String, String, String),
+    request_path: &str,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let url = format!("{}{}", uc::OKX_API_ADDRESS, request_path);
+    let response = sign_request(client.get(&url), credentials, "GET", request_path, "")
+        .send()
+        .await?;
+    okx_data(response).await
+}
+
+/*
+okx trades channel connection
+- binance_connection 과 같은 메세지(UpdatePrice / AddCandlestick)를 보냄
+- 체결 수량(sz)은 계약 수이므로 계약 크기를 곱해 기초 자산 수량으로 변환
+*/
+pub fn okx_connection() -> impl Stream<Item = Message> {
+    stream! {
+        let (tx, mut rx) = mpsc::channel(100);
+        let mut current_symbol = Symbol::fallback(MarketType::OkxSwap, "BTC");
+        let mut last_prices: HashMap<String, f64> = HashMap::new();
+        let mut recorder = TapeRecorder::from_env();
+
+        yield Message::WebSocketInit(tx.clone());
+
+        loop {
+            match connect_async(MarketType::OkxSwap.ws_address()).await {
+                Ok((mut ws_stream, _)) => {
+                    println!("Connected to OKX stream for {}", current_symbol.exchange_symbol);
+                    yield Message::StreamConnected;
+
+                    let subscribe = serde_json::json!({
+                        "op": "subscribe",
+                        "args": [{ "channel": "trades", "instId": current_symbol.exchange_symbol }],
+                    });
+                    if let Err(e) = ws_stream.send(ME::Text(subscribe.to_string().into())).await {
+                        println!("OKX subscribe error: {}", e);
+                    }
+
+                    // okx 는 30초 동안 메세지가 없으면 연결을 끊으므로 ping 전송
+                    let mut ping = tokio::time::interval(Duration::from_secs(25));
+
+                    loop {
+                        tokio::select! {
+                            Some(new_symbol) = rx.next() => {
+                                println!("Switching to OKX coin: {}", new_symbol);
+                                current_symbol = new_symbol;
+                                break;
+                            }
+                            _ = ping.tick() => {
+                                if let Err(e) = ws_stream.send(ME::Text("ping".into())).await {
+                                    println!("OKX ping error: {}", e);
+                                    break;
+                                }
+                            }
+                            Some(msg) = ws_stream.next() => {
+                                match msg {
+                                    Ok(ME::Text(text)) => {
+                                        let trades = parse_trades(&text, current_symbol.contract_size);
+                                        if let (Some(recorder), false) = (recorder.as_mut(), trades.is_empty()) {
+                                            recorder.record(MarketType::OkxSwap, TapeEvent::Trade, &text);
+                                        }
+                                        for trade in trades {
+                                            let symbol = trade.symbol.clone();
+
+                                            if let Ok(price) = trade.price.parse::<f64>() {
+                                                let prev_price = *last_prices.get(&symbol).unwrap_or(&price);
+                                                let change_percent = if prev_price != 0.0 {
+                                                    ((price - prev_price) / prev_price) * 100.0
+                                                } else {
+                                                    0.0
+                                                };
+
+                                                last_prices.insert(symbol.clone(), price);
+
+                                                yield Message::UpdatePrice(
+                                                    symbol.clone(),
+                                                    price,
+                                                    change_percent
+                                                );
+                                                yield Message::AddCandlestick((
+                                                    trade.transaction_time as u64,
+                                                    trade
+                                                ));
+                                            }
+                                        }
+                                    }
+                                    Err(e) => {
+                                        println!("OKX WebSocket error: {}", e);
+                                        break;
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                    }
+                    let _ = ws_stream.close(None).await;
+                }
+                Err(e) => {
+                    println!("OKX connection error: {}", e);
+                    yield Message::Error;
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                }
+            }
+        }
+    }
+}
+
+/*
+trades 메세지 -> BinanceTrade
+- side 는 taker 방향이므로 sell 이면 매수자가 maker
+*/
+pub fn parse_trades(text: &str, contract_size: f64) -> Vec<BinanceTrade> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(text) else {
+        return vec![];
+    };
+    if json["arg"]["channel"].as_str() != Some("trades") {
+        return vec![];
+    }
+
+    json["data"]
+        .as_array()
+        .map(|trades| {
+            trades
+                .iter()
+                .filter_map(|trade| {
+                    let time = trade["ts"].as_str()?.parse::<i64>().ok()?;
+                    let contracts = trade["sz"].as_str()?.parse::<f64>().ok()?;
+                    Some(BinanceTrade {
+                        event_time: time,
+                        symbol: trade["instId"].as_str()?.to_string(),
+                        price: trade["px"].as_str()?.to_string(),
+                        quantity: (contracts * contract_size).to_string(),
+                        transaction_time: time,
+                        is_buyer_maker: trade["side"].as_str() == Some("sell"),
+                        first_trade_id: 0,
+                        last_trade_id: 0,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/*
+okx 캔들 조회 (최신순 응답, 최근 캔들 최대 300개, 과거 캔들 최대 100개)
+- 일봉은 UTC 기준(1Dutc)으로 바이낸스와 맞춤
+- 거래량은 계약 수 대신 기초 자산 수량(volCcy) 사용
+- end_time 이 있으면 history-candles 에서 그 시각 이전 캔들 조회
+*/
+pub async fn fetch_candles_async(
+    symbol: &Symbol,
+    candle_type: &CandleType,
+    end_time: Option<u64>,
+) -> Result<BTreeMap<u64, Candlestick>, Box<dyn std::error::Error>> {
+    // OKX 는 8시간봉 없음 (4시간봉을 리샘플링, MarketType::supports)
+    let bar = match candle_type {
+        CandleType::Minute1 => "1m",
+        CandleType::Minute3 => "3m",
+        CandleType::Minute5 => "5m",
+        CandleType::Minute15 => "15m",
+        CandleType::Minute30 => "30m",
+        CandleType::Hour1 => "1H",
+        CandleType::Hour2 => "2H",
+        CandleType::Hour4 => "4H",
+        CandleType::Hour6 => "6Hutc",
+        CandleType::Hour12 => "12Hutc",
+        CandleType::Day => "1Dutc",
+        CandleType::Day3 => "3Dutc",
+        CandleType::Week => "1Wutc",
+        CandleType::Month => "1Mutc",
+        CandleType::Hour8 | CandleType::Custom(_) => {
+            return Err(format!("OKX does not support {} candles", candle_type).into())
+        }
+    };
+
+    let request_path = match end_time {
+        Some(end_time) => format!(
+            "/api/v5/market/history-candles?instId={}&bar={}&limit=100&after={}",
+            symbol.exchange_symbol,
+            bar,
+            end_time + 1
+        ),
+        None => format!(
+            "/api/v5/market/candles?instId={}&bar={}&limit=300",
+            symbol.exchange_symbol, bar
+        ),
+    };
+    let data = public_get(&request_path).await?;
+
+    //[ts, open, high, low, close, vol, volCcy, volCcyQuote, confirm]
+    let result: BTreeMap<u64, Candlestick> = data
+        .as_array()
+        .ok_or("Invalid candles response")?
+        .iter()
+        .filter_map(|candle| {
+            let field = |i: usize| candle[i].as_str();
+            let candlestick = Candlestick {
+                open: field(1)?.parse().ok()?,
+                high: field(2)?.parse().ok()?,
+                low: field(3)?.parse().ok()?,
+                close: field(4)?.parse().ok()?,
+                volume: field(6)?.parse().ok()?,
+                quote_volume: field(7)?.parse().ok()?,
+                ..Default::default() // 체결 수 / 시장가 매수 거래량은 제공하지 않음
+            };
+            Some((field(0)?.parse::<u64>().ok()?, candlestick))
+        })
+        .filter(|(_, candle)| {
+            candle.open > 0.0 && candle.high > 0.0 && candle.low > 0.0 && candle.close > 0.0
+        })
+        .collect();
+
+    if result.is_empty() {
+        Err("No valid candles returned".into())
+    } else {
+        Ok(result)
+    }
+}
+
+//24시간 거래대금 순 심볼 목록 (volCcy24h * 현재가)
+pub async fn get_top_volume_pairs() -> Result<Vec<(String, f64)>, Box<dyn std::error::Error>> {
+    let data = public_get("/api/v5/market/tickers?instType=SWAP").await?;
+
+    let mut pairs: Vec<(String, f64)> = data
+        .as_array()
+        .ok_or("Invalid tickers response")?
+        .iter()
+        .filter_map(|item| {
+            let symbol = item["instId"].as_str()?.to_string();
+            let volume = item["volCcy24h"].as_str()?.parse::<f64>().ok()?;
+            let last = item["last"].as_str()?.parse::<f64>().ok()?;
+            Some((symbol, volume * last))
+        })
+        .collect();
+
+    pairs.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    Ok(pairs)
+}
+
+/*
+거래 가능한 USDT / USDC 무기한 스왑 목록
+- contract_size 는 계약 1개당 기초 자산 수량(ctVal)
+- quantity_precision 은 계약 수 단위(lotSz)
+*/
+pub async fn get_exchange_symbols() -> Result<Vec<Symbol>, Box<dyn std::error::Error>> {
+    let data = public_get("/api/v5/public/instruments?instType=SWAP").await?;
+
+    let symbols = data
+        .as_array()
+        .ok_or("Invalid instruments response")?
+        .iter()
+        .filter_map(|item| {
+            if item["state"].as_str() != Some("live") || item["ctType"].as_str() != Some("linear") {
+                return None;
+            }
+            let mut symbol = Symbol::from_okx_inst_id(item["instId"].as_str()?)?;
+            if !uc::QUOTE_ASSETS.contains(&symbol.quote.as_str()) {
+                return None;
+            }
+
+            symbol.contract_size = item["ctVal"].as_str()?.parse().ok()?;
+            if let Some(lot) = item["lotSz"].as_str() {
+                symbol.quantity_precision = step_precision(lot);
+            }
+            if let Some(tick) = item["tickSz"].as_str() {
+                symbol.price_precision = step_precision(tick);
+            }
+            Some(symbol)
+        })
+        .collect();
+
+    Ok(symbols)
+}
+
+/*
+okx 계정 연결
+- 잔고 + SWAP 포지션을 FuturesAccountInfo 로 변환
+- 포지션 수량(계약 수)은 ctVal 을 곱해 기초 자산 수량으로 변환
+*/
+pub fn okx_account_connection() -> impl Stream<Item = Message> {
+    stream! {
+        let credentials = match credentials() {
+            Ok(credentials) => credentials,
+            Err(e) => {
+                println!("OKX API KEY/SECRET/PASSPHRASE error: {}", e);
+                yield Message::FetchError("OKX API KEY not found".to_string());
+                return;
+            }
+        };
+
+        // 계약 크기 (instId -> ctVal)
+        let contract_sizes: HashMap<String, f64> = get_exchange_symbols()
+            .await
+            .map(|symbols| {
+                symbols
+                    .into_iter()
+                    .map(|symbol| (symbol.exchange_symbol, symbol.contract_size))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let client = reqwest::Client::new();
+
+        loop {
+            let balance = private_get(&client, &credentials, "/api/v5/account/balance")
+                .await
+                .map_err(|e| e.to_string());
+            let positions = private_get(&client, &credentials, "/api/v5/account/positions?instType=SWAP")
+                .await
+                .map_err(|e| e.to_string());
+
+            match (balance, positions) {
+                (Ok(balance), Ok(positions)) => {
+                    let account_info = to_account_info(&balance, &positions, &contract_sizes);
+                    for position in &account_info.positions {
+                        let entry_price = position.entry_price.parse::<f64>().unwrap_or(0.0);
+                        if position.position_amt.parse::<f64>().unwrap_or(0.0) != 0.0 && entry_price > 0.0 {
+                            yield Message::UpdateAveragePrice(position.symbol.clone(), entry_price);
+                        }
+                    }
+                    yield Message::UpdateOkxAccountInfo(account_info);
+                }
+                (Err(e), _) | (_, Err(e)) => {
+                    println!("OKX account error: {}", e);
+                    yield Message::FetchError(format!("OKX account error: {}", e));
+                }
+            }
+
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        }
+    }
+}
+
+//okx 숫자 문자열 필드 (빈 문자열은 0)
+fn value_f64(value: &serde_json::Value) -> f64 {
+    value
+        .as_str()
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(0.0)
+}
+
+fn value_string(value: &serde_json::Value) -> String {
+    match value.as_str() {
+        Some(v) if !v.is_empty() => v.to_string(),
+        _ => "0".to_string(),
+    }
+}
+
+//balance / positions 응답 -> FuturesAccountInfo
+fn to_account_info(
+    balance: &serde_json::Value,
+    positions: &serde_json::Value,
+    contract_sizes: &HashMap<String, f64>,
+) -> FuturesAccountInfo {
+    let account = &balance[0];
+
+    let assets = account["details"]
+        .as_array()
+        .map(|details| {
+            details
+                .iter()
+                .map(|detail| FuturesAsset {
+                    asset: value_string(&detail["ccy"]),
+                    wallet_balance: value_string(&detail["cashBal"]),
+                    unrealized_profit: value_string(&detail["upl"]),
+                    margin_balance: value_string(&detail["eq"]),
+                    maint_margin: value_string(&detail["mmr"]),
+                    initial_margin: value_string(&detail["imr"]),
+                    position_initial_margin: value_string(&detail["imr"]),
+                    available_balance: value_string(&detail["availBal"]),
+                    ..Default::default()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let positions = positions
+        .as_array()
+        .map(|list| {
+            list.iter()
+                .map(|position| {
+                    let inst_id = value_string(&position["instId"]);
+                    let contract_size = contract_sizes.get(&inst_id).copied().unwrap_or(1.0);
+                    // net 모드는 pos 부호가 방향, long/short 모드는 posSide 가 방향
+                    let contracts = value_f64(&position["pos"]);
+                    let contracts = match position["posSide"].as_str() {
+                        Some("short") => -contracts.abs(),
+                        Some("long") => contracts.abs(),
+                        _ => contracts,
+                    };
+                    FuturesPosition {
+                        symbol: inst_id,
+                        initial_margin: value_string(&position["imr"]),
+                        maint_margin: value_string(&position["mmr"]),
+                        unrealized_profit: value_string(&position["upl"]),
+                        position_initial_margin: value_string(&position["imr"]),
+                        leverage: value_string(&position["lever"]),
+                        isolated: position["mgnMode"].as_str() == Some("isolated"),
+                        entry_price: value_string(&position["avgPx"]),
+                        position_side: "BOTH".to_string(),
+                        position_amt: (contracts * contract_size).to_string(),
+                        ..Default::default()
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    FuturesAccountInfo {
+        can_trade: true,
+        update_time: chrono::Utc::now().timestamp_millis(),
+        total_initial_margin: value_string(&account["imr"]),
+        total_maint_margin: value_string(&account["mmr"]),
+        total_wallet_balance: value_string(&account["totalEq"]),
+        total_unrealized_profit: value_string(&account["upl"]),
+        total_margin_balance: value_string(&account["adjEq"]),
+        assets,
+        positions,
+        ..Default::default()
+    }
+}
+
+/*
+long/short 모드 주문의 posSide (net 모드면 None)
+- 반대 방향 포지션이 있으면 그 포지션을 줄이고, 없으면 주문 방향으로 진입
+*/
+async fn position_side(
+    client: &reqwest::Client,
+    credentials: &(String, String, String),
+    inst_id: &str,
+    trade_type: TradeType,
+) -> Result<Option<&'static str>, Box<dyn std::error::Error>> {
+    let config = private_get(client, credentials, "/api/v5/account/config").await?;
+    if config[0]["posMode"].as_str() != Some("long_short_mode") {
+        return Ok(None);
+    }
+
+    let positions = private_get(
+        client,
+        credentials,
+        &format!("/api/v5/account/positions?instId={}", inst_id),
+    )
+    .await?;
+    let (opposite, same) = match trade_type {
+        TradeType::Buy => ("short", "long"),
+        TradeType::Sell => ("long", "short"),
+    };
+    let has_opposite = positions.as_array().is_some_and(|list| {
+        list.iter().any(|position| {
+            position["posSide"].as_str() == Some(opposite) && value_f64(&position["pos"]) != 0.0
+        })
+    });
+    Ok(Some(if has_opposite { opposite } else { same }))
+}
+
+/*
+okx 시장가 주문 (교차 마진)
+- amount 는 기초 자산 수량이므로 계약 수로 변환 후 주문
+- long/short 모드 계정은 posSide 포함
+*/
+pub async fn execute_trade(
+    symbol: Symbol,
+    trade_type: TradeType,
+    price: f64,
+    amount: f64,
+    mut alert_sender: mpsc::Sender<(String, AlertType)>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let credentials = credentials()?;
+
+    let client = reqwest::Client::new();
+
+    let contracts = adjust_precision(amount / symbol.contract_size, symbol.quantity_precision);
+    let mut body = serde_json::json!({
+        "instId": symbol.exchange_symbol,
+        "tdMode": "cross",
+        "side": match trade_type {
+            TradeType::Buy => "buy",
+            TradeType::Sell => "sell",
+        },
+        "ordType": "market",
+        "sz": contracts.to_string(),
+    });
+    if let Some(pos_side) =
+        position_side(&client, &credentials, &symbol.exchange_symbol, trade_type).await?
+    {
+        body["posSide"] = pos_side.into();
+    }
+    let body = body.to_string();
+
+    println!("Sending OKX order: {}", body);
+
+    let request_path = "/api/v5/trade/order";
+    let url = format!("{}{}", uc::OKX_API_ADDRESS, request_path);
+    let response = sign_request(client.post(&url), &credentials, "POST", request_path, &body)
+        .header("Content-Type", "application/json")
+        .body(body.clone())
+        .send()
+        .await?;
+
+    match okx_data(response).await.map_err(|e| e.to_string()) {
+        Ok(data) => {
+            let quantity = contracts * symbol.contract_size;
+            // 시장가 주문 응답에는 체결가가 없으므로 주문 시점 가격으로 표시
+            let message = format!(
+                "{} order success (order id: {}):\nContracts: {} ({} {})\nEstimated price: {} {}\nTotal amount: {:.2} {}",
+                match trade_type {
+                    TradeType::Buy => "롱",
+                    TradeType::Sell => "숏",
+                },
+                data[0]["ordId"].as_str().unwrap_or_default(),
+                contracts,
+                quantity,
+                symbol.base,
+                price,
+                symbol.quote,
+                quantity * price,
+                symbol.quote
+            );
+            println!("{}: {}", ul::ORDER_SUCCESS, message);
+
+            alert_sender
+                .send((
+                    message,
+                    match trade_type {
+                        TradeType::Buy => AlertType::Buy,
+                        TradeType::Sell => AlertType::Sell,
+                    },
+                ))
+                .await?;
+            Ok(())
+        }
+        Err(e) => {
+            let error_message = format!("{}: {}", ul::ORDER_FAIL, e);
+            println!("{}", error_message);
+
+            alert_sender
+                .send((error_message.clone(), AlertType::Error))
+                .await?;
+
+            Err(error_message.into())
+        }
+    }
+}
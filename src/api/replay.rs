@@ -0,0 +1,406 @@
+use crate::api::{bybit, okx};
+use crate::models::Symbol;
+use crate::store::candles::CandleStore;
+use crate::uc;
+use crate::BinanceTrade;
+use crate::Message;
+use crate::{CandleType, Candlestick};
+use async_stream::stream;
+use flate2::read::MultiGzDecoder;
+use futures_util::Stream;
+use iced::futures::{channel::mpsc, StreamExt};
+use iced::time::Duration;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+
+//리플레이 속도
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum ReplaySpeed {
+    #[default]
+    X1, // 실제 시간
+    X10,  // 10배속
+    Max,  // 대기 없이
+    Step, // Step 명령마다 이벤트 하나
+}
+
+impl ReplaySpeed {
+    pub const ALL: [ReplaySpeed; 4] = [
+        ReplaySpeed::X1,
+        ReplaySpeed::X10,
+        ReplaySpeed::Max,
+        ReplaySpeed::Step,
+    ];
+
+    //이벤트 사이 대기 시간 (Step 은 None)
+    fn delay(&self, gap_ms: u64) -> Option<Duration> {
+        let gap_ms = gap_ms.min(uc::REPLAY_MAX_GAP_MS);
+        match self {
+            ReplaySpeed::X1 => Some(Duration::from_millis(gap_ms)),
+            ReplaySpeed::X10 => Some(Duration::from_millis(gap_ms / 10)),
+            ReplaySpeed::Max => Some(Duration::ZERO),
+            ReplaySpeed::Step => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ReplaySpeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplaySpeed::X1 => write!(f, "1x"),
+            ReplaySpeed::X10 => write!(f, "10x"),
+            ReplaySpeed::Max => write!(f, "Max"),
+            ReplaySpeed::Step => write!(f, "Step"),
+        }
+    }
+}
+
+//리플레이 데이터
+#[derive(Debug, Clone)]
+pub enum ReplaySource {
+    Tape(PathBuf),         // 기록된 체결 (data/tape/*.jsonl.gz)
+    Candles { from: u64 }, // 저장된 캔들 (from 이후)
+}
+
+//리플레이 제어 명령
+#[derive(Debug, Clone)]
+pub enum ReplayCommand {
+    Start(ReplaySource, Symbol, CandleType),
+    Speed(ReplaySpeed),
+    Step,
+    Stop,
+}
+
+/*
+리플레이 connection
+- binance_connection 과 같은 메세지(UpdatePrice / AddCandlestick)를 보냄
+- 시작 시 ReplayStarted(첫 이벤트 시각), 끝나면 ReplayFinished
+- 이벤트 사이 간격은 기록된 시각 기준 (배속 적용)
+*/
+pub fn replay_connection() -> impl Stream<Item = Message> {
+    stream! {
+        let (tx, mut rx) = mpsc::channel(100);
+        let mut speed = ReplaySpeed::default();
+        let mut pending_start = None;
+
+        yield Message::ReplayInit(tx.clone());
+
+        loop {
+            // Start 명령 대기
+            let (source, symbol, candle_type) = match pending_start.take() {
+                Some(start) => start,
+                None => loop {
+                    match rx.next().await {
+                        Some(ReplayCommand::Start(source, symbol, candle_type)) => {
+                            break (source, symbol, candle_type)
+                        }
+                        Some(ReplayCommand::Speed(new_speed)) => speed = new_speed,
+                        Some(_) => {}
+                        None => return,
+                    }
+                },
+            };
+
+            let events = match load_events(&source, &symbol, &candle_type).map_err(|e| e.to_string()) {
+                Ok(events) if !events.is_empty() => events,
+                Ok(_) => {
+                    yield Message::FetchError("No replay events for the selected symbol".to_string());
+                    continue;
+                }
+                Err(e) => {
+                    yield Message::FetchError(format!("Replay load error: {}", e));
+                    continue;
+                }
+            };
+            println!("Replaying {} events for {}", events.len(), symbol);
+            yield Message::ReplayStarted(events[0].0);
+
+            let mut last_prices: HashMap<String, f64> = HashMap::new();
+            let mut prev_time = events[0].0;
+            let mut index = 0;
+            let mut batch = 0;
+
+            'replay: while index < events.len() {
+                let (time, trade) = &events[index];
+
+                match speed.delay(time.saturating_sub(prev_time)) {
+                    // Step 명령이 올 때까지 대기
+                    None => match rx.next().await {
+                        Some(ReplayCommand::Step) => {}
+                        Some(ReplayCommand::Speed(new_speed)) => {
+                            speed = new_speed;
+                            continue;
+                        }
+                        Some(ReplayCommand::Start(source, symbol, candle_type)) => {
+                            pending_start = Some((source, symbol, candle_type));
+                            break 'replay;
+                        }
+                        Some(ReplayCommand::Stop) | None => break 'replay,
+                    },
+                    Some(delay) if delay.is_zero() => {
+                        // 최대 속도에서도 UI 가 갱신될 수 있도록 주기적으로 양보
+                        batch += 1;
+                        if batch >= uc::REPLAY_MAX_BATCH {
+                            batch = 0;
+                            tokio::time::sleep(Duration::from_millis(1)).await;
+                        }
+                        while let Ok(Some(command)) = rx.try_next() {
+                            match command {
+                                ReplayCommand::Speed(new_speed) => speed = new_speed,
+                                ReplayCommand::Start(source, symbol, candle_type) => {
+                                    pending_start = Some((source, symbol, candle_type));
+                                    break 'replay;
+                                }
+                                ReplayCommand::Stop => break 'replay,
+                                ReplayCommand::Step => {}
+                            }
+                        }
+                    }
+                    Some(delay) => {
+                        tokio::select! {
+                            _ = tokio::time::sleep(delay) => {}
+                            command = rx.next() => {
+                                match command {
+                                    Some(ReplayCommand::Speed(new_speed)) => speed = new_speed,
+                                    Some(ReplayCommand::Start(source, symbol, candle_type)) => {
+                                        pending_start = Some((source, symbol, candle_type));
+                                        break 'replay;
+                                    }
+                                    Some(ReplayCommand::Stop) | None => break 'replay,
+                                    Some(ReplayCommand::Step) => {}
+                                }
+                                // 속도가 바뀌면 남은 대기 시간을 다시 계산
+                                continue;
+                            }
+                        }
+                    }
+                }
+
+                if let Ok(price) = trade.price.parse::<f64>() {
+                    let prev_price = *last_prices.get(&trade.symbol).unwrap_or(&price);
+                    let change_percent = if prev_price != 0.0 {
+                        ((price - prev_price) / prev_price) * 100.0
+                    } else {
+                        0.0
+                    };
+                    last_prices.insert(trade.symbol.clone(), price);
+
+                    yield Message::UpdatePrice(trade.symbol.clone(), price, change_percent);
+                    yield Message::AddCandlestick((trade.transaction_time as u64, trade.clone()));
+                }
+
+                prev_time = *time;
+                index += 1;
+            }
+
+            yield Message::ReplayFinished;
+        }
+    }
+}
+
+//(재생 시각, 체결) 목록
+fn load_events(
+    source: &ReplaySource,
+    symbol: &Symbol,
+    candle_type: &CandleType,
+) -> Result<Vec<(u64, BinanceTrade)>, Box<dyn std::error::Error>> {
+    match source {
+        ReplaySource::Tape(path) => load_tape(path, symbol),
+        ReplaySource::Candles { from } => {
+            let candles =
+                CandleStore::open()?.load_range(symbol, candle_type, *from, u64::MAX >> 1)?;
+            Ok(candles
+                .iter()
+                .flat_map(|(&open_time, candle)| {
+                    let duration = candle_type.next_open_time(open_time) - open_time;
+                    candle_ticks(symbol, open_time, candle, duration)
+                })
+                .collect())
+        }
+    }
+}
+
+/*
+기록된 tape 읽기
+- 체결(kind=trade)만 사용, 재생 시각은 수신 시각(recv)
+- 거래소는 원본 메세지 형태로 판단 (바이낸스 e / bybit topic / okx arg)
+*/
+fn load_tape(
+    path: &PathBuf,
+    symbol: &Symbol,
+) -> Result<Vec<(u64, BinanceTrade)>, Box<dyn std::error::Error>> {
+    let reader = BufReader::new(MultiGzDecoder::new(File::open(path)?));
+
+    let mut events = vec![];
+    for line in reader.lines() {
+        // 기록 중 종료된 파일은 마지막 줄이 잘려 있을 수 있음
+        let Ok(line) = line else {
+            break;
+        };
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(&line) else {
+            continue;
+        };
+        if json["kind"].as_str() != Some("trade") {
+            continue;
+        }
+        let recv = json["recv"].as_u64().unwrap_or_default();
+        let data = &json["data"];
+
+        let trades = if data.get("e").is_some() {
+            serde_json::from_value::<BinanceTrade>(data.clone())
+                .map(|trade| vec![trade])
+                .unwrap_or_default()
+        } else if data.get("topic").is_some() {
+            bybit::parse_public_trades(&data.to_string())
+        } else if data.get("arg").is_some() {
+            okx::parse_trades(&data.to_string(), symbol.contract_size)
+        } else {
+            vec![]
+        };
+
+        events.extend(
+            trades
+                .into_iter()
+                .filter(|trade| trade.symbol == symbol.exchange_symbol)
+                .map(|trade| (recv, trade)),
+        );
+    }
+    Ok(events)
+}
+
+/*
+캔들 -> 체결 4개 (시가, 고가/저가, 저가/고가, 종가)
+- 양봉은 저가를 먼저, 음봉은 고가를 먼저 지난 것으로 가정
+- 거래량은 4등분
+*/
+fn candle_ticks(
+    symbol: &Symbol,
+    open_time: u64,
+    candle: &Candlestick,
+    duration: u64,
+) -> Vec<(u64, BinanceTrade)> {
+    let (first, second) = if candle.close >= candle.open {
+        (candle.low, candle.high)
+    } else {
+        (candle.high, candle.low)
+    };
+    let quantity = (candle.volume / 4.0).to_string();
+
+    [
+        (0, candle.open),
+        (duration / 3, first),
+        (duration * 2 / 3, second),
+        (duration - 1, candle.close),
+    ]
+    .into_iter()
+    .map(|(offset, price)| {
+        let time = open_time + offset;
+        (
+            time,
+            BinanceTrade {
+                event_time: time as i64,
+                symbol: symbol.exchange_symbol.clone(),
+                price: price.to_string(),
+                quantity: quantity.clone(),
+                transaction_time: time as i64,
+                is_buyer_maker: false,
+                first_trade_id: 0,
+                last_trade_id: 0,
+            },
+        )
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::MarketType;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    #[test]
+    fn speed_delay() {
+        // 기록된 간격은 REPLAY_MAX_GAP_MS 까지만 기다림
+        assert_eq!(
+            ReplaySpeed::X1.delay(1_000),
+            Some(Duration::from_millis(1_000))
+        );
+        assert_eq!(
+            ReplaySpeed::X10.delay(1_000),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            ReplaySpeed::X1.delay(60_000),
+            Some(Duration::from_millis(uc::REPLAY_MAX_GAP_MS))
+        );
+        assert_eq!(ReplaySpeed::Max.delay(1_000), Some(Duration::ZERO));
+        assert_eq!(ReplaySpeed::Step.delay(1_000), None);
+    }
+
+    #[test]
+    fn candle_to_ticks() {
+        let symbol = Symbol::fallback(MarketType::Futures, "BTC");
+        let candle = Candlestick {
+            open: 10.0,
+            high: 12.0,
+            low: 9.0,
+            close: 11.0,
+            volume: 8.0,
+            ..Default::default()
+        };
+        // 양봉은 저가 -> 고가 순서
+        let ticks = candle_ticks(&symbol, 60_000, &candle, 60_000);
+        let path: Vec<_> = ticks
+            .iter()
+            .map(|(time, trade)| (*time, trade.price.as_str(), trade.quantity.as_str()))
+            .collect();
+        assert_eq!(
+            path,
+            [
+                (60_000, "10", "2"),
+                (80_000, "9", "2"),
+                (100_000, "12", "2"),
+                (119_999, "11", "2")
+            ]
+        );
+
+        // 음봉은 고가 -> 저가 순서
+        let candle = Candlestick {
+            close: 9.5,
+            ..candle
+        };
+        let ticks = candle_ticks(&symbol, 0, &candle, 60_000);
+        assert_eq!(ticks[1].1.price, "12");
+        assert_eq!(ticks[2].1.price, "9");
+    }
+
+    #[test]
+    fn read_recorded_tape() {
+        let path =
+            std::env::temp_dir().join(format!("futurx_replay_{}.jsonl.gz", std::process::id()));
+        let lines = [
+            r#"{"recv":1000,"kind":"trade","data":{"e":"aggTrade","E":999,"s":"BTCUSDT","p":"100.5","q":"0.2","T":998,"m":false}}"#,
+            r#"{"recv":1001,"kind":"trade","data":{"e":"aggTrade","E":999,"s":"ETHUSDT","p":"5","q":"1","T":998,"m":true}}"#,
+            r#"{"recv":1002,"kind":"depth","data":{"e":"depthUpdate","s":"BTCUSDT"}}"#,
+            r#"{"recv":1003,"kind":"trade","data":{"topic":"publicTrade.BTCUSDT","ts":1003,"data":[{"s":"BTCUSDT","p":"101","v":"1.5","T":1002,"S":"Sell"}]}}"#,
+            r#"{"recv":1004,"kind":"trade","data":{"e":"aggTr"#,
+        ];
+        let mut encoder = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+        encoder.write_all(lines.join("\n").as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        // 다른 심볼 / 호가 / 잘린 마지막 줄은 건너뜀
+        let symbol = Symbol::fallback(MarketType::Futures, "BTC");
+        let events = load_tape(&path, &symbol).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let events: Vec<_> = events
+            .iter()
+            .map(|(recv, trade)| (*recv, trade.price.as_str(), trade.is_buyer_maker))
+            .collect();
+        assert_eq!(events, [(1000, "100.5", false), (1003, "101", true)]);
+    }
+}
@@ -0,0 +1,230 @@
+use super::moving_average::Rolling;
+use super::{
+    param, Indicator, IndicatorState, LineStyle, Output, Param, ParamKind, RenderHint, Source,
+};
+use crate::Candlestick;
+use iced::Color;
+use std::collections::BTreeMap;
+
+//실제 범위 (이전 종가가 없으면 고가 - 저가)
+pub fn true_range(candle: &Candlestick, prev_close: Option<f64>) -> f64 {
+    match prev_close {
+        Some(prev) => (candle.high - candle.low)
+            .max((candle.high - prev).abs())
+            .max((candle.low - prev).abs()),
+        None => candle.high - candle.low,
+    }
+}
+
+//평균 실제 범위 (Wilder 평활, 첫 값은 period 개 단순 평균)
+#[derive(Debug)]
+pub struct Atr {
+    pub period: usize,
+    pub prev_close: Option<f64>,
+    seed: Rolling,
+    value: Option<f64>,
+}
+
+impl Atr {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            prev_close: None,
+            seed: Rolling::new(period),
+            value: None,
+        }
+    }
+
+    fn next(&self, tr: f64) -> Option<f64> {
+        let period = self.period as f64;
+        match self.value {
+            Some(prev) => Some((prev * (period - 1.0) + tr) / period),
+            None => self.seed.peek(tr).map(|sum| sum / period),
+        }
+    }
+
+    pub fn push(&mut self, candle: &Candlestick) -> Option<f64> {
+        let tr = true_range(candle, self.prev_close);
+        self.value = match self.value {
+            Some(_) => self.next(tr),
+            None => self.seed.push(tr).map(|sum| sum / self.period as f64),
+        };
+        self.prev_close = Some(candle.close);
+        self.value
+    }
+
+    pub fn peek(&self, candle: &Candlestick) -> Option<f64> {
+        self.next(true_range(candle, self.prev_close))
+    }
+}
+
+/*
+ATR / NATR
+- ATR 은 Wilder 평활 평균 실제 범위, TR 은 캔들별 실제 범위
+- NATR = ATR / 종가 * 100 (코인끼리 비교용)
+*/
+#[derive(Debug, Clone)]
+pub struct AverageTrueRange {
+    pub period: usize,
+    pub normalized: bool,
+}
+
+impl AverageTrueRange {
+    //params: [기간]
+    fn boxed(normalized: bool, params: &[f64]) -> Box<dyn Indicator> {
+        Box::new(Self {
+            period: (param(params, 0, 14.0) as usize).max(1),
+            normalized,
+        })
+    }
+
+    pub fn build_atr(params: &[f64], _source: Source) -> Box<dyn Indicator> {
+        Self::boxed(false, params)
+    }
+
+    pub fn build_natr(params: &[f64], _source: Source) -> Box<dyn Indicator> {
+        Self::boxed(true, params)
+    }
+}
+
+impl Indicator for AverageTrueRange {
+    fn name(&self) -> String {
+        if self.normalized {
+            format!("NATR({})", self.period)
+        } else {
+            format!("ATR({})", self.period)
+        }
+    }
+
+    fn inputs(&self) -> Vec<Source> {
+        vec![Source::High, Source::Low, Source::Close]
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![Param {
+            name: "Period",
+            value: self.period as f64,
+            kind: ParamKind::Period,
+        }]
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        if self.normalized {
+            return vec![Output {
+                name: "NATR",
+                color: Color::from_rgb(0.9, 0.4, 0.4),
+                style: LineStyle::Line,
+            }];
+        }
+        vec![
+            Output {
+                name: "ATR",
+                color: Color::from_rgb(0.9, 0.4, 0.4),
+                style: LineStyle::Line,
+            },
+            Output {
+                name: "TR",
+                color: Color::from_rgba(0.6, 0.6, 0.6, 0.6),
+                style: LineStyle::Line,
+            },
+        ]
+    }
+
+    fn render(&self) -> RenderHint {
+        RenderHint::SubPane {
+            range: None,
+            levels: Vec::new(),
+            band: None,
+        }
+    }
+
+    fn state(&self) -> Box<dyn IndicatorState> {
+        Box::new(AtrState {
+            atr: Atr::new(self.period),
+            normalized: self.normalized,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct AtrState {
+    atr: Atr,
+    normalized: bool,
+}
+
+impl AtrState {
+    fn outputs(&self, candle: &Candlestick, tr: f64, atr: Option<f64>) -> Vec<Option<f64>> {
+        if self.normalized {
+            vec![atr
+                .filter(|_| candle.close > 0.0)
+                .map(|atr| atr / candle.close * 100.0)]
+        } else {
+            vec![atr, Some(tr)]
+        }
+    }
+}
+
+impl IndicatorState for AtrState {
+    fn push(&mut self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        let tr = true_range(candle, self.atr.prev_close);
+        let atr = self.atr.push(candle);
+        self.outputs(candle, tr, atr)
+    }
+
+    fn peek(&self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        let tr = true_range(candle, self.atr.prev_close);
+        self.outputs(candle, tr, self.atr.peek(candle))
+    }
+}
+
+//캔들 전체로 계산한 최신 ATR (진행 중 캔들 포함, 손절 거리 등 매매 코드용)
+pub fn latest(candles: &BTreeMap<u64, Candlestick>, period: usize) -> Option<f64> {
+    let mut atr = Atr::new(period.max(1));
+    candles
+        .values()
+        .map(|candle| atr.push(candle))
+        .last()
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(high: f64, low: f64, close: f64) -> Candlestick {
+        Candlestick {
+            open: close,
+            high,
+            low,
+            close,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn wilder_atr() {
+        // TR: 2 (이전 종가 없음), 2, 1.5, 4 (갭 상승 |15 - 11|)
+        let candles = [
+            candle(10.0, 8.0, 9.0),
+            candle(11.0, 9.0, 10.5),
+            candle(12.0, 10.5, 11.0),
+            candle(15.0, 14.0, 14.5),
+        ];
+        let mut atr = Atr::new(2);
+        let values: Vec<_> = candles.iter().map(|c| atr.push(c)).collect();
+        assert_eq!(values, [None, Some(2.0), Some(1.75), Some(2.875)]);
+
+        let map: BTreeMap<u64, Candlestick> = candles
+            .into_iter()
+            .enumerate()
+            .map(|(i, c)| (i as u64, c))
+            .collect();
+        assert_eq!(latest(&map, 2), Some(2.875));
+    }
+
+    #[test]
+    fn natr_is_percent_of_close() {
+        let mut state = AverageTrueRange::build_natr(&[1.0], Source::Close).state();
+        assert_eq!(state.push(0, &candle(11.0, 9.0, 10.0)), [Some(20.0)]);
+    }
+}
@@ -0,0 +1,388 @@
+use super::atr::Atr;
+use super::moving_average::{Ema, Rolling};
+use super::{
+    param, Indicator, IndicatorState, LineStyle, Output, Param, ParamKind, RenderHint, Source,
+};
+use crate::Candlestick;
+use iced::Color;
+
+const BAND_COLOR: Color = Color::from_rgb(0.2, 0.5, 1.0);
+
+fn band_outputs(basis: Color, band: Color) -> Vec<Output> {
+    vec![
+        Output {
+            name: "Basis",
+            color: basis,
+            style: LineStyle::Line,
+        },
+        Output {
+            name: "Upper",
+            color: band,
+            style: LineStyle::Line,
+        },
+        Output {
+            name: "Lower",
+            color: band,
+            style: LineStyle::Line,
+        },
+    ]
+}
+
+/*
+볼린저 밴드
+- 중심 = SMA(period), 밴드 = 중심 ± 배수 * 표준편차 (모집단, TradingView 와 같음)
+- %B = (값 - 하단) / (상단 - 하단), 밴드폭 = (상단 - 하단) / 중심
+- 같은 기간의 켈트너 채널 (EMA ± KC 배수 * ATR) 안으로 들어가면 스퀴즈
+*/
+#[derive(Debug, Clone)]
+pub struct BollingerBands {
+    pub period: usize,
+    pub multiplier: f64,
+    pub kc_multiplier: f64,
+    pub source: Source,
+}
+
+impl BollingerBands {
+    //params: [기간, 표준편차 배수, 스퀴즈 판단용 KC 배수]
+    pub fn build(params: &[f64], source: Source) -> Box<dyn Indicator> {
+        Box::new(Self {
+            period: (param(params, 0, 20.0) as usize).max(1),
+            multiplier: param(params, 1, 2.0),
+            kc_multiplier: param(params, 2, 1.5),
+            source,
+        })
+    }
+}
+
+impl Indicator for BollingerBands {
+    fn name(&self) -> String {
+        format!("BB({}, {})", self.period, self.multiplier)
+    }
+
+    fn inputs(&self) -> Vec<Source> {
+        vec![self.source, Source::High, Source::Low]
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param {
+                name: "Period",
+                value: self.period as f64,
+                kind: ParamKind::Period,
+            },
+            Param {
+                name: "StdDev",
+                value: self.multiplier,
+                kind: ParamKind::Number,
+            },
+            Param {
+                name: "KC Mult",
+                value: self.kc_multiplier,
+                kind: ParamKind::Number,
+            },
+        ]
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        let mut outputs = band_outputs(Color::from_rgb(1.0, 0.647, 0.0), BAND_COLOR);
+        for name in ["%B", "Bandwidth", "Squeeze"] {
+            outputs.push(Output {
+                name,
+                color: BAND_COLOR,
+                style: LineStyle::Hidden,
+            });
+        }
+        outputs
+    }
+
+    fn render(&self) -> RenderHint {
+        RenderHint::Overlay
+    }
+
+    fn state(&self) -> Box<dyn IndicatorState> {
+        Box::new(BollingerState {
+            indicator: self.clone(),
+            values: Rolling::new(self.period),
+            squares: Rolling::new(self.period),
+            kc_basis: Ema::new(self.period),
+            atr: Atr::new(self.period),
+        })
+    }
+
+    fn fill(&self) -> Option<(usize, usize)> {
+        Some((1, 2))
+    }
+
+    fn squeeze(&self) -> Option<usize> {
+        Some(5)
+    }
+}
+
+#[derive(Debug)]
+struct BollingerState {
+    indicator: BollingerBands,
+    values: Rolling,
+    squares: Rolling,
+    kc_basis: Ema,
+    atr: Atr,
+}
+
+impl BollingerState {
+    fn outputs(
+        &self,
+        value: f64,
+        sums: Option<(f64, f64)>,
+        kc_basis: Option<f64>,
+        atr: Option<f64>,
+    ) -> Vec<Option<f64>> {
+        let Some((sum, squares)) = sums else {
+            return vec![None; 6];
+        };
+        let period = self.indicator.period as f64;
+        let basis = sum / period;
+        let deviation = (squares / period - basis * basis).max(0.0).sqrt();
+        let upper = basis + self.indicator.multiplier * deviation;
+        let lower = basis - self.indicator.multiplier * deviation;
+
+        let width = upper - lower;
+        let percent_b = (width > 0.0).then(|| (value - lower) / width);
+        let bandwidth = (basis != 0.0).then(|| width / basis);
+        let squeeze = kc_basis.zip(atr).map(|(kc_basis, atr)| {
+            let kc_width = self.indicator.kc_multiplier * atr;
+            if upper < kc_basis + kc_width && lower > kc_basis - kc_width {
+                1.0
+            } else {
+                0.0
+            }
+        });
+
+        vec![
+            Some(basis),
+            Some(upper),
+            Some(lower),
+            percent_b,
+            bandwidth,
+            squeeze,
+        ]
+    }
+}
+
+impl IndicatorState for BollingerState {
+    fn push(&mut self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        let value = self.indicator.source.value(candle);
+        let sums = self
+            .values
+            .push(value)
+            .zip(self.squares.push(value * value));
+        let kc_basis = self.kc_basis.push(value);
+        let atr = self.atr.push(candle);
+        self.outputs(value, sums, kc_basis, atr)
+    }
+
+    fn peek(&self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        let value = self.indicator.source.value(candle);
+        let sums = self
+            .values
+            .peek(value)
+            .zip(self.squares.peek(value * value));
+        self.outputs(
+            value,
+            sums,
+            self.kc_basis.peek(value),
+            self.atr.peek(candle),
+        )
+    }
+}
+
+/*
+켈트너 채널
+- 중심 = EMA(period), 채널 = 중심 ± 배수 * ATR(ATR 기간)
+*/
+#[derive(Debug, Clone)]
+pub struct KeltnerChannels {
+    pub period: usize,
+    pub multiplier: f64,
+    pub atr_period: usize,
+    pub source: Source,
+}
+
+impl KeltnerChannels {
+    //params: [EMA 기간, ATR 배수, ATR 기간]
+    pub fn build(params: &[f64], source: Source) -> Box<dyn Indicator> {
+        Box::new(Self {
+            period: (param(params, 0, 20.0) as usize).max(1),
+            multiplier: param(params, 1, 2.0),
+            atr_period: (param(params, 2, 10.0) as usize).max(1),
+            source,
+        })
+    }
+}
+
+impl Indicator for KeltnerChannels {
+    fn name(&self) -> String {
+        format!(
+            "KC({}, {}, {})",
+            self.period, self.multiplier, self.atr_period
+        )
+    }
+
+    fn inputs(&self) -> Vec<Source> {
+        vec![self.source, Source::High, Source::Low]
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param {
+                name: "Period",
+                value: self.period as f64,
+                kind: ParamKind::Period,
+            },
+            Param {
+                name: "Multiplier",
+                value: self.multiplier,
+                kind: ParamKind::Number,
+            },
+            Param {
+                name: "ATR Period",
+                value: self.atr_period as f64,
+                kind: ParamKind::Period,
+            },
+        ]
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        band_outputs(
+            Color::from_rgb(0.8, 0.2, 0.8),
+            Color::from_rgb(0.6, 0.3, 0.9),
+        )
+    }
+
+    fn render(&self) -> RenderHint {
+        RenderHint::Overlay
+    }
+
+    fn state(&self) -> Box<dyn IndicatorState> {
+        Box::new(KeltnerState {
+            multiplier: self.multiplier,
+            source: self.source,
+            basis: Ema::new(self.period),
+            atr: Atr::new(self.atr_period),
+        })
+    }
+
+    fn fill(&self) -> Option<(usize, usize)> {
+        Some((1, 2))
+    }
+}
+
+#[derive(Debug)]
+struct KeltnerState {
+    multiplier: f64,
+    source: Source,
+    basis: Ema,
+    atr: Atr,
+}
+
+impl KeltnerState {
+    fn outputs(&self, basis: Option<f64>, atr: Option<f64>) -> Vec<Option<f64>> {
+        let upper = basis
+            .zip(atr)
+            .map(|(basis, atr)| basis + self.multiplier * atr);
+        let lower = basis
+            .zip(atr)
+            .map(|(basis, atr)| basis - self.multiplier * atr);
+        vec![basis, upper, lower]
+    }
+}
+
+impl IndicatorState for KeltnerState {
+    fn push(&mut self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        let basis = self.basis.push(self.source.value(candle));
+        let atr = self.atr.push(candle);
+        self.outputs(basis, atr)
+    }
+
+    fn peek(&self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        self.outputs(
+            self.basis.peek(self.source.value(candle)),
+            self.atr.peek(candle),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{EventKind, IndicatorCache, IndicatorConfig};
+    use std::collections::BTreeMap;
+
+    fn candle(high: f64, low: f64, close: f64) -> Candlestick {
+        Candlestick {
+            open: close,
+            high,
+            low,
+            close,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn bollinger_uses_population_deviation() {
+        // [1, 2, 3] : 중심 2, 표준편차 sqrt(2/3)
+        let mut state = BollingerBands::build(&[3.0, 2.0], Source::Close).state();
+        let outputs: Vec<_> = [1.0, 2.0, 3.0]
+            .iter()
+            .map(|&close| state.push(0, &candle(close, close, close)))
+            .collect();
+        let deviation = (2.0f64 / 3.0).sqrt();
+        assert_eq!(outputs[2][0], Some(2.0));
+        assert!((outputs[2][1].unwrap() - (2.0 + 2.0 * deviation)).abs() < 1e-9);
+        assert!((outputs[2][2].unwrap() - (2.0 - 2.0 * deviation)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn keltner_channels() {
+        // EMA(3) 10, ATR(2) 2 -> 10 ± 2 * 2
+        let mut state = KeltnerChannels::build(&[3.0, 2.0, 2.0], Source::Close).state();
+        let outputs: Vec<_> = (0..3)
+            .map(|_| state.push(0, &candle(11.0, 9.0, 10.0)))
+            .collect();
+        assert_eq!(outputs[2][..3], [Some(10.0), Some(14.0), Some(6.0)]);
+    }
+
+    #[test]
+    fn squeeze_events() {
+        /*
+        BB(3, 2), KC 배수 1.5
+        - 0~2 : 종가 그대로 (밴드폭 0) -> 스퀴즈
+        - 3 : 종가 20 으로 급등, BB 상단 22.76 > KC 상단 22 -> 해제
+        - 4~5 : 종가 20 유지, 5 에서 밴드폭 0 -> 다시 스퀴즈
+        - 6 : 진행 중
+        */
+        let candles: BTreeMap<u64, Candlestick> = [
+            candle(11.0, 9.0, 10.0),
+            candle(11.0, 9.0, 10.0),
+            candle(11.0, 9.0, 10.0),
+            candle(20.0, 10.0, 20.0),
+            candle(21.0, 19.0, 20.0),
+            candle(21.0, 19.0, 20.0),
+            candle(21.0, 19.0, 20.0),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, candle)| (i as u64, candle))
+        .collect();
+
+        let mut cache = IndicatorCache::default();
+        cache.update(
+            &[IndicatorConfig::new("BB", &[3.0, 2.0, 1.5])],
+            &candles,
+            None,
+        );
+        let events: Vec<_> = cache.events().map(|e| (e.timestamp, e.kind)).collect();
+        assert_eq!(
+            events,
+            [(3, EventKind::SqueezeOff), (5, EventKind::SqueezeOn)]
+        );
+    }
+}
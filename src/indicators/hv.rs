@@ -0,0 +1,144 @@
+use super::moving_average::Rolling;
+use super::{
+    param, Indicator, IndicatorState, LineStyle, Output, Param, ParamKind, RenderHint, Source,
+};
+use crate::Candlestick;
+use iced::Color;
+
+/*
+역사적 (실현) 변동성
+- 로그 수익률 ln(값 / 이전 값) 의 표본 표준편차 * sqrt(연간 캔들 수) * 100
+- 연간 캔들 수는 캔들 길이로 계산 (일봉 365, 1시간봉 8760 등 - 코인은 24시간 거래)
+  파라미터로 입력하면 그 값 사용, 캔들 길이를 모르면 (비시간 캔들) 365
+*/
+#[derive(Debug, Clone)]
+pub struct HistoricalVolatility {
+    pub period: usize,
+    pub bars_per_year: f64, // 0 이면 캔들 길이로 계산
+    pub bar_duration: Option<u64>,
+    pub source: Source,
+}
+
+const YEAR_MS: f64 = 365.0 * 86_400_000.0;
+
+impl HistoricalVolatility {
+    //params: [기간, 연간 캔들 수 (0 이면 자동)]
+    pub fn build(params: &[f64], source: Source) -> Box<dyn Indicator> {
+        Box::new(Self {
+            period: (param(params, 0, 20.0) as usize).max(2),
+            bars_per_year: param(params, 1, 0.0).max(0.0),
+            bar_duration: None,
+            source,
+        })
+    }
+
+    fn annual_bars(&self) -> f64 {
+        if self.bars_per_year > 0.0 {
+            return self.bars_per_year;
+        }
+        match self.bar_duration {
+            Some(duration) if duration > 0 => YEAR_MS / duration as f64,
+            _ => 365.0,
+        }
+    }
+}
+
+impl Indicator for HistoricalVolatility {
+    fn name(&self) -> String {
+        format!("HV({})", self.period)
+    }
+
+    fn inputs(&self) -> Vec<Source> {
+        vec![self.source]
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param {
+                name: "Period",
+                value: self.period as f64,
+                kind: ParamKind::Period,
+            },
+            Param {
+                name: "Bars/Year (0 = auto)",
+                value: self.bars_per_year,
+                kind: ParamKind::Number,
+            },
+        ]
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output {
+            name: "HV",
+            color: Color::from_rgb(0.9, 0.7, 0.2),
+            style: LineStyle::Line,
+        }]
+    }
+
+    fn render(&self) -> RenderHint {
+        RenderHint::SubPane {
+            range: None,
+            levels: Vec::new(),
+            band: None,
+        }
+    }
+
+    fn state(&self) -> Box<dyn IndicatorState> {
+        Box::new(HvState {
+            source: self.source,
+            annualize: self.annual_bars().sqrt() * 100.0,
+            prev: None,
+            returns: Rolling::new(self.period),
+            squares: Rolling::new(self.period),
+        })
+    }
+
+    fn set_bar_duration(&mut self, duration_ms: Option<u64>) {
+        self.bar_duration = duration_ms;
+    }
+}
+
+#[derive(Debug)]
+struct HvState {
+    source: Source,
+    annualize: f64,
+    prev: Option<f64>,
+    returns: Rolling,
+    squares: Rolling,
+}
+
+impl HvState {
+    fn log_return(&self, value: f64) -> Option<f64> {
+        self.prev
+            .filter(|&prev| prev > 0.0 && value > 0.0)
+            .map(|prev| (value / prev).ln())
+    }
+
+    fn volatility(&self, sum: f64, squares: f64) -> f64 {
+        let n = self.returns.period as f64;
+        let variance = ((squares - sum * sum / n) / (n - 1.0)).max(0.0);
+        variance.sqrt() * self.annualize
+    }
+}
+
+impl IndicatorState for HvState {
+    fn push(&mut self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        let value = self.source.value(candle);
+        let log_return = self.log_return(value);
+        self.prev = Some(value);
+
+        let hv = log_return.and_then(|r| {
+            let (sum, squares) = self.returns.push(r).zip(self.squares.push(r * r))?;
+            Some(self.volatility(sum, squares))
+        });
+        vec![hv]
+    }
+
+    fn peek(&self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        let hv = self.log_return(self.source.value(candle)).and_then(|r| {
+            let (sum, squares) = self.returns.peek(r).zip(self.squares.peek(r * r))?;
+            Some(self.volatility(sum, squares))
+        });
+        vec![hv]
+    }
+}
@@ -0,0 +1,197 @@
+use super::moving_average::Ema;
+use super::{
+    param, Indicator, IndicatorState, LineStyle, Output, Param, ParamKind, RenderHint, Source,
+};
+use crate::Candlestick;
+use iced::Color;
+
+/*
+MACD
+- MACD = EMA(fast) - EMA(slow), 신호선 = MACD 의 EMA(signal), 히스토그램 = MACD - 신호선
+- MACD 가 신호선을 뚫으면 교차 이벤트 (점수 / 자동 매매용)
+*/
+#[derive(Debug, Clone)]
+pub struct Macd {
+    pub fast: usize,
+    pub slow: usize,
+    pub signal: usize,
+    pub source: Source,
+}
+
+impl Macd {
+    //params: [빠른 기간, 느린 기간, 신호선 기간]
+    pub fn build(params: &[f64], source: Source) -> Box<dyn Indicator> {
+        Box::new(Self {
+            fast: (param(params, 0, 12.0) as usize).max(1),
+            slow: (param(params, 1, 26.0) as usize).max(1),
+            signal: (param(params, 2, 9.0) as usize).max(1),
+            source,
+        })
+    }
+}
+
+impl Indicator for Macd {
+    fn name(&self) -> String {
+        format!("MACD({}, {}, {})", self.fast, self.slow, self.signal)
+    }
+
+    fn inputs(&self) -> Vec<Source> {
+        vec![self.source]
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param {
+                name: "Fast",
+                value: self.fast as f64,
+                kind: ParamKind::Period,
+            },
+            Param {
+                name: "Slow",
+                value: self.slow as f64,
+                kind: ParamKind::Period,
+            },
+            Param {
+                name: "Signal",
+                value: self.signal as f64,
+                kind: ParamKind::Period,
+            },
+        ]
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![
+            Output {
+                name: "MACD",
+                color: Color::from_rgb(0.0, 0.6, 1.0),
+                style: LineStyle::Line,
+            },
+            Output {
+                name: "Signal",
+                color: Color::from_rgb(1.0, 0.647, 0.0),
+                style: LineStyle::Line,
+            },
+            Output {
+                name: "Histogram",
+                color: Color::from_rgba(0.0, 0.8, 0.4, 0.6),
+                style: LineStyle::Histogram,
+            },
+        ]
+    }
+
+    fn render(&self) -> RenderHint {
+        RenderHint::SubPane {
+            range: None,
+            levels: Vec::new(),
+            band: None,
+        }
+    }
+
+    fn state(&self) -> Box<dyn IndicatorState> {
+        Box::new(MacdState {
+            source: self.source,
+            fast: Ema::new(self.fast),
+            slow: Ema::new(self.slow),
+            signal: Ema::new(self.signal),
+        })
+    }
+
+    fn crossovers(&self) -> Vec<(usize, usize)> {
+        vec![(0, 1)] // MACD / 신호선
+    }
+}
+
+#[derive(Debug)]
+struct MacdState {
+    source: Source,
+    fast: Ema,
+    slow: Ema,
+    signal: Ema,
+}
+
+fn outputs(macd: Option<f64>, signal: Option<f64>) -> Vec<Option<f64>> {
+    let histogram = macd.zip(signal).map(|(macd, signal)| macd - signal);
+    vec![macd, signal, histogram]
+}
+
+impl IndicatorState for MacdState {
+    fn push(&mut self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        let value = self.source.value(candle);
+        let (fast, slow) = (self.fast.push(value), self.slow.push(value));
+        let macd = fast.zip(slow).map(|(fast, slow)| fast - slow);
+        let signal = macd.and_then(|macd| self.signal.push(macd));
+        outputs(macd, signal)
+    }
+
+    fn peek(&self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        let value = self.source.value(candle);
+        let macd = self
+            .fast
+            .peek(value)
+            .zip(self.slow.peek(value))
+            .map(|(fast, slow)| fast - slow);
+        let signal = macd.and_then(|macd| self.signal.peek(macd));
+        outputs(macd, signal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{Cross, EventKind, IndicatorCache, IndicatorConfig};
+    use std::collections::BTreeMap;
+
+    fn candles(closes: &[f64]) -> BTreeMap<u64, Candlestick> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| {
+                let candle = Candlestick {
+                    open: close,
+                    high: close,
+                    low: close,
+                    close,
+                    ..Default::default()
+                };
+                (i as u64, candle)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn macd_and_signal_values() {
+        // 4번째 캔들: EMA2 = 3.8333, EMA3 = 4 -> MACD -1/6, 신호선 = (-1/2 + -1/6) / 2
+        let mut state = Macd::build(&[2.0, 3.0, 2.0], Source::Close).state();
+        let outputs: Vec<_> = candles(&[5.0, 4.0, 3.0, 4.0])
+            .values()
+            .map(|candle| state.push(0, candle))
+            .collect();
+        assert_eq!(outputs[2][0], Some(-0.5));
+        let macd = outputs[3][0].unwrap();
+        let signal = outputs[3][1].unwrap();
+        assert!((macd + 1.0 / 6.0).abs() < 1e-9);
+        assert!((signal + 1.0 / 3.0).abs() < 1e-9);
+        assert!((outputs[3][2].unwrap() - (macd - signal)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn crossover_events() {
+        // 6 에서 MACD 가 신호선 아래로, 9 에서 위로 (마지막 캔들은 진행 중)
+        let candles = candles(&[5.0, 4.0, 3.0, 4.0, 5.0, 6.0, 5.0, 4.0, 3.0, 4.0, 4.0]);
+        let mut cache = IndicatorCache::default();
+        cache.update(
+            &[IndicatorConfig::new("MACD", &[2.0, 3.0, 2.0])],
+            &candles,
+            None,
+        );
+
+        let events: Vec<_> = cache.events().map(|e| (e.timestamp, e.kind)).collect();
+        assert_eq!(
+            events,
+            [
+                (6, EventKind::Cross(Cross::Down)),
+                (9, EventKind::Cross(Cross::Up))
+            ]
+        );
+    }
+}
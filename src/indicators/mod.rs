@@ -0,0 +1,819 @@
+pub mod atr;
+pub mod bands;
+pub mod hv;
+pub mod macd;
+pub mod moving_average;
+pub mod order_flow;
+pub mod rsi;
+pub mod vwap;
+
+use crate::utils::constant as uc;
+use crate::Candlestick;
+use atr::AverageTrueRange;
+use bands::{BollingerBands, KeltnerChannels};
+use hv::HistoricalVolatility;
+use iced::Color;
+use macd::Macd;
+use moving_average::MovingAverage;
+use order_flow::{CumulativeDelta, VolumeDelta};
+use rsi::Rsi;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::Bound;
+use vwap::Vwap;
+
+//지표 입력 값 (캔들에서 꺼낼 값)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default, Serialize, Deserialize)]
+pub enum Source {
+    Open,
+    High,
+    Low,
+    #[default]
+    Close,
+    Hl2,   // (고가 + 저가) / 2
+    Hlc3,  // (고가 + 저가 + 종가) / 3
+    Ohlc4, // (시가 + 고가 + 저가 + 종가) / 4
+    Volume,
+}
+
+impl Source {
+    pub const ALL: [Source; 8] = [
+        Source::Open,
+        Source::High,
+        Source::Low,
+        Source::Close,
+        Source::Hl2,
+        Source::Hlc3,
+        Source::Ohlc4,
+        Source::Volume,
+    ];
+
+    pub fn value(&self, candle: &Candlestick) -> f64 {
+        match self {
+            Source::Open => candle.open,
+            Source::High => candle.high,
+            Source::Low => candle.low,
+            Source::Close => candle.close,
+            Source::Hl2 => (candle.high + candle.low) / 2.0,
+            Source::Hlc3 => (candle.high + candle.low + candle.close) / 3.0,
+            Source::Ohlc4 => (candle.open + candle.high + candle.low + candle.close) / 4.0,
+            Source::Volume => candle.volume,
+        }
+    }
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Open => write!(f, "Open"),
+            Source::High => write!(f, "High"),
+            Source::Low => write!(f, "Low"),
+            Source::Close => write!(f, "Close"),
+            Source::Hl2 => write!(f, "HL2"),
+            Source::Hlc3 => write!(f, "HLC3"),
+            Source::Ohlc4 => write!(f, "OHLC4"),
+            Source::Volume => write!(f, "Volume"),
+        }
+    }
+}
+
+//지표 파라미터 (이름, 값, 값 종류)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Param {
+    pub name: &'static str,
+    pub value: f64,
+    pub kind: ParamKind,
+}
+
+//파라미터 값 종류 (입력 검증 / 저장된 값 보정)
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ParamKind {
+    Period,         // 캔들 수 (1 ~ INDICATOR_MAX_PERIOD 정수)
+    OptionalPeriod, // 캔들 수, 0 이면 사용 안 함
+    Number,         // 0 이상 실수 (배수, 시간 등)
+    Timestamp,      // 시각 (ms, 정수)
+}
+
+impl ParamKind {
+    //입력 값이 범위 안이면 그대로, 아니면 None
+    pub fn validate(&self, value: f64) -> Option<f64> {
+        let max = uc::INDICATOR_MAX_PERIOD as f64;
+        let integer = value.fract() == 0.0;
+        let valid = value.is_finite()
+            && match self {
+                ParamKind::Period => integer && (1.0..=max).contains(&value),
+                ParamKind::OptionalPeriod => integer && (0.0..=max).contains(&value),
+                ParamKind::Number => value >= 0.0,
+                ParamKind::Timestamp => integer && value >= 0.0,
+            };
+        valid.then_some(value)
+    }
+
+    //범위 밖 값을 가장 가까운 값으로 (잘못 저장된 설정 복구)
+    pub fn clamp(&self, value: f64) -> f64 {
+        let value = if value.is_nan() { 0.0 } else { value };
+        let max = uc::INDICATOR_MAX_PERIOD as f64;
+        match self {
+            ParamKind::Period => value.round().clamp(1.0, max),
+            ParamKind::OptionalPeriod => value.round().clamp(0.0, max),
+            ParamKind::Number => value.max(0.0),
+            ParamKind::Timestamp => value.round().max(0.0),
+        }
+    }
+
+    //입력 안내 (잘못된 값 알림용)
+    pub fn describe(&self) -> String {
+        match self {
+            ParamKind::Period => format!("integer 1-{}", uc::INDICATOR_MAX_PERIOD),
+            ParamKind::OptionalPeriod => {
+                format!("integer 0-{} (0 = off)", uc::INDICATOR_MAX_PERIOD)
+            }
+            ParamKind::Number => "number >= 0".to_string(),
+            ParamKind::Timestamp => "timestamp (ms)".to_string(),
+        }
+    }
+}
+
+//지표 출력 시리즈 (이름, 기본 색, 그리는 모양)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Output {
+    pub name: &'static str,
+    pub color: Color,
+    pub style: LineStyle,
+}
+
+//출력 모양
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum LineStyle {
+    #[default]
+    Line,
+    Histogram, // 0 기준 막대 (음수는 빨간색)
+    Hidden,    // 계산만 (%B 처럼 가격 축이 아닌 값, 전략용)
+}
+
+//교차 방향
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Cross {
+    Up,   // 골든 크로스
+    Down, // 데드 크로스
+}
+
+//이벤트 종류
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum EventKind {
+    Cross(Cross),
+    SqueezeOn,  // 볼린저 밴드가 켈트너 채널 안으로
+    SqueezeOff, // 스퀴즈 해제 (변동성 확대 시작)
+}
+
+//지표 이벤트 (마감된 캔들 기준)
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndicatorEvent {
+    pub timestamp: u64,
+    pub name: String, // 지표 표시 이름 (MACD(12, 26, 9))
+    pub kind: EventKind,
+}
+
+//그리는 위치
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderHint {
+    Overlay, // 가격 차트 위
+    SubPane {
+        range: Option<(f64, f64)>, // 고정 범위 (None 이면 보이는 값 기준)
+        levels: Vec<f64>,          // 기준선 (비어 있으면 범위를 4등분)
+        band: Option<(f64, f64)>,  // 배경을 칠할 구간 (과매도 ~ 과매수 등)
+    },
+}
+
+//지표
+pub trait Indicator: std::fmt::Debug {
+    fn name(&self) -> String; // 표시 이름 (MA(20))
+    fn inputs(&self) -> Vec<Source>;
+    fn params(&self) -> Vec<Param>;
+    fn outputs(&self) -> Vec<Output>;
+    fn render(&self) -> RenderHint;
+    fn state(&self) -> Box<dyn IndicatorState>; // 빈 계산 상태
+
+    //교차 이벤트를 낼 출력 쌍 (a 가 b 를 위로 뚫으면 Cross::Up)
+    fn crossovers(&self) -> Vec<(usize, usize)> {
+        Vec::new()
+    }
+
+    //사이를 칠할 출력 쌍 (밴드 위 / 아래)
+    fn fill(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    //스퀴즈 여부 출력 (1 이면 스퀴즈 중, 바뀔 때 SqueezeOn / SqueezeOff)
+    fn squeeze(&self) -> Option<usize> {
+        None
+    }
+
+    //캔들 길이 (ms, 비시간 캔들이면 None) - 연율화처럼 캔들 간격이 필요한 지표만 사용
+    fn set_bar_duration(&mut self, _duration_ms: Option<u64>) {}
+}
+
+/*
+지표 계산 상태 (캔들 하나당 O(1))
+- 반환 값은 outputs 순서, 기간 부족 등으로 값이 없으면 None
+- push : 마감된 캔들 반영
+- peek : 진행 중 캔들 값 (상태는 그대로, 체결마다 호출)
+- timestamp 는 캔들 시작 시각 (ms, VWAP 세션 구분 등)
+*/
+pub trait IndicatorState: std::fmt::Debug {
+    fn push(&mut self, timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>>;
+    fn peek(&self, timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>>;
+}
+
+//등록된 지표 (key, 생성 함수 - 빠진 파라미터는 기본값)
+pub struct IndicatorSpec {
+    pub key: &'static str,
+    pub build: fn(&[f64], Source) -> Box<dyn Indicator>,
+}
+
+//새 지표는 여기에 추가
+pub static REGISTRY: &[IndicatorSpec] = &[
+    IndicatorSpec {
+        key: "SMA",
+        build: MovingAverage::build_sma,
+    },
+    IndicatorSpec {
+        key: "EMA",
+        build: MovingAverage::build_ema,
+    },
+    IndicatorSpec {
+        key: "WMA",
+        build: MovingAverage::build_wma,
+    },
+    IndicatorSpec {
+        key: "HMA",
+        build: MovingAverage::build_hma,
+    },
+    IndicatorSpec {
+        key: "VWMA",
+        build: MovingAverage::build_vwma,
+    },
+    IndicatorSpec {
+        key: "RSI",
+        build: Rsi::build,
+    },
+    IndicatorSpec {
+        key: "MACD",
+        build: Macd::build,
+    },
+    IndicatorSpec {
+        key: "BB",
+        build: BollingerBands::build,
+    },
+    IndicatorSpec {
+        key: "KC",
+        build: KeltnerChannels::build,
+    },
+    IndicatorSpec {
+        key: "ATR",
+        build: AverageTrueRange::build_atr,
+    },
+    IndicatorSpec {
+        key: "NATR",
+        build: AverageTrueRange::build_natr,
+    },
+    IndicatorSpec {
+        key: "HV",
+        build: HistoricalVolatility::build,
+    },
+    IndicatorSpec {
+        key: "VWAP",
+        build: Vwap::build_session,
+    },
+    IndicatorSpec {
+        key: "AVWAP",
+        build: Vwap::build_anchored,
+    },
+    IndicatorSpec {
+        key: "DELTA",
+        build: VolumeDelta::build,
+    },
+    IndicatorSpec {
+        key: "CVD",
+        build: CumulativeDelta::build,
+    },
+];
+
+pub fn create(key: &str, params: &[f64], source: Source) -> Option<Box<dyn Indicator>> {
+    REGISTRY
+        .iter()
+        .find(|spec| spec.key == key)
+        .map(|spec| (spec.build)(params, source))
+}
+
+//차트에 올린 지표 설정 (차트별로 저장)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndicatorConfig {
+    pub key: String,
+    pub params: Vec<f64>,
+    #[serde(default)]
+    pub source: Source,
+    #[serde(default, with = "color_serde")]
+    pub color: Option<Color>, // 첫 번째 출력 색 (None 이면 지표 기본 색)
+    pub visible: bool,
+}
+
+impl IndicatorConfig {
+    pub fn new(key: &str, params: &[f64]) -> Self {
+        Self {
+            key: key.to_string(),
+            params: params.to_vec(),
+            source: Source::default(),
+            color: None,
+            visible: true,
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn hidden(mut self) -> Self {
+        self.visible = false;
+        self
+    }
+
+    pub fn build(&self) -> Option<Box<dyn Indicator>> {
+        create(&self.key, &self.params, self.source)
+    }
+
+    //저장된 파라미터를 지표 범위에 맞춤
+    pub fn clamp_params(&mut self) {
+        let Some(indicator) = self.build() else {
+            return;
+        };
+        for (value, param) in self.params.iter_mut().zip(indicator.params()) {
+            *value = param.kind.clamp(*value);
+        }
+    }
+
+    //표시 이름 (등록되지 않은 key 면 key 그대로)
+    pub fn name(&self) -> String {
+        self.build()
+            .map(|indicator| indicator.name())
+            .unwrap_or_else(|| self.key.clone())
+    }
+
+    //파라미터 / 입력 설명 (Period: 20, Input: Close)
+    pub fn description(&self) -> String {
+        let Some(indicator) = self.build() else {
+            return String::new();
+        };
+        let params = indicator
+            .params()
+            .into_iter()
+            .map(|param| format!("{}: {}", param.name, param.value));
+        let inputs = indicator
+            .inputs()
+            .iter()
+            .map(|source| source.to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+        params
+            .chain(std::iter::once(format!("Input: {}", inputs)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+//기본 지표 구성
+pub fn default_configs() -> Vec<IndicatorConfig> {
+    vec![
+        IndicatorConfig::new("SMA", &[5.0])
+            .with_color(uc::ORNAGE)
+            .hidden(),
+        IndicatorConfig::new("SMA", &[10.0])
+            .with_color(uc::YELLOW)
+            .hidden(),
+        IndicatorConfig::new("SMA", &[20.0])
+            .with_color(uc::DAKR_RED)
+            .hidden(),
+        IndicatorConfig::new("SMA", &[200.0])
+            .with_color(Color::from_rgb(0.0, 0.0, 1.0))
+            .hidden(),
+        IndicatorConfig::new("RSI", &[14.0]),
+    ]
+}
+
+//계산된 지표 (차트 표시용)
+#[derive(Debug, Clone)]
+pub struct IndicatorSeries {
+    pub name: String,
+    pub render: RenderHint,
+    pub lines: Vec<SeriesLine>,
+    pub fill: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SeriesLine {
+    pub values: BTreeMap<u64, f64>,
+    pub color: Color,
+    pub style: LineStyle,
+}
+
+impl IndicatorSeries {
+    //from 이후 값만 (차트에 보이는 구간)
+    pub fn window(&self, from: u64) -> Self {
+        Self {
+            name: self.name.clone(),
+            render: self.render.clone(),
+            lines: self
+                .lines
+                .iter()
+                .map(|line| SeriesLine {
+                    values: line.values.range(from..).map(|(&k, &v)| (k, v)).collect(),
+                    color: line.color,
+                    style: line.style,
+                })
+                .collect(),
+            fill: self.fill,
+        }
+    }
+
+    fn set(&mut self, timestamp: u64, outputs: Vec<Option<f64>>) {
+        for (line, output) in self.lines.iter_mut().zip(outputs) {
+            match output {
+                Some(value) => line.values.insert(timestamp, value),
+                None => line.values.remove(&timestamp),
+            };
+        }
+    }
+}
+
+/*
+지표 캐시 (앱 상태에 보관)
+- 마감된 캔들은 한 번만 push, 마지막 캔들은 매번 peek
+- 첫 캔들 / 마지막으로 push 한 캔들이 달라지면 (코인 변경, 과거 로드, 백필) 처음부터 다시 계산
+- 이벤트는 전부 events 에 쌓고, 새로 마감된 마지막 캔들의 이벤트만 take_new_events 로 전달
+*/
+#[derive(Debug, Default)]
+pub struct IndicatorCache {
+    entries: Vec<CacheEntry>,
+    bar_duration: Option<u64>, // 캔들 길이 (ms, 바뀌면 전부 다시 계산)
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    config: IndicatorConfig,
+    bar_duration: Option<u64>,
+    state: Box<dyn IndicatorState>,
+    series: IndicatorSeries,
+    first: Option<u64>,                 // 계산 시작 캔들
+    closed: Option<(u64, Candlestick)>, // 마지막으로 push 한 캔들
+    count: usize,                       // push 한 캔들 수
+    crossovers: Vec<(usize, usize)>,
+    diffs: Vec<Option<f64>>, // 교차 쌍별 마지막으로 0 이 아니었던 (a - b)
+    squeeze: Option<usize>,
+    squeezed: Option<bool>,
+    events: Vec<IndicatorEvent>,
+    new_events: Vec<IndicatorEvent>,
+}
+
+impl IndicatorCache {
+    /*
+    지표 구성에 맞춰 캐시를 맞추고 새 캔들만 반영
+    - 숨긴 지표도 계산 (이벤트 / latest 용), 그리기만 건너뜀
+    - 표시 여부만 바뀌면 다시 계산하지 않음
+    */
+    pub fn update(
+        &mut self,
+        configs: &[IndicatorConfig],
+        candles: &BTreeMap<u64, Candlestick>,
+        bar_duration: Option<u64>,
+    ) {
+        if self.bar_duration != bar_duration {
+            self.bar_duration = bar_duration;
+            self.entries.clear();
+        }
+        self.entries.truncate(configs.len());
+        for (i, config) in configs.iter().enumerate() {
+            if let Some(entry) = self.entries.get_mut(i) {
+                if entry.config.key == config.key
+                    && entry.config.params == config.params
+                    && entry.config.source == config.source
+                    && entry.config.color == config.color
+                {
+                    entry.config.visible = config.visible;
+                    continue;
+                }
+            }
+            let Some(entry) = CacheEntry::new(config, bar_duration) else {
+                continue;
+            };
+            if i < self.entries.len() {
+                self.entries[i] = entry;
+            } else {
+                self.entries.push(entry);
+            }
+        }
+
+        for entry in &mut self.entries {
+            entry.sync(candles);
+        }
+    }
+
+    //차트에 그릴 지표 목록 (보이는 것만)
+    pub fn series(&self) -> impl Iterator<Item = &IndicatorSeries> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.config.visible)
+            .map(|entry| &entry.series)
+    }
+
+    //지금까지의 이벤트 (점수 계산용)
+    pub fn events(&self) -> impl Iterator<Item = &IndicatorEvent> {
+        self.entries.iter().flat_map(|entry| &entry.events)
+    }
+
+    //key 지표 (숨긴 것 포함 첫 번째) 의 첫 출력 최신 값
+    pub fn latest(&self, key: &str) -> Option<f64> {
+        self.entries
+            .iter()
+            .find(|entry| entry.config.key == key)
+            .and_then(|entry| entry.series.lines.first())
+            .and_then(|line| line.values.values().next_back().copied())
+    }
+
+    //마지막 update 에서 새로 생긴 이벤트 (자동 매매용, 한 번만 반환)
+    pub fn take_new_events(&mut self) -> Vec<IndicatorEvent> {
+        self.entries
+            .iter_mut()
+            .flat_map(|entry| std::mem::take(&mut entry.new_events))
+            .collect()
+    }
+}
+
+impl CacheEntry {
+    fn build(config: &IndicatorConfig, bar_duration: Option<u64>) -> Option<Box<dyn Indicator>> {
+        let mut indicator = config.build()?;
+        indicator.set_bar_duration(bar_duration);
+        Some(indicator)
+    }
+
+    fn new(config: &IndicatorConfig, bar_duration: Option<u64>) -> Option<Self> {
+        let indicator = Self::build(config, bar_duration)?;
+        let lines = indicator
+            .outputs()
+            .into_iter()
+            .enumerate()
+            .map(|(i, output)| {
+                let color = match config.color {
+                    Some(color) if i == 0 => color,
+                    _ => output.color,
+                };
+                SeriesLine {
+                    values: BTreeMap::new(),
+                    color,
+                    style: output.style,
+                }
+            })
+            .collect();
+        let crossovers = indicator.crossovers();
+        Some(Self {
+            config: config.clone(),
+            bar_duration,
+            state: indicator.state(),
+            series: IndicatorSeries {
+                name: indicator.name(),
+                render: indicator.render(),
+                lines,
+                fill: indicator.fill(),
+            },
+            first: None,
+            closed: None,
+            count: 0,
+            diffs: vec![None; crossovers.len()],
+            crossovers,
+            squeeze: indicator.squeeze(),
+            squeezed: None,
+            events: Vec::new(),
+            new_events: Vec::new(),
+        })
+    }
+
+    fn sync(&mut self, candles: &BTreeMap<u64, Candlestick>) {
+        let Some((&last, last_candle)) = candles.iter().next_back() else {
+            self.reset();
+            return;
+        };
+
+        // 이미 계산한 구간이 바뀌었으면 처음부터
+        let first = candles.keys().next().copied();
+        let stale = self.first != first
+            || self.count >= candles.len()
+            || self.closed.as_ref().is_some_and(|(timestamp, candle)| {
+                candles
+                    .get(timestamp)
+                    .is_none_or(|current| !same_candle(current, candle))
+            });
+        if stale {
+            self.reset();
+            self.first = first;
+        }
+        let newest = candles
+            .range(..last)
+            .next_back()
+            .map(|(&timestamp, _)| timestamp);
+
+        // 새로 마감된 캔들 반영
+        let from = match &self.closed {
+            Some((timestamp, _)) => Bound::Excluded(*timestamp),
+            None => Bound::Unbounded,
+        };
+        for (&timestamp, candle) in candles.range((from, Bound::Excluded(last))) {
+            let outputs = self.state.push(timestamp, candle);
+            self.detect_events(timestamp, &outputs, !stale && Some(timestamp) == newest);
+            self.series.set(timestamp, outputs);
+            self.closed = Some((timestamp, candle.clone()));
+            self.count += 1;
+        }
+
+        // 진행 중 캔들
+        let outputs = self.state.peek(last, last_candle);
+        self.series.set(last, outputs);
+    }
+
+    fn reset(&mut self) {
+        if let Some(indicator) = Self::build(&self.config, self.bar_duration) {
+            self.state = indicator.state();
+        }
+        for line in &mut self.series.lines {
+            line.values.clear();
+        }
+        self.first = None;
+        self.closed = None;
+        self.count = 0;
+        self.diffs.fill(None);
+        self.squeezed = None;
+        self.events.clear();
+        self.new_events.clear();
+    }
+
+    //마감 캔들에서 교차 / 스퀴즈 변화 확인 (live 면 자동 매매에도 전달)
+    fn detect_events(&mut self, timestamp: u64, outputs: &[Option<f64>], live: bool) {
+        let mut kinds = Vec::new();
+
+        // (a - b) 부호가 바뀌면 교차
+        for (i, &(a, b)) in self.crossovers.iter().enumerate() {
+            let diff = match (
+                outputs.get(a).copied().flatten(),
+                outputs.get(b).copied().flatten(),
+            ) {
+                (Some(a), Some(b)) if a != b => a - b,
+                _ => continue,
+            };
+            let cross = match self.diffs[i] {
+                Some(prev) if prev < 0.0 && diff > 0.0 => Some(Cross::Up),
+                Some(prev) if prev > 0.0 && diff < 0.0 => Some(Cross::Down),
+                _ => None,
+            };
+            self.diffs[i] = Some(diff);
+            kinds.extend(cross.map(EventKind::Cross));
+        }
+
+        if let Some(squeezed) = self
+            .squeeze
+            .and_then(|index| outputs.get(index).copied().flatten())
+            .map(|value| value > 0.0)
+        {
+            match self.squeezed.replace(squeezed) {
+                Some(false) if squeezed => kinds.push(EventKind::SqueezeOn),
+                Some(true) if !squeezed => kinds.push(EventKind::SqueezeOff),
+                _ => {}
+            }
+        }
+
+        for kind in kinds {
+            let event = IndicatorEvent {
+                timestamp,
+                name: self.series.name.clone(),
+                kind,
+            };
+            if live {
+                self.new_events.push(event.clone());
+            }
+            self.events.push(event);
+        }
+    }
+}
+
+//마감 캔들이 바뀌었는지 확인 (백필 / 재로드)
+pub fn same_candle(a: &Candlestick, b: &Candlestick) -> bool {
+    a.open == b.open
+        && a.high == b.high
+        && a.low == b.low
+        && a.close == b.close
+        && a.volume == b.volume
+}
+
+//지표 색 선택지
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PaletteColor {
+    pub name: &'static str,
+    pub color: Color,
+}
+
+impl std::fmt::Display for PaletteColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+pub const PALETTE: [PaletteColor; 8] = [
+    PaletteColor {
+        name: "Orange",
+        color: uc::ORNAGE,
+    },
+    PaletteColor {
+        name: "Yellow",
+        color: uc::YELLOW,
+    },
+    PaletteColor {
+        name: "Red",
+        color: uc::DAKR_RED,
+    },
+    PaletteColor {
+        name: "Blue",
+        color: Color::from_rgb(0.0, 0.0, 1.0),
+    },
+    PaletteColor {
+        name: "Green",
+        color: uc::BRIGH_GREEN,
+    },
+    PaletteColor {
+        name: "Cyan",
+        color: Color::from_rgb(0.0, 0.8, 0.8),
+    },
+    PaletteColor {
+        name: "Magenta",
+        color: Color::from_rgb(0.8, 0.2, 0.8),
+    },
+    PaletteColor {
+        name: "White",
+        color: Color::from_rgb(0.9, 0.9, 0.9),
+    },
+];
+
+//Color 는 serde 를 지원하지 않으므로 [r, g, b, a] 로 저장
+mod color_serde {
+    use iced::Color;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        color: &Option<Color>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        color
+            .map(|color| [color.r, color.g, color.b, color.a])
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Color>, D::Error> {
+        let rgba = Option::<[f32; 4]>::deserialize(deserializer)?;
+        Ok(rgba.map(|[r, g, b, a]| Color::from_rgba(r, g, b, a)))
+    }
+}
+
+//파라미터 값 (없으면 기본값)
+pub fn param(params: &[f64], index: usize, default: f64) -> f64 {
+    params.get(index).copied().unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn param_kind_bounds() {
+        let max = uc::INDICATOR_MAX_PERIOD as f64;
+        assert_eq!(ParamKind::Period.validate(20.0), Some(20.0));
+        for value in [0.0, 20.5, 1e20, f64::NAN, f64::INFINITY] {
+            assert_eq!(ParamKind::Period.validate(value), None, "{}", value);
+        }
+        assert_eq!(ParamKind::OptionalPeriod.validate(0.0), Some(0.0));
+        assert_eq!(ParamKind::Number.validate(-1.0), None);
+        assert_eq!(ParamKind::Number.validate(2.5), Some(2.5));
+
+        assert_eq!(ParamKind::Period.clamp(1e20), max);
+        assert_eq!(ParamKind::Period.clamp(f64::NAN), 1.0);
+        assert_eq!(ParamKind::OptionalPeriod.clamp(-3.0), 0.0);
+    }
+
+    #[test]
+    fn clamp_saved_params() {
+        // 잘못 저장된 기간 (1e20) 은 최대값으로, 상태 생성 시 패닉 없음
+        let mut config = IndicatorConfig::new("SMA", &[1e20]);
+        config.clamp_params();
+        assert_eq!(config.params, [uc::INDICATOR_MAX_PERIOD as f64]);
+        let mut state = config.build().expect("SMA").state();
+        assert_eq!(state.push(0, &Candlestick::default()), [None]);
+    }
+}
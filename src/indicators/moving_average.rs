@@ -0,0 +1,400 @@
+use super::{
+    param, Indicator, IndicatorState, LineStyle, Output, Param, ParamKind, RenderHint, Source,
+};
+use crate::Candlestick;
+use iced::Color;
+use std::collections::VecDeque;
+
+//이동평균 종류 (registry key 와 같음)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum MaType {
+    #[default]
+    Sma, // 단순
+    Ema,  // 지수
+    Wma,  // 선형 가중
+    Hma,  // Hull (WMA(2 * WMA(n/2) - WMA(n), sqrt(n)))
+    Vwma, // 거래량 가중
+}
+
+impl MaType {
+    pub const ALL: [MaType; 5] = [
+        MaType::Sma,
+        MaType::Ema,
+        MaType::Wma,
+        MaType::Hma,
+        MaType::Vwma,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            MaType::Sma => "SMA",
+            MaType::Ema => "EMA",
+            MaType::Wma => "WMA",
+            MaType::Hma => "HMA",
+            MaType::Vwma => "VWMA",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<MaType> {
+        MaType::ALL.into_iter().find(|ma_type| ma_type.key() == key)
+    }
+}
+
+impl std::fmt::Display for MaType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+//이동평균
+#[derive(Debug, Clone)]
+pub struct MovingAverage {
+    pub ma_type: MaType,
+    pub period: usize,
+    pub source: Source,
+}
+
+impl MovingAverage {
+    //params: [기간]
+    fn boxed(ma_type: MaType, params: &[f64], source: Source) -> Box<dyn Indicator> {
+        Box::new(Self {
+            ma_type,
+            period: (param(params, 0, 20.0) as usize).max(1),
+            source,
+        })
+    }
+
+    pub fn build_sma(params: &[f64], source: Source) -> Box<dyn Indicator> {
+        Self::boxed(MaType::Sma, params, source)
+    }
+
+    pub fn build_ema(params: &[f64], source: Source) -> Box<dyn Indicator> {
+        Self::boxed(MaType::Ema, params, source)
+    }
+
+    pub fn build_wma(params: &[f64], source: Source) -> Box<dyn Indicator> {
+        Self::boxed(MaType::Wma, params, source)
+    }
+
+    pub fn build_hma(params: &[f64], source: Source) -> Box<dyn Indicator> {
+        Self::boxed(MaType::Hma, params, source)
+    }
+
+    pub fn build_vwma(params: &[f64], source: Source) -> Box<dyn Indicator> {
+        Self::boxed(MaType::Vwma, params, source)
+    }
+}
+
+impl Indicator for MovingAverage {
+    fn name(&self) -> String {
+        match self.source {
+            Source::Close => format!("{}({})", self.ma_type, self.period),
+            source => format!("{}({}, {})", self.ma_type, self.period, source),
+        }
+    }
+
+    fn inputs(&self) -> Vec<Source> {
+        match self.ma_type {
+            MaType::Vwma => vec![self.source, Source::Volume],
+            _ => vec![self.source],
+        }
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![Param {
+            name: "Period",
+            value: self.period as f64,
+            kind: ParamKind::Period,
+        }]
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output {
+            name: self.ma_type.key(),
+            color: Color::from_rgb(0.9, 0.9, 0.9),
+            style: LineStyle::Line,
+        }]
+    }
+
+    fn render(&self) -> RenderHint {
+        RenderHint::Overlay
+    }
+
+    fn state(&self) -> Box<dyn IndicatorState> {
+        let period = self.period;
+        let average = match self.ma_type {
+            MaType::Sma => Average::Sma(Rolling::new(period)),
+            MaType::Ema => Average::Ema(Ema::new(period)),
+            MaType::Wma => Average::Wma(Wma::new(period)),
+            MaType::Hma => Average::Hma {
+                half: Wma::new((period / 2).max(1)),
+                full: Wma::new(period),
+                smooth: Wma::new(((period as f64).sqrt() as usize).max(1)),
+            },
+            MaType::Vwma => Average::Vwma {
+                price_volume: Rolling::new(period),
+                volume: Rolling::new(period),
+            },
+        };
+        Box::new(MovingAverageState {
+            source: self.source,
+            average,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct MovingAverageState {
+    source: Source,
+    average: Average,
+}
+
+#[derive(Debug)]
+enum Average {
+    Sma(Rolling),
+    Ema(Ema),
+    Wma(Wma),
+    Hma {
+        half: Wma,
+        full: Wma,
+        smooth: Wma,
+    },
+    Vwma {
+        price_volume: Rolling,
+        volume: Rolling,
+    },
+}
+
+impl IndicatorState for MovingAverageState {
+    fn push(&mut self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        let value = self.source.value(candle);
+        let average = match &mut self.average {
+            Average::Sma(rolling) => rolling.push(value).map(|sum| sum / rolling.period as f64),
+            Average::Ema(ema) => ema.push(value),
+            Average::Wma(wma) => wma.push(value),
+            Average::Hma { half, full, smooth } => match (half.push(value), full.push(value)) {
+                (Some(half), Some(full)) => smooth.push(2.0 * half - full),
+                _ => None,
+            },
+            Average::Vwma {
+                price_volume,
+                volume,
+            } => {
+                let price_volume = price_volume.push(value * candle.volume);
+                match (price_volume, volume.push(candle.volume)) {
+                    (Some(price_volume), Some(volume)) if volume > 0.0 => {
+                        Some(price_volume / volume)
+                    }
+                    _ => None,
+                }
+            }
+        };
+        vec![average]
+    }
+
+    fn peek(&self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        let value = self.source.value(candle);
+        let average = match &self.average {
+            Average::Sma(rolling) => rolling.peek(value).map(|sum| sum / rolling.period as f64),
+            Average::Ema(ema) => ema.peek(value),
+            Average::Wma(wma) => wma.peek(value),
+            Average::Hma { half, full, smooth } => match (half.peek(value), full.peek(value)) {
+                (Some(half), Some(full)) => smooth.peek(2.0 * half - full),
+                _ => None,
+            },
+            Average::Vwma {
+                price_volume,
+                volume,
+            } => match (
+                price_volume.peek(value * candle.volume),
+                volume.peek(candle.volume),
+            ) {
+                (Some(price_volume), Some(volume)) if volume > 0.0 => Some(price_volume / volume),
+                _ => None,
+            },
+        };
+        vec![average]
+    }
+}
+
+//최근 period 개 합계 (다 차기 전에는 None)
+#[derive(Debug)]
+pub struct Rolling {
+    pub period: usize,
+    window: VecDeque<f64>,
+    sum: f64,
+}
+
+impl Rolling {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            window: VecDeque::with_capacity(period + 1),
+            sum: 0.0,
+        }
+    }
+
+    pub fn push(&mut self, value: f64) -> Option<f64> {
+        self.window.push_back(value);
+        self.sum += value;
+        if self.window.len() > self.period {
+            self.sum -= self.window.pop_front().unwrap_or_default();
+        }
+        (self.window.len() == self.period).then_some(self.sum)
+    }
+
+    pub fn peek(&self, value: f64) -> Option<f64> {
+        if self.window.len() == self.period {
+            Some(self.sum - self.window.front().copied().unwrap_or_default() + value)
+        } else if self.window.len() + 1 == self.period {
+            Some(self.sum + value)
+        } else {
+            None
+        }
+    }
+}
+
+//지수 이동평균 (첫 값은 period 개 단순 평균)
+#[derive(Debug)]
+pub struct Ema {
+    alpha: f64,
+    seed: Rolling,
+    value: Option<f64>,
+}
+
+impl Ema {
+    pub fn new(period: usize) -> Self {
+        Self {
+            alpha: 2.0 / (period as f64 + 1.0),
+            seed: Rolling::new(period),
+            value: None,
+        }
+    }
+
+    pub fn push(&mut self, value: f64) -> Option<f64> {
+        self.value = match self.value {
+            Some(prev) => Some(prev + self.alpha * (value - prev)),
+            None => self
+                .seed
+                .push(value)
+                .map(|sum| sum / self.seed.period as f64),
+        };
+        self.value
+    }
+
+    pub fn peek(&self, value: f64) -> Option<f64> {
+        match self.value {
+            Some(prev) => Some(prev + self.alpha * (value - prev)),
+            None => self
+                .seed
+                .peek(value)
+                .map(|sum| sum / self.seed.period as f64),
+        }
+    }
+}
+
+/*
+선형 가중 이동평균 (최근 값일수록 가중치 큼, 1..=period)
+- 가중 합계는 새 값이 들어올 때 (이전 가중 합계 - 이전 합계 + period * 새 값)
+*/
+#[derive(Debug)]
+pub struct Wma {
+    rolling: Rolling,
+    weighted: f64,
+}
+
+impl Wma {
+    pub fn new(period: usize) -> Self {
+        Self {
+            rolling: Rolling::new(period),
+            weighted: 0.0,
+        }
+    }
+
+    fn next_weighted(&self, value: f64) -> f64 {
+        let period = self.rolling.period;
+        let len = self.rolling.window.len();
+        if len == period {
+            self.weighted - self.rolling.sum + period as f64 * value
+        } else {
+            self.weighted + (len + 1) as f64 * value
+        }
+    }
+
+    fn divisor(&self) -> f64 {
+        let period = self.rolling.period as f64;
+        period * (period + 1.0) / 2.0
+    }
+
+    pub fn push(&mut self, value: f64) -> Option<f64> {
+        self.weighted = self.next_weighted(value);
+        self.rolling
+            .push(value)
+            .map(|_| self.weighted / self.divisor())
+    }
+
+    pub fn peek(&self, value: f64) -> Option<f64> {
+        self.rolling
+            .peek(value)
+            .map(|_| self.next_weighted(value) / self.divisor())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn closes(indicator: Box<dyn Indicator>, values: &[f64]) -> Vec<Option<f64>> {
+        let mut state = indicator.state();
+        values
+            .iter()
+            .map(|&close| {
+                let candle = Candlestick {
+                    close,
+                    ..Default::default()
+                };
+                state.push(0, &candle)[0]
+            })
+            .collect()
+    }
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("value");
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn wma_weights_recent_values() {
+        // (1*1 + 2*2 + 3*3) / 6, (2*1 + 3*2 + 4*3) / 6
+        let values = closes(
+            MovingAverage::build_wma(&[3.0], Source::Close),
+            &[1.0, 2.0, 3.0, 4.0],
+        );
+        assert_eq!(values[..2], [None, None]);
+        assert_close(values[2], 14.0 / 6.0);
+        assert_close(values[3], 20.0 / 6.0);
+
+        let mut wma = Wma::new(3);
+        for value in [1.0, 2.0, 3.0, 4.0] {
+            wma.push(value);
+        }
+        assert_close(wma.peek(5.0), 26.0 / 6.0);
+    }
+
+    #[test]
+    fn hma_follows_linear_series_without_lag() {
+        // HMA(4) = WMA2(2 * WMA2 - WMA4), 직선이면 마지막 값과 같음
+        let values = closes(
+            MovingAverage::build_hma(&[4.0], Source::Close),
+            &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+        );
+        assert!(values[..4].iter().all(Option::is_none));
+        assert_close(values[4], 5.0);
+        assert_close(values[5], 6.0);
+    }
+}
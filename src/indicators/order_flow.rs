@@ -0,0 +1,246 @@
+use super::{
+    param, Indicator, IndicatorState, LineStyle, Output, Param, ParamKind, RenderHint, Source,
+};
+use crate::Candlestick;
+use iced::Color;
+
+/*
+거래량 델타 (캔들별 시장가 매수 - 시장가 매도)
+- 매수 / 매도 거래량은 전략용 값으로만 출력
+- 체결 정보가 없는 캔들 (Bybit / OKX 과거 캔들) 은 값 없음
+*/
+#[derive(Debug, Clone)]
+pub struct VolumeDelta;
+
+impl VolumeDelta {
+    pub fn build(_params: &[f64], _source: Source) -> Box<dyn Indicator> {
+        Box::new(Self)
+    }
+}
+
+impl Indicator for VolumeDelta {
+    fn name(&self) -> String {
+        "Delta".to_string()
+    }
+
+    fn inputs(&self) -> Vec<Source> {
+        vec![Source::Volume]
+    }
+
+    fn params(&self) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![
+            Output {
+                name: "Delta",
+                color: Color::from_rgba(0.0, 0.8, 0.4, 0.6),
+                style: LineStyle::Histogram,
+            },
+            Output {
+                name: "Buy",
+                color: Color::from_rgb(0.0, 0.8, 0.4),
+                style: LineStyle::Hidden,
+            },
+            Output {
+                name: "Sell",
+                color: Color::from_rgb(0.8, 0.2, 0.2),
+                style: LineStyle::Hidden,
+            },
+        ]
+    }
+
+    fn render(&self) -> RenderHint {
+        RenderHint::SubPane {
+            range: None,
+            levels: Vec::new(),
+            band: None,
+        }
+    }
+
+    fn state(&self) -> Box<dyn IndicatorState> {
+        Box::new(VolumeDeltaState)
+    }
+}
+
+#[derive(Debug)]
+struct VolumeDeltaState;
+
+impl VolumeDeltaState {
+    fn outputs(candle: &Candlestick) -> Vec<Option<f64>> {
+        match candle.volume_delta() {
+            Some(delta) => vec![
+                Some(delta),
+                Some(candle.taker_buy_volume),
+                Some(candle.taker_sell_volume()),
+            ],
+            None => vec![None; 3],
+        }
+    }
+}
+
+impl IndicatorState for VolumeDeltaState {
+    fn push(&mut self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        Self::outputs(candle)
+    }
+
+    fn peek(&self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        Self::outputs(candle)
+    }
+}
+
+/*
+누적 거래량 델타 (CVD)
+- session 시간마다 (UTC 0 시 기준) 0 부터 다시 누적, 0 이면 초기화 없음
+- 체결 정보가 없는 캔들은 건너뜀 (누적 값 유지)
+*/
+#[derive(Debug, Clone)]
+pub struct CumulativeDelta {
+    pub session_hours: f64,
+}
+
+impl CumulativeDelta {
+    //params: [초기화 간격 (시간, 0 이면 없음)]
+    pub fn build(params: &[f64], _source: Source) -> Box<dyn Indicator> {
+        Box::new(Self {
+            session_hours: param(params, 0, 0.0).max(0.0),
+        })
+    }
+
+    fn session(&self, timestamp: u64) -> u64 {
+        if self.session_hours > 0.0 {
+            (timestamp as f64 / (self.session_hours * 3_600_000.0)).floor() as u64
+        } else {
+            0
+        }
+    }
+}
+
+impl Indicator for CumulativeDelta {
+    fn name(&self) -> String {
+        if self.session_hours > 0.0 {
+            format!("CVD({}h)", self.session_hours)
+        } else {
+            "CVD".to_string()
+        }
+    }
+
+    fn inputs(&self) -> Vec<Source> {
+        vec![Source::Volume]
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![Param {
+            name: "Reset (h)",
+            value: self.session_hours,
+            kind: ParamKind::Number,
+        }]
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output {
+            name: "CVD",
+            color: Color::from_rgb(0.2, 0.7, 1.0),
+            style: LineStyle::Line,
+        }]
+    }
+
+    fn render(&self) -> RenderHint {
+        RenderHint::SubPane {
+            range: None,
+            levels: Vec::new(),
+            band: None,
+        }
+    }
+
+    fn state(&self) -> Box<dyn IndicatorState> {
+        Box::new(CumulativeDeltaState {
+            indicator: self.clone(),
+            total: None,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct CumulativeDeltaState {
+    indicator: CumulativeDelta,
+    total: Option<(u64, f64)>, // (세션, 누적 델타)
+}
+
+impl CumulativeDeltaState {
+    fn next(&self, timestamp: u64, candle: &Candlestick) -> Option<(u64, f64)> {
+        let session = self.indicator.session(timestamp);
+        let total = match self.total {
+            Some((current, total)) if current == session => total,
+            _ => 0.0,
+        };
+        match candle.volume_delta() {
+            Some(delta) => Some((session, total + delta)),
+            None => self.total.filter(|(current, _)| *current == session),
+        }
+    }
+}
+
+impl IndicatorState for CumulativeDeltaState {
+    fn push(&mut self, timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        self.total = self.next(timestamp, candle);
+        vec![self.total.map(|(_, total)| total)]
+    }
+
+    fn peek(&self, timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        vec![self.next(timestamp, candle).map(|(_, total)| total)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 3_600_000;
+
+    fn candle(volume: f64, taker_buy_volume: f64, trades: u32) -> Candlestick {
+        Candlestick {
+            volume,
+            taker_buy_volume,
+            trades,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn volume_delta() {
+        let mut state = VolumeDelta::build(&[], Source::Close).state();
+        assert_eq!(
+            state.push(0, &candle(10.0, 7.0, 5)),
+            [Some(4.0), Some(7.0), Some(3.0)]
+        );
+        // 체결 정보가 없는 캔들
+        assert_eq!(state.push(1, &candle(10.0, 0.0, 0)), [None, None, None]);
+    }
+
+    #[test]
+    fn cumulative_delta_resets_by_session() {
+        // 4 시간마다 초기화, 체결 정보가 없는 캔들은 누적 값 유지
+        let mut state = CumulativeDelta::build(&[4.0], Source::Close).state();
+        let values: Vec<_> = [
+            (0, candle(10.0, 7.0, 5)),
+            (HOUR, candle(10.0, 0.0, 0)),
+            (2 * HOUR, candle(4.0, 1.0, 2)),
+            (4 * HOUR, candle(2.0, 2.0, 1)),
+            (5 * HOUR, candle(2.0, 0.0, 0)),
+        ]
+        .iter()
+        .map(|(timestamp, candle)| state.push(*timestamp, candle)[0])
+        .collect();
+        assert_eq!(
+            values,
+            [Some(4.0), Some(4.0), Some(2.0), Some(2.0), Some(2.0)]
+        );
+
+        // 초기화 없음
+        let mut state = CumulativeDelta::build(&[0.0], Source::Close).state();
+        state.push(0, &candle(10.0, 7.0, 5));
+        assert_eq!(state.peek(100 * HOUR, &candle(2.0, 2.0, 1)), [Some(6.0)]);
+    }
+}
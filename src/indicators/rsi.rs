@@ -0,0 +1,225 @@
+use super::moving_average::Rolling;
+use super::{
+    param, Indicator, IndicatorState, LineStyle, Output, Param, ParamKind, RenderHint, Source,
+};
+use crate::Candlestick;
+use iced::Color;
+
+/*
+RSI (Wilder 평활)
+- 첫 평균 상승 / 하락은 period 개 단순 평균, 이후 (이전 평균 * (period - 1) + 현재) / period
+- 하락 평균이 0 이면 100, 상승 평균이 0 이면 0 (바이낸스 / TradingView 와 같음)
+- signal > 0 이면 RSI 의 단순 이동평균을 신호선으로 같이 출력
+*/
+#[derive(Debug, Clone)]
+pub struct Rsi {
+    pub period: usize,
+    pub overbought: f64,
+    pub oversold: f64,
+    pub signal: usize,
+    pub source: Source,
+}
+
+impl Rsi {
+    //params: [기간, 과매수, 과매도, 신호선 기간 (0 이면 없음)]
+    pub fn build(params: &[f64], source: Source) -> Box<dyn Indicator> {
+        Box::new(Self {
+            period: (param(params, 0, 14.0) as usize).max(1),
+            overbought: param(params, 1, 70.0).clamp(0.0, 100.0),
+            oversold: param(params, 2, 30.0).clamp(0.0, 100.0),
+            signal: param(params, 3, 0.0) as usize,
+            source,
+        })
+    }
+}
+
+impl Indicator for Rsi {
+    fn name(&self) -> String {
+        format!("RSI({})", self.period)
+    }
+
+    fn inputs(&self) -> Vec<Source> {
+        vec![self.source]
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param {
+                name: "Period",
+                value: self.period as f64,
+                kind: ParamKind::Period,
+            },
+            Param {
+                name: "Overbought",
+                value: self.overbought,
+                kind: ParamKind::Number,
+            },
+            Param {
+                name: "Oversold",
+                value: self.oversold,
+                kind: ParamKind::Number,
+            },
+            Param {
+                name: "Signal",
+                value: self.signal as f64,
+                kind: ParamKind::OptionalPeriod,
+            },
+        ]
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        let mut outputs = vec![Output {
+            name: "RSI",
+            color: Color::from_rgb(0.0, 0.8, 0.8), // 청록색
+            style: LineStyle::Line,
+        }];
+        if self.signal > 0 {
+            outputs.push(Output {
+                name: "Signal",
+                color: Color::from_rgb(1.0, 0.647, 0.0),
+                style: LineStyle::Line,
+            });
+        }
+        outputs
+    }
+
+    fn render(&self) -> RenderHint {
+        RenderHint::SubPane {
+            range: Some((0.0, 100.0)),
+            levels: vec![100.0, self.overbought, 50.0, self.oversold, 0.0],
+            band: Some((
+                self.oversold.min(self.overbought),
+                self.overbought.max(self.oversold),
+            )),
+        }
+    }
+
+    fn state(&self) -> Box<dyn IndicatorState> {
+        Box::new(RsiState {
+            period: self.period,
+            source: self.source,
+            prev: None,
+            seed: 0,
+            avg_gain: 0.0,
+            avg_loss: 0.0,
+            signal: (self.signal > 0).then(|| Rolling::new(self.signal)),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct RsiState {
+    period: usize,
+    source: Source,
+    prev: Option<f64>,
+    seed: usize, // 지금까지 반영한 변화 수 (period 까지만 셈)
+    avg_gain: f64,
+    avg_loss: f64,
+    signal: Option<Rolling>,
+}
+
+impl RsiState {
+    //다음 (평균 상승, 평균 하락) - 첫 period 개 동안은 합계
+    fn next(&self, value: f64) -> Option<(f64, f64)> {
+        let change = value - self.prev?;
+        let (gain, loss) = (change.max(0.0), (-change).max(0.0));
+        let period = self.period as f64;
+
+        if self.seed + 1 < self.period {
+            Some((self.avg_gain + gain, self.avg_loss + loss))
+        } else if self.seed + 1 == self.period {
+            Some((
+                (self.avg_gain + gain) / period,
+                (self.avg_loss + loss) / period,
+            ))
+        } else {
+            Some((
+                (self.avg_gain * (period - 1.0) + gain) / period,
+                (self.avg_loss * (period - 1.0) + loss) / period,
+            ))
+        }
+    }
+
+    //seed 가 period 에 도달한 뒤부터 값 있음
+    fn rsi(&self, seed: usize, avg_gain: f64, avg_loss: f64) -> Option<f64> {
+        if seed < self.period {
+            None
+        } else if avg_loss == 0.0 {
+            Some(100.0)
+        } else if avg_gain == 0.0 {
+            Some(0.0)
+        } else {
+            Some(100.0 - 100.0 / (1.0 + avg_gain / avg_loss))
+        }
+    }
+}
+
+impl IndicatorState for RsiState {
+    fn push(&mut self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        let value = self.source.value(candle);
+        let next = self.next(value);
+        self.prev = Some(value);
+
+        let Some((avg_gain, avg_loss)) = next else {
+            return vec![None, None];
+        };
+        self.avg_gain = avg_gain;
+        self.avg_loss = avg_loss;
+        self.seed = (self.seed + 1).min(self.period);
+
+        let rsi = self.rsi(self.seed, avg_gain, avg_loss);
+        let signal = match (rsi, &mut self.signal) {
+            (Some(rsi), Some(signal)) => signal.push(rsi).map(|sum| sum / signal.period as f64),
+            _ => None,
+        };
+        vec![rsi, signal]
+    }
+
+    fn peek(&self, _timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        let rsi = self
+            .next(self.source.value(candle))
+            .and_then(|(avg_gain, avg_loss)| {
+                self.rsi((self.seed + 1).min(self.period), avg_gain, avg_loss)
+            });
+        let signal = match (rsi, &self.signal) {
+            (Some(rsi), Some(signal)) => signal.peek(rsi).map(|sum| sum / signal.period as f64),
+            _ => None,
+        };
+        vec![rsi, signal]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rsi(params: &[f64], values: &[f64]) -> Vec<Option<f64>> {
+        let mut state = Rsi::build(params, Source::Close).state();
+        values
+            .iter()
+            .map(|&close| {
+                let candle = Candlestick {
+                    close,
+                    ..Default::default()
+                };
+                state.push(0, &candle)[0]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn wilder_smoothing() {
+        // 변화 +1 +1 -1 : 평균 상승 2/3, 하락 1/3 -> RS 2
+        // 변화 +2 : 상승 (2/3 * 2 + 2) / 3 = 10/9, 하락 (1/3 * 2) / 3 = 2/9 -> RS 5
+        let values = rsi(&[3.0], &[10.0, 11.0, 12.0, 11.0, 13.0]);
+        assert_eq!(values[..3], [None, None, None]);
+        assert!((values[3].unwrap() - (100.0 - 100.0 / 3.0)).abs() < 1e-9);
+        assert!((values[4].unwrap() - (100.0 - 100.0 / 6.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn only_gains_is_100() {
+        let values = rsi(&[2.0], &[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(values[2..], [Some(100.0), Some(100.0)]);
+    }
+}
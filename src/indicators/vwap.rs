@@ -0,0 +1,322 @@
+use super::{
+    param, Indicator, IndicatorState, LineStyle, Output, Param, ParamKind, RenderHint, Source,
+};
+use crate::Candlestick;
+use iced::Color;
+
+const HOUR_MS: f64 = 3_600_000.0;
+
+//VWAP 누적 구간
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Anchor {
+    Session { hours: f64, start_hour: f64 }, // UTC start_hour 부터 hours 시간마다 초기화 (24 면 UTC 하루)
+    Candle(u64),                             // 이 캔들부터 누적 (차트 클릭)
+}
+
+/*
+VWAP
+- 캔들 가격은 체결 기준 거래대금 / 거래량 (실시간 체결을 그대로 가중), 없거나 캔들 범위를 벗어나면 입력 값
+- 밴드 = VWAP ± 배수 * 거래량 가중 표준편차 (배수 0 이면 밴드 없음)
+*/
+#[derive(Debug, Clone)]
+pub struct Vwap {
+    pub anchor: Anchor,
+    pub multiplier: f64,
+    pub source: Source,
+}
+
+impl Vwap {
+    //params: [세션 길이 (시간), 시작 시각 (UTC 시), 밴드 배수]
+    pub fn build_session(params: &[f64], source: Source) -> Box<dyn Indicator> {
+        Box::new(Self {
+            anchor: Anchor::Session {
+                hours: param(params, 0, 24.0).max(1.0),
+                start_hour: param(params, 1, 0.0) % 24.0,
+            },
+            multiplier: param(params, 2, 1.0),
+            source,
+        })
+    }
+
+    //params: [시작 캔들 (ms), 밴드 배수]
+    pub fn build_anchored(params: &[f64], source: Source) -> Box<dyn Indicator> {
+        Box::new(Self {
+            anchor: Anchor::Candle(param(params, 0, 0.0) as u64),
+            multiplier: param(params, 1, 1.0),
+            source,
+        })
+    }
+
+    fn has_bands(&self) -> bool {
+        self.multiplier > 0.0
+    }
+}
+
+impl Indicator for Vwap {
+    fn name(&self) -> String {
+        match self.anchor {
+            Anchor::Session { hours, start_hour } if hours == 24.0 && start_hour == 0.0 => {
+                "VWAP".to_string()
+            }
+            Anchor::Session { hours, start_hour } => {
+                format!("VWAP({}h from {}:00 UTC)", hours, start_hour)
+            }
+            Anchor::Candle(timestamp) => {
+                let time = chrono::DateTime::from_timestamp_millis(timestamp as i64)
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                format!("AVWAP({})", time)
+            }
+        }
+    }
+
+    fn inputs(&self) -> Vec<Source> {
+        vec![self.source, Source::Volume]
+    }
+
+    fn params(&self) -> Vec<Param> {
+        let mut params = match self.anchor {
+            Anchor::Session { hours, start_hour } => vec![
+                Param {
+                    name: "Session (h)",
+                    value: hours,
+                    kind: ParamKind::Number,
+                },
+                Param {
+                    name: "Start (h UTC)",
+                    value: start_hour,
+                    kind: ParamKind::Number,
+                },
+            ],
+            Anchor::Candle(timestamp) => vec![Param {
+                name: "Anchor (ms)",
+                value: timestamp as f64,
+                kind: ParamKind::Timestamp,
+            }],
+        };
+        params.push(Param {
+            name: "Band",
+            value: self.multiplier,
+            kind: ParamKind::Number,
+        });
+        params
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        let color = match self.anchor {
+            Anchor::Session { .. } => Color::from_rgb(0.95, 0.95, 0.95),
+            Anchor::Candle(_) => Color::from_rgb(0.0, 0.8, 0.8),
+        };
+        let mut outputs = vec![Output {
+            name: "VWAP",
+            color,
+            style: LineStyle::Line,
+        }];
+        if self.has_bands() {
+            for name in ["Upper", "Lower"] {
+                outputs.push(Output {
+                    name,
+                    color: Color { a: 0.5, ..color },
+                    style: LineStyle::Line,
+                });
+            }
+        }
+        outputs
+    }
+
+    fn render(&self) -> RenderHint {
+        RenderHint::Overlay
+    }
+
+    fn state(&self) -> Box<dyn IndicatorState> {
+        Box::new(VwapState {
+            anchor: self.anchor,
+            multiplier: self.multiplier,
+            bands: self.has_bands(),
+            source: self.source,
+            sums: None,
+        })
+    }
+
+    fn fill(&self) -> Option<(usize, usize)> {
+        self.has_bands().then_some((1, 2))
+    }
+}
+
+//구간 누적 값
+#[derive(Debug, Clone, Copy)]
+struct Sums {
+    key: i64,     // 구간 번호 (바뀌면 초기화)
+    volume: f64,  // 거래량 합
+    price: f64,   // 가격 * 거래량 합
+    squares: f64, // 가격^2 * 거래량 합
+}
+
+#[derive(Debug)]
+struct VwapState {
+    anchor: Anchor,
+    multiplier: f64,
+    bands: bool,
+    source: Source,
+    sums: Option<Sums>,
+}
+
+impl VwapState {
+    //캔들이 속한 구간 (앵커 이전이면 None)
+    fn key(&self, timestamp: u64) -> Option<i64> {
+        match self.anchor {
+            Anchor::Session { hours, start_hour } => {
+                let since = timestamp as f64 - start_hour * HOUR_MS;
+                Some((since / (hours * HOUR_MS)).floor() as i64)
+            }
+            Anchor::Candle(anchor) => (timestamp >= anchor).then_some(0),
+        }
+    }
+
+    //체결 가중 평균 가격 (거래대금이 없거나 맞지 않으면 입력 값)
+    fn price(&self, candle: &Candlestick) -> f64 {
+        if candle.volume > 0.0 && candle.quote_volume > 0.0 {
+            let price = candle.quote_volume / candle.volume;
+            if (candle.low..=candle.high).contains(&price) {
+                return price;
+            }
+        }
+        self.source.value(candle)
+    }
+
+    fn next(&self, timestamp: u64, candle: &Candlestick) -> Option<Sums> {
+        let key = self.key(timestamp)?;
+        let sums = match self.sums {
+            Some(sums) if sums.key == key => sums,
+            _ => Sums {
+                key,
+                volume: 0.0,
+                price: 0.0,
+                squares: 0.0,
+            },
+        };
+        let price = self.price(candle);
+        Some(Sums {
+            key,
+            volume: sums.volume + candle.volume,
+            price: sums.price + price * candle.volume,
+            squares: sums.squares + price * price * candle.volume,
+        })
+    }
+
+    fn outputs(&self, sums: Option<Sums>) -> Vec<Option<f64>> {
+        let Some(sums) = sums.filter(|sums| sums.volume > 0.0) else {
+            return vec![None; if self.bands { 3 } else { 1 }];
+        };
+        let vwap = sums.price / sums.volume;
+        if !self.bands {
+            return vec![Some(vwap)];
+        }
+        let deviation = (sums.squares / sums.volume - vwap * vwap).max(0.0).sqrt();
+        vec![
+            Some(vwap),
+            Some(vwap + self.multiplier * deviation),
+            Some(vwap - self.multiplier * deviation),
+        ]
+    }
+}
+
+impl IndicatorState for VwapState {
+    fn push(&mut self, timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        let sums = self.next(timestamp, candle);
+        if sums.is_some() {
+            self.sums = sums;
+        }
+        self.outputs(sums)
+    }
+
+    fn peek(&self, timestamp: u64, candle: &Candlestick) -> Vec<Option<f64>> {
+        self.outputs(self.next(timestamp, candle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 3_600_000;
+
+    fn candle(close: f64, volume: f64) -> Candlestick {
+        Candlestick {
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume,
+            ..Default::default()
+        }
+    }
+
+    fn run(indicator: Box<dyn Indicator>, candles: &[(u64, Candlestick)]) -> Vec<Vec<Option<f64>>> {
+        let mut state = indicator.state();
+        candles
+            .iter()
+            .map(|(timestamp, candle)| state.push(*timestamp, candle))
+            .collect()
+    }
+
+    #[test]
+    fn session_vwap_with_bands() {
+        // (10 * 1 + 20 * 3) / 4 = 17.5, 분산 (100 + 1200) / 4 - 17.5^2 = 18.75
+        let outputs = run(
+            Vwap::build_session(&[24.0, 0.0, 2.0], Source::Close),
+            &[
+                (0, candle(10.0, 1.0)),
+                (HOUR, candle(20.0, 3.0)),
+                (24 * HOUR, candle(30.0, 1.0)),
+            ],
+        );
+        let deviation = 18.75f64.sqrt();
+        assert_eq!(outputs[0], [Some(10.0), Some(10.0), Some(10.0)]);
+        assert_eq!(
+            outputs[1],
+            [
+                Some(17.5),
+                Some(17.5 + 2.0 * deviation),
+                Some(17.5 - 2.0 * deviation)
+            ]
+        );
+        // 다음 날 (UTC 0 시) 초기화
+        assert_eq!(outputs[2][0], Some(30.0));
+    }
+
+    #[test]
+    fn session_start_hour() {
+        // 8 시 시작 세션이면 7 시 캔들은 전날 세션
+        let outputs = run(
+            Vwap::build_session(&[24.0, 8.0, 0.0], Source::Close),
+            &[
+                (0, candle(10.0, 1.0)),
+                (7 * HOUR, candle(20.0, 1.0)),
+                (8 * HOUR, candle(30.0, 1.0)),
+            ],
+        );
+        assert_eq!(outputs[1], [Some(15.0)]);
+        assert_eq!(outputs[2], [Some(30.0)]);
+    }
+
+    #[test]
+    fn anchored_vwap_uses_traded_price() {
+        // 앵커 이전은 값 없음, 거래대금 / 거래량 이 캔들 범위 안이면 체결 가격 사용
+        let traded = Candlestick {
+            quote_volume: 21.0,
+            ..candle(10.0, 2.0)
+        };
+        let outside = Candlestick {
+            quote_volume: 100.0,
+            ..candle(20.0, 2.0)
+        };
+        let outputs = run(
+            Vwap::build_anchored(&[HOUR as f64, 0.0], Source::Close),
+            &[(0, candle(5.0, 1.0)), (HOUR, traded), (2 * HOUR, outside)],
+        );
+        assert_eq!(outputs[0], [None]);
+        assert_eq!(outputs[1], [Some(10.5)]);
+        assert_eq!(outputs[2], [Some((21.0 + 40.0) / 4.0)]);
+    }
+}
@@ -1,59 +1,114 @@
 use dotenv::dotenv;
 mod api;
+mod indicators;
 mod models;
+mod store;
 mod trading;
 mod ui;
 mod utils;
-use crate::models::SignalScoring;
+use crate::indicators::{
+    moving_average::MaType, Cross, EventKind, IndicatorCache, IndicatorConfig, IndicatorEvent,
+    PaletteColor, Source, PALETTE,
+};
+use crate::models::{
+    bars::{BarBuilder, BarKind},
+    footprint::FootprintStore,
+    SignalScoring, Symbol,
+};
 use api::{
-    account::binance_account_connection,
-    binance::{binance_connection, fetch_candles, fetch_candles_async, get_top_volume_pairs},
+    account::{
+        binance_account_connection, binance_coin_m_account_connection,
+        binance_spot_account_connection,
+    },
+    binance::{
+        binance_connection, fetch_candles_async, get_exchange_symbols, get_top_volume_pairs,
+    },
+    bybit::{bybit_account_connection, bybit_connection},
     excution::execute_trade,
-    BinanceTrade, FuturesAccountInfo,
+    okx::{okx_account_connection, okx_connection},
+    replay::{replay_connection, ReplayCommand, ReplaySource, ReplaySpeed},
+    BinanceTrade, FuturesAccountInfo, MarketType, SpotAccountInfo,
 };
 use iced::{
     futures::channel::mpsc,
     time::{Duration, Instant},
-    widget::{canvas::Canvas, container, pane_grid, pick_list, text, Column, Container, Row, Text},
+    widget::{
+        canvas::Canvas, container, pane_grid, pick_list, text, text_input, Column, Container, Row,
+        Text,
+    },
     Element, Length,
     Length::FillPortion,
-    Size, Subscription,
+    Size, Subscription, Task,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap, VecDeque};
+use store::candles::{backfill_candles_async, load_candles, sync_candles_async, CandleStore};
+use store::export::{export_candles, import_candles, parse_time, FileFormat};
+use store::indicators::{load_indicators, save_indicators};
 use trading::{
+    atr_stop_distance,
     markey_order::{market_buy, market_sell},
-    TradeType,
+    stop_price, TradeType,
 };
 use ui::chart::calculate_scored_signals;
+use ui::transform::{ChartStyle, TransformCache};
 use ui::{
     buttons::ma_controls,
+    data::{data_controls, replay_controls},
     infos::{account_info, coin_info, current_position},
     trading::{auto_trading_toggle, order_buttons},
-    CandleType, Candlestick, Chart, ChartState,
+    CandleType, Candlestick, Chart, ChartOptions, ChartState,
 };
-use utils::{constant as uc, logs as ul};
+use utils::{constant as uc, format_price, logs as ul};
 //Main
 pub struct Futurx {
     panes: pane_grid::State<Pane>,
     candlesticks: BTreeMap<u64, Candlestick>, // 캔들스틱 데이터 저장
-    selected_coin: String,                    // 현재 선택된 코인
+    selected_symbol: String,                  // 현재 선택된 심볼 (거래소 심볼, coin_list 키)
+    selected_market: MarketType,              // 선택된 마켓 (선물/현물)
     pub selected_candle_type: CandleType,     // 선택된 캔들 타입 (1분,3분,일봉)
-    coin_list: HashMap<String, CoinInfo>,     // 코인 목록 정보
-    auto_scroll: bool,                        // 자동 스크롤 여부
-    ws_sender: Option<mpsc::Sender<String>>,  // WebSocket 메시지 전송자
-    show_ma5: bool,                           // 5일 이동평균선 표시 여부
-    show_ma10: bool,                          // 10일 이동평균선 표시 여부
-    show_ma20: bool,                          // 20일 이동평균선 표시 여부
-    show_ma200: bool,                         // 200일 이동평균선 표시 여부
-    loading_more: bool,                       // 추가 데이터 로딩 중 여부
-    oldest_date: Option<String>,              // 가장 오래된 캔들 날짜
+    custom_timeframe: String,                 // 사용자 지정 타임프레임 입력 (7m, 45m, 2d)
+    bar_kind: BarKind,                        // 캔들 생성 방식 (시간 / 체결 수 / 거래량 등)
+    bar_size: String,                         // 비시간 캔들 크기 입력
+    bar_builder: Option<BarBuilder>,          // 비시간 캔들 생성기 (시간 캔들이면 None)
+    chart_style: ChartStyle,                  // 캔들 표시 방식 (Heikin-Ashi 등)
+    transformed_indicators: bool,             // 지표 / 패턴을 변환된 캔들로 계산
+    transformed: TransformCache, // 지표 / 패턴 계산용 변환 캔들 (마감 캔들은 한 번만 변환)
+    coin_list: HashMap<String, CoinInfo>, // 코인 목록 정보
+    auto_scroll: bool,           // 자동 스크롤 여부
+    ws_sender: Option<mpsc::Sender<Symbol>>, // WebSocket 메시지 전송자
+    indicators: Vec<IndicatorConfig>, // 차트 지표 구성
+    indicator_cache: IndicatorCache, // 지표 계산 상태 (새 캔들만 반영)
+    param_edit: Option<(usize, usize, String)>, // 수정 중인 지표 파라미터 입력 (지표, 파라미터, 값)
+    anchor_pick: bool,           // 다음 차트 클릭으로 앵커 VWAP 시작
+    footprints: FootprintStore,  // 최근 캔들 가격대별 매도 / 매수 거래량 (실시간 체결)
+    footprint_enabled: bool,     // 풋프린트 표시
+    loading_more: bool,          // 추가 데이터 로딩 중 여부
+    needs_backfill: bool,        // 재연결 / 절전 후 캔들 다시 채우기 필요
+    backfill_request: Option<BackfillRequest>, // 캔들 동기화 / 다시 채우기 (update 에서 Task 로 실행)
+    backfill_id: u64, // 마지막 동기화 / 다시 채우기 요청 번호 (이전 요청 결과는 버림)
+    backfilling: bool, // 캔들 다시 채우는 중
+    coin_list_request: Option<MarketType>, // 불러올 마켓 코인 목록 (update 에서 Task 로 실행)
+    loading_market: Option<MarketType>, // 코인 목록을 불러오는 중인 마켓
+    last_tick_time: u64, // 마지막 틱 시각 (ms, 절전 감지용)
+    oldest_date: Option<String>, // 가장 오래된 캔들 날짜
     account_info: Option<FuturesAccountInfo>, // 계좌 정보
-    alerts: VecDeque<Alert>,                  // 알림 메시지 큐
-    auto_trading_enabled: bool,               // 자동매매 활성화 상태
-    last_trade_time: Option<Instant>,         // 마지막 거래 시간
+    spot_account_info: Option<SpotAccountInfo>, // 현물 계좌 정보
+    coin_m_account_info: Option<FuturesAccountInfo>, // COIN-M 계좌 정보
+    bybit_account_info: Option<FuturesAccountInfo>, // Bybit 계좌 정보
+    okx_account_info: Option<FuturesAccountInfo>, // OKX 계좌 정보
+    alerts: VecDeque<Alert>, // 알림 메시지 큐
+    auto_trading_enabled: bool, // 자동매매 활성화 상태
+    last_trade_time: Option<Instant>, // 마지막 거래 시간
     alert_sender: mpsc::Sender<(String, AlertType)>, // 알림 메시지 전송자
-    average_prices: HashMap<String, f64>,     // 평균 가격 정보
+    average_prices: HashMap<String, f64>, // 평균 가격 정보
+    export_from: String, // 내보내기 시작일 (YYYY-MM-DD)
+    export_to: String, // 내보내기 종료일 (YYYY-MM-DD)
+    import_path: String, // 가져올 파일 경로
+    replay_enabled: bool, // 리플레이 모드 여부
+    replay_sender: Option<mpsc::Sender<ReplayCommand>>, // 리플레이 명령 전송자
+    replay_speed: ReplaySpeed, // 리플레이 속도
+    replay_input: String, // 리플레이 tape 경로 또는 시작일
 
     scored_signals_enabled: bool,
     buy_scored_signals: BTreeMap<u64, SignalScoring>,
@@ -64,6 +119,12 @@ enum Pane {
     LeftSidebar,
     RightSidebar,
 }
+//REST 캔들 조회 요청
+#[derive(Debug, Clone, Copy)]
+enum BackfillRequest {
+    Sync,      // 저장소 동기화 (마지막 저장 캔들 이후 + 빈 구간)
+    From(u64), // 끊긴 구간 시작부터 다시 채우기
+}
 #[derive(Debug, Clone)]
 struct Alert {
     message: String,       // 알림 메시지 내용
@@ -84,45 +145,72 @@ struct Trade {
     id: u64,
     price: String,
     qty: String,
-    #[serde(rename = "quoteQty")]
-    quote_qty: String,
+    #[serde(rename = "quoteQty", default)]
+    quote_qty: String, // COIN-M 은 baseQty
     #[serde(rename = "isBuyer")]
     is_buyer: bool,
     time: u64,
 }
 #[derive(Debug, Clone)]
 pub enum Message {
-    PaneDragged(pane_grid::DragEvent),             // 매개변수 필요
-    PaneResized(pane_grid::ResizeEvent),           // 매개변수 필요
-    AddCandlestick((u64, BinanceTrade)),           // 캔들스틱 추가
-    RemoveCandlestick,                             // 캔들스틱 제거
-    SelectCoin(String),                            // 코인 선택
-    UpdateCoinPrice(String, f64, f64),             // 코인 가격 업데이트
-    SelectCandleType(CandleType),                  // 캔들 타입 선택
-    Error,                                         // 에러 발생
-    WebSocketInit(mpsc::Sender<String>),           // WebSocket 초기화
-    UpdatePrice(String, f64, f64),                 // 가격 업데이트
-    ToggleMA5,                                     // 5일 이동평균선 토글
-    ToggleMA10,                                    // 10일 이동평균선 토글
-    ToggleMA20,                                    // 20일 이동평균선 토글
-    ToggleMA200,                                   // 200일 이동평균선 토글
-    LoadMoreCandles,                               // 추가 캔들 로드
+    PaneDragged(pane_grid::DragEvent),   // 매개변수 필요
+    PaneResized(pane_grid::ResizeEvent), // 매개변수 필요
+    AddCandlestick((u64, BinanceTrade)), // 캔들스틱 추가
+    RemoveCandlestick,                   // 캔들스틱 제거
+    SelectCoin(String),                  // 코인 선택 (거래소 심볼)
+    SelectMarket(MarketType),            // 마켓 선택 (선물/현물)
+    CoinListLoaded(MarketType, HashMap<String, CoinInfo>), // 마켓 코인 목록 로드 완료
+    UpdateCoinPrice(String, f64, f64),   // 코인 가격 업데이트
+    SelectCandleType(CandleType),        // 캔들 타입 선택
+    CustomTimeframeChanged(String),      // 사용자 지정 타임프레임 입력
+    ApplyCustomTimeframe,                // 사용자 지정 타임프레임 적용
+    SelectBarKind(BarKind),              // 캔들 생성 방식 선택
+    BarSizeChanged(String),              // 비시간 캔들 크기 입력
+    ApplyBarSize,                        // 비시간 캔들 크기 적용
+    SelectChartStyle(ChartStyle),        // 캔들 표시 방식 선택
+    ToggleTransformedIndicators,         // 지표 입력 (원본 / 변환) 토글
+    Error,                               // 에러 발생
+    WebSocketInit(mpsc::Sender<Symbol>), // WebSocket 초기화
+    StreamConnected,                     // 체결 스트림 (재)연결됨
+    UpdatePrice(String, f64, f64),       // 가격 업데이트
+    ToggleIndicator(usize),              // 지표 표시 토글 (indicators 인덱스)
+    SelectIndicatorSource(usize, Source), // 지표 입력 값 변경
+    SelectMaType(usize, MaType),         // 이동평균 종류 변경
+    IndicatorParamChanged(usize, usize, String), // 지표 파라미터 입력 (지표, 파라미터, 값)
+    ApplyIndicatorParam,                 // 지표 파라미터 적용
+    SelectIndicatorColor(usize, PaletteColor), // 지표 색 변경
+    AddIndicator(&'static str),          // 지표 추가 (registry key)
+    RemoveIndicator(usize),              // 지표 삭제
+    ToggleAnchorPick,                    // 앵커 VWAP 시작 캔들 선택 모드
+    ChartClicked(u64),                   // 차트 캔들 클릭 (캔들 시각)
+    ToggleFootprint,                     // 풋프린트 표시 토글
+    LoadMoreCandles,                     // 추가 캔들 로드
     MoreCandlesLoaded(BTreeMap<u64, Candlestick>), // 추가 캔들 로드 완료
+    CandlesBackfilled(
+        u64,
+        String,
+        CandleType,
+        Result<BTreeMap<u64, Candlestick>, String>,
+    ), // 동기화 / 다시 채운 캔들 (요청 번호, 심볼, 캔들 타입)
     TryBuy {
         // 매수 시도
         price: f64,
-        strength: f32,
+        strength: f64,
         timestamp: u64,
         indicators: TradeIndicators,
     },
     TrySell {
         // 매도 시도
         price: f64,
-        strength: f32,
+        strength: f64,
         timestamp: u64,
         indicators: TradeIndicators,
     },
     UpdateAccountInfo(FuturesAccountInfo), // 계좌 정보 업데이트
+    UpdateSpotAccountInfo(SpotAccountInfo), // 현물 계좌 정보 업데이트
+    UpdateCoinMAccountInfo(FuturesAccountInfo), // COIN-M 계좌 정보 업데이트
+    UpdateBybitAccountInfo(FuturesAccountInfo), // Bybit 계좌 정보 업데이트
+    UpdateOkxAccountInfo(FuturesAccountInfo), // OKX 계좌 정보 업데이트
     FetchError(String),                    // 데이터 가져오기 에러
     AddAlert(String, AlertType),           // 알림 추가
     RemoveAlert,                           // 알림 제거
@@ -132,22 +220,77 @@ pub enum Message {
     MarketSell,                            // 시장가 매도
     UpdateAveragePrice(String, f64),       // 평균가격 업데이트
     ToggleScoredSignals,
+    ExportFromChanged(String),               // 내보내기 시작일 입력
+    ExportToChanged(String),                 // 내보내기 종료일 입력
+    ExportCandles(FileFormat),               // 캔들 내보내기 (CSV / Parquet)
+    ImportPathChanged(String),               // 가져올 파일 경로 입력
+    ImportCandles,                           // 캔들 가져오기
+    ToggleReplay,                            // 리플레이 모드 토글
+    ReplayInit(mpsc::Sender<ReplayCommand>), // 리플레이 초기화
+    ReplayInputChanged(String),              // 리플레이 tape 경로 / 시작일 입력
+    SelectReplaySpeed(ReplaySpeed),          // 리플레이 속도 선택
+    StartReplay,                             // 리플레이 시작
+    StepReplay,                              // 리플레이 한 단계 진행
+    ReplayStarted(u64),                      // 리플레이 시작됨 (첫 이벤트 시각)
+    ReplayFinished,                          // 리플레이 종료
+}
+impl Message {
+    //캔들 / 지표 구성을 바꾸지 않는 메세지 (지표 동기화 생략)
+    fn keeps_candles(&self) -> bool {
+        matches!(
+            self,
+            Message::PaneDragged(_)
+                | Message::PaneResized(_)
+                | Message::UpdateCoinPrice(..)
+                | Message::CustomTimeframeChanged(_)
+                | Message::BarSizeChanged(_)
+                | Message::Error
+                | Message::WebSocketInit(_)
+                | Message::StreamConnected
+                | Message::UpdatePrice(..)
+                | Message::IndicatorParamChanged(..)
+                | Message::ToggleAnchorPick
+                | Message::ToggleFootprint
+                | Message::TryBuy { .. }
+                | Message::TrySell { .. }
+                | Message::UpdateAccountInfo(_)
+                | Message::UpdateSpotAccountInfo(_)
+                | Message::UpdateCoinMAccountInfo(_)
+                | Message::UpdateBybitAccountInfo(_)
+                | Message::UpdateOkxAccountInfo(_)
+                | Message::FetchError(_)
+                | Message::AddAlert(..)
+                | Message::RemoveAlert
+                | Message::Tick
+                | Message::ToggleAutoTrading
+                | Message::MarketBuy
+                | Message::MarketSell
+                | Message::UpdateAveragePrice(..)
+                | Message::ToggleScoredSignals
+                | Message::ExportFromChanged(_)
+                | Message::ExportToChanged(_)
+                | Message::ExportCandles(_)
+                | Message::ImportPathChanged(_)
+                | Message::ReplayInputChanged(_)
+                | Message::SelectReplaySpeed(_)
+        )
+    }
 }
+
 //코인 정보 구조체
 #[derive(Debug, Clone)]
-struct CoinInfo {
-    symbol: String, // 코인 심볼
-    name: String,   // 코인 이름
+pub struct CoinInfo {
+    symbol: Symbol, // 심볼 정보 (exchangeInfo)
     price: f64,     // 현재 가격
 }
 
 // 거래 지표 정보를 담는 구조체
 #[derive(Debug, Clone)]
 pub struct TradeIndicators {
-    rsi: f32,          // RSI 지표
-    ma5: f32,          // 5일 이동평균
-    ma20: f32,         // 20일 이동평균
-    volume_ratio: f32, // 거래량 비율
+    rsi: f64,          // RSI 지표
+    ma5: f64,          // 5일 이동평균
+    ma20: f64,         // 20일 이동평균
+    volume_ratio: f64, // 거래량 비율
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -155,48 +298,56 @@ struct OrderBookEntry {}
 #[derive(Debug, Clone)]
 pub struct OrderBool {}
 
-impl Default for Futurx {
-    fn default() -> Self {
-        // 거래량 상위 20개 코인 가져오기
-        let runtime = tokio::runtime::Runtime::new().unwrap();
-        let top_pairs = runtime.block_on(async {
-            match get_top_volume_pairs().await {
-                Ok(pairs) => pairs,
-                Err(e) => {
-                    println!("Error fetching top pairs: {}", e);
-                    vec![] // 에러 시 빈 벡터 반환
-                }
-            }
-        });
+//마켓별 거래량 상위 20개 코인 목록 (시작 시 한 번)
+fn load_coin_list(market_type: MarketType) -> HashMap<String, CoinInfo> {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(fetch_coin_list_async(market_type))
+}
 
-        let mut coin_list = HashMap::new();
+//마켓별 거래량 상위 20개 코인 목록 (키: 거래소 심볼)
+async fn fetch_coin_list_async(market_type: MarketType) -> HashMap<String, CoinInfo> {
+    let symbols = get_exchange_symbols(market_type).await.unwrap_or_else(|e| {
+        println!("Error fetching exchange info: {}", e);
+        vec![]
+    });
+    let top_pairs = get_top_volume_pairs(market_type).await.unwrap_or_else(|e| {
+        println!("Error fetching top pairs: {}", e);
+        vec![] // 에러 시 빈 벡터 반환
+    });
+
+    let mut symbols: HashMap<String, Symbol> = symbols
+        .into_iter()
+        .map(|symbol| (symbol.exchange_symbol.clone(), symbol))
+        .collect();
+    let mut coin_list = HashMap::new();
+
+    // 상위 20개 코인으로 초기화
+    for (exchange_symbol, _volume) in top_pairs {
+        if coin_list.len() >= 20 {
+            break;
+        }
+        if let Some(symbol) = symbols.remove(&exchange_symbol) {
+            coin_list.insert(exchange_symbol, CoinInfo { symbol, price: 0.0 });
+        }
+    }
 
-        // 상위 20개 코인으로 초기화
-        for (symbol, _volume) in top_pairs {
-            let symbol = symbol.strip_suffix("USDT").unwrap_or(&symbol);
+    // 만약 API 호출이 실패하면 기본 리스트 사용
+    if coin_list.is_empty() {
+        for base in &uc::DEFAULT_ARR {
+            let symbol = Symbol::fallback(market_type, base);
             coin_list.insert(
-                symbol.to_string(),
-                CoinInfo {
-                    symbol: format!("{}-USDT", symbol),
-                    name: symbol.to_string(),
-                    price: 0.0,
-                },
+                symbol.exchange_symbol.clone(),
+                CoinInfo { symbol, price: 0.0 },
             );
         }
+    }
+    coin_list
+}
 
-        // 만약 API 호출이 실패하면 기본 리스트 사용
-        if coin_list.is_empty() {
-            for symbol in &uc::DEFAULT_ARR {
-                coin_list.insert(
-                    symbol.to_string(),
-                    CoinInfo {
-                        symbol: format!("{}-USDT", symbol),
-                        name: symbol.to_string(),
-                        price: 0.0,
-                    },
-                );
-            }
-        }
+impl Default for Futurx {
+    fn default() -> Self {
+        // 거래량 상위 20개 코인 가져오기
+        let coin_list = load_coin_list(MarketType::Futures);
         //pannel 정의
         let (mut panes, first_pane) = pane_grid::State::new(Pane::Chart);
         let a = panes
@@ -206,27 +357,62 @@ impl Default for Futurx {
 
         let (alert_sender, alert_receiver) = mpsc::channel(100);
 
+        let selected_symbol = MarketType::Futures.default_symbol().to_string();
+        let symbol = coin_list
+            .get(&selected_symbol)
+            .map(|info| info.symbol.clone())
+            .unwrap_or_else(|| Symbol::fallback(MarketType::Futures, "BTC"));
+
         Self {
             panes,
-            candlesticks: fetch_candles("USDT-BTC", &CandleType::Day, None).unwrap_or_default(),
-            selected_coin: "BTC".to_string(),
+            candlesticks: load_candles(&symbol, &CandleType::Day).unwrap_or_default(),
+            selected_symbol,
+            selected_market: MarketType::Futures,
             selected_candle_type: CandleType::Day,
+            custom_timeframe: String::new(),
+            bar_kind: BarKind::Time,
+            bar_size: String::new(),
+            bar_builder: None,
+            chart_style: ChartStyle::Candle,
+            transformed_indicators: false,
+            transformed: TransformCache::default(),
             coin_list,
             auto_scroll: true,
             ws_sender: None,
-            show_ma5: false,
-            show_ma10: false,
-            show_ma20: false,
-            show_ma200: false,
+            indicators: load_indicators(&symbol, &CandleType::Day)
+                .unwrap_or_else(indicators::default_configs),
+            indicator_cache: IndicatorCache::default(),
+            anchor_pick: false,
+            footprints: FootprintStore::default(),
+            footprint_enabled: false,
+            param_edit: None,
             loading_more: false,
+            needs_backfill: false,
+            backfill_request: None,
+            backfill_id: 0,
+            backfilling: false,
+            coin_list_request: None,
+            loading_market: None,
+            last_tick_time: 0,
             oldest_date: None,
 
             account_info: None,
+            spot_account_info: None,
+            coin_m_account_info: None,
+            bybit_account_info: None,
+            okx_account_info: None,
             alerts: VecDeque::with_capacity(5),
             auto_trading_enabled: false,
             last_trade_time: None,
             alert_sender,
             average_prices: HashMap::new(),
+            export_from: String::new(),
+            export_to: String::new(),
+            import_path: String::new(),
+            replay_enabled: false,
+            replay_sender: None,
+            replay_speed: ReplaySpeed::default(),
+            replay_input: String::new(),
 
             scored_signals_enabled: true, // 기본으로 활성화
             buy_scored_signals: BTreeMap::new(),
@@ -236,9 +422,199 @@ impl Default for Futurx {
 }
 //Main 메서드
 impl Futurx {
-    //바이낸스 계정 구독
+    //바이낸스 계정 구독 (현물 계좌는 현물 마켓 선택 시에만, 리플레이 중에는 없음)
     fn binance_account_subscription(&self) -> Subscription<Message> {
-        Subscription::run(binance_account_connection)
+        if self.replay_enabled {
+            return Subscription::none();
+        }
+        match self.selected_market {
+            MarketType::Futures => Subscription::run(binance_account_connection),
+            MarketType::Spot => Subscription::batch([
+                Subscription::run(binance_account_connection),
+                Subscription::run(binance_spot_account_connection),
+            ]),
+            MarketType::CoinM => Subscription::run(binance_coin_m_account_connection),
+            MarketType::BybitLinear => Subscription::run(bybit_account_connection),
+            MarketType::OkxSwap => Subscription::run(okx_account_connection),
+        }
+    }
+    //선택된 선물 마켓의 계좌 정보 (USDⓈ-M / COIN-M)
+    pub fn futures_account_info(&self) -> Option<&FuturesAccountInfo> {
+        match self.selected_market {
+            MarketType::CoinM => self.coin_m_account_info.as_ref(),
+            MarketType::BybitLinear => self.bybit_account_info.as_ref(),
+            MarketType::OkxSwap => self.okx_account_info.as_ref(),
+            _ => self.account_info.as_ref(),
+        }
+    }
+    //선택된 심볼 정보
+    pub fn current_symbol(&self) -> Option<&Symbol> {
+        self.coin_list
+            .get(&self.selected_symbol)
+            .map(|info| &info.symbol)
+    }
+    //가격 표시 자릿수 (선택된 심볼 tick size)
+    pub fn price_precision(&self) -> u32 {
+        self.current_symbol()
+            .map(|symbol| symbol.price_precision)
+            .unwrap_or(2)
+    }
+    //선택된 심볼 (목록에 없으면 마켓 기본 심볼)
+    fn symbol(&self) -> Symbol {
+        self.current_symbol()
+            .cloned()
+            .unwrap_or_else(|| Symbol::fallback(self.selected_market, "BTC"))
+    }
+    //호가 자산 (USDT, USDC, USD)
+    pub fn quote_asset(&self) -> String {
+        self.current_symbol()
+            .map(|symbol| symbol.quote.clone())
+            .unwrap_or_else(|| "USDT".to_string())
+    }
+    //다시 채울 구간의 시작 시각 (필요 없으면 None)
+    fn backfill_from(&self, timestamp: u64) -> Option<u64> {
+        if self.replay_enabled || self.bar_builder.is_some() {
+            return None;
+        }
+        let &last = self.candlesticks.keys().last()?;
+        let candle_type = &self.selected_candle_type;
+        let current = candle_type.open_time(timestamp);
+
+        // 재연결 / 절전 : 마지막 캔들이 한 캔들 이상 지났을 때만 (마감 전에 끊겼으므로 거기서부터)
+        if self.needs_backfill {
+            return (current > last).then_some(last);
+        }
+        // 체결 사이에 캔들 하나 이상이 빠짐
+        (current > candle_type.next_open_time(last)).then_some(last)
+    }
+    //캔들 동기화 / 다시 채우기 요청 (리플레이, 비시간 캔들은 REST 캔들을 쓰지 않음)
+    fn request_backfill(&mut self, request: BackfillRequest) {
+        if self.replay_enabled || self.bar_builder.is_some() {
+            return;
+        }
+        self.backfill_id += 1;
+        self.backfilling = true;
+        self.backfill_request = Some(request);
+    }
+    //캔들 동기화 / 다시 채우기 (REST 조회는 UI 밖에서)
+    fn backfill_task(&self, request: BackfillRequest) -> Task<Message> {
+        let id = self.backfill_id;
+        let symbol = self.symbol();
+        let candle_type = self.selected_candle_type.clone();
+        let selected_symbol = self.selected_symbol.clone();
+        Task::perform(
+            async move {
+                let result = match request {
+                    BackfillRequest::Sync => sync_candles_async(&symbol, &candle_type).await,
+                    BackfillRequest::From(from) => {
+                        backfill_candles_async(&symbol, &candle_type, from).await
+                    }
+                }
+                .map_err(|e| e.to_string());
+                (candle_type, result)
+            },
+            move |(candle_type, result)| {
+                Message::CandlesBackfilled(id, selected_symbol.clone(), candle_type, result)
+            },
+        )
+    }
+    //가장 오래된 캔들의 날짜 저장
+    fn update_oldest_date(&mut self) {
+        self.oldest_date = self.candlesticks.keys().next().map(|&timestamp| {
+            chrono::NaiveDateTime::from_timestamp_opt((timestamp / 1000) as i64, 0)
+                .unwrap()
+                .format("%Y-%m-%dT%H:%M:%S")
+                .to_string()
+        });
+    }
+    //신호 / 패턴 계산에 쓸 캔들 (원본 또는 변환)
+    //(변환 캔들은 sync_signal_candles 이후 값)
+    fn signal_candles(&self) -> &BTreeMap<u64, Candlestick> {
+        if self.transformed_indicators {
+            &self.transformed.candles
+        } else {
+            &self.candlesticks
+        }
+    }
+    //캔들 길이 (ms, 비시간 캔들은 None)
+    fn bar_duration(&self) -> Option<u64> {
+        self.bar_builder
+            .is_none()
+            .then(|| self.selected_candle_type.duration_ms())
+    }
+    //차트에 그릴 캔들 (Heikin-Ashi / 선은 변환 캔들)
+    fn display_candles(&self) -> &BTreeMap<u64, Candlestick> {
+        if self.chart_style.transforms() {
+            &self.transformed.candles
+        } else {
+            &self.candlesticks
+        }
+    }
+    //변환 캔들 캐시를 현재 캔들에 맞춤 (새로 마감된 캔들과 진행 중 캔들만 변환)
+    fn sync_signal_candles(&mut self) {
+        if self.transformed_indicators || self.chart_style.transforms() {
+            self.transformed.sync(&self.candlesticks, self.chart_style);
+        }
+    }
+    //비시간 캔들은 실시간 체결로만 만들어지므로 처음부터 다시
+    fn reset_bars(&mut self) {
+        if let Some(builder) = self.bar_builder.as_mut() {
+            builder.reset();
+            self.candlesticks.clear();
+            self.buy_scored_signals.clear();
+            self.sell_scored_signals.clear();
+            self.footprints.clear();
+        }
+    }
+    fn add_footprint(&mut self, timestamp: u64, price: f64, quantity: f64, taker_buy: bool) {
+        let chart = self.chart_id();
+        let precision = self.price_precision();
+        self.footprints
+            .add(&chart, timestamp, price, quantity, taker_buy, precision);
+    }
+    //풋프린트 구분용 차트 키 (거래소 / 심볼 / 캔들 타입)
+    fn chart_id(&self) -> String {
+        let symbol = self.symbol();
+        format!(
+            "{}:{}:{}",
+            symbol.market.venue_key(),
+            symbol.exchange_symbol,
+            self.selected_candle_type.interval()
+        )
+    }
+    //현재 차트(심볼 / 타임프레임) 지표 구성 불러오기 (없으면 기본 구성)
+    fn load_chart_indicators(&mut self) {
+        self.indicators = load_indicators(&self.symbol(), &self.selected_candle_type)
+            .unwrap_or_else(indicators::default_configs);
+        self.param_edit = None;
+    }
+    //현재 차트 지표 구성 저장
+    fn save_chart_indicators(&self) {
+        if let Err(e) =
+            save_indicators(&self.symbol(), &self.selected_candle_type, &self.indicators)
+        {
+            println!("Indicator config save error: {}", e);
+        }
+    }
+    //아직 쓰지 않은 지표 색 (다 쓰면 첫 번째)
+    fn unused_color(&self) -> iced::Color {
+        PALETTE
+            .iter()
+            .find(|palette| {
+                !self
+                    .indicators
+                    .iter()
+                    .any(|config| config.color == Some(palette.color))
+            })
+            .unwrap_or(&PALETTE[0])
+            .color
+    }
+    //자동매매 주문 수량 (COIN-M 은 1계약)
+    fn auto_trade_amount(&self) -> f64 {
+        match self.selected_market {
+            MarketType::CoinM => 1.0,
+            _ => 0.001,
+        }
     }
     //전체 구독 설정
     pub fn subscription(&self) -> Subscription<Message> {
@@ -249,9 +625,16 @@ impl Futurx {
             iced::time::every(std::time::Duration::from_millis(100)).map(|_| Message::Tick),
         ])
     }
-    //Websocket 구독 설정
+    //Websocket 구독 설정 (거래소별 체결 스트림, 리플레이 중에는 기록된 데이터)
     fn websocket_subscription(&self) -> Subscription<Message> {
-        Subscription::run(binance_connection)
+        if self.replay_enabled {
+            return Subscription::run(replay_connection);
+        }
+        match self.selected_market {
+            MarketType::BybitLinear => Subscription::run(bybit_connection),
+            MarketType::OkxSwap => Subscription::run(okx_connection),
+            _ => Subscription::run(binance_connection),
+        }
     }
     //UI
     pub fn view(&self) -> Element<Message> {
@@ -274,50 +657,77 @@ impl Futurx {
             .width(Length::Shrink)
             .height(Length::Shrink);
 
-            let coins: Vec<String> = self.coin_list.keys().cloned().collect();
-            let coin_picker =
-                pick_list(coins, Some(self.selected_coin.clone()), Message::SelectCoin)
-                    .width(Length::Fixed(150.0));
+            let market_picker = pick_list(
+                MarketType::ALL.to_vec(),
+                Some(self.selected_market),
+                Message::SelectMarket,
+            )
+            .width(Length::Fixed(100.0));
+
+            let mut coins: Vec<Symbol> = self
+                .coin_list
+                .values()
+                .map(|info| info.symbol.clone())
+                .collect();
+            coins.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+            let coin_picker = pick_list(coins, self.current_symbol().cloned(), |symbol| {
+                Message::SelectCoin(symbol.exchange_symbol)
+            })
+            .width(Length::Fixed(150.0));
 
-            let candle_types = vec![CandleType::Minute1, CandleType::Minute3, CandleType::Day];
-            let candle_type_strings: Vec<String> =
-                candle_types.iter().map(|ct| ct.to_string()).collect();
             let candle_type_picker = pick_list(
-                candle_type_strings,
-                Some(self.selected_candle_type.to_string()),
-                |s| {
-                    let candle_type = match s.as_str() {
-                        "1Minute" => CandleType::Minute1,
-                        "3Minute" => CandleType::Minute3,
-                        "Day" => CandleType::Day,
-                        _ => CandleType::Day,
-                    };
-                    Message::SelectCandleType(candle_type)
-                },
+                CandleType::ALL.to_vec(),
+                Some(self.selected_candle_type.clone()),
+                Message::SelectCandleType,
+            )
+            .width(Length::Fixed(100.0));
+            let custom_timeframe_input = text_input("7m, 45m, 2d", &self.custom_timeframe)
+                .on_input(Message::CustomTimeframeChanged)
+                .on_submit(Message::ApplyCustomTimeframe)
+                .width(Length::Fixed(100.0));
+            let bar_kind_picker = pick_list(
+                BarKind::ALL.to_vec(),
+                Some(self.bar_kind),
+                Message::SelectBarKind,
             )
             .width(Length::Fixed(100.0));
+            let bar_size_input = text_input("Bar size", &self.bar_size)
+                .on_input_maybe(
+                    self.bar_builder
+                        .is_some()
+                        .then_some(Message::BarSizeChanged),
+                )
+                .on_submit(Message::ApplyBarSize)
+                .width(Length::Fixed(100.0));
 
             match content_type {
                 // 차트 패널
                 Pane::Chart => {
-                    let canvas = Canvas::new(Chart::new(
-                        self.candlesticks.clone(),
-                        self.selected_candle_type.clone(),
-                        self.show_ma5,
-                        self.show_ma10,
-                        self.show_ma20,
-                        self.show_ma200,
-                        self.scored_signals_enabled,
-                        self.buy_scored_signals.clone(),
-                        self.sell_scored_signals.clone(),
-                    ))
-                    .width(iced::Fill)
-                    .height(iced::Fill);
+                    let mut chart = Chart::new(
+                        self.display_candles(),
+                        &self.indicator_cache,
+                        &self.buy_scored_signals,
+                        &self.sell_scored_signals,
+                        ChartOptions {
+                            candle_type: self.selected_candle_type.clone(),
+                            style: self.chart_style,
+                            price_precision: self.symbol().price_precision,
+                            scored_signals_enabled: self.scored_signals_enabled,
+                        },
+                    );
+                    if self.footprint_enabled {
+                        chart.footprints = self.footprints.get(&self.chart_id());
+                    }
+                    let canvas = Canvas::new(chart).width(iced::Fill).height(iced::Fill);
 
                     // 상단 컨트롤 영역
                     let top_controls = Row::new()
+                        .push(market_picker.width(FillPortion(1)))
                         .push(coin_picker.width(FillPortion(1)))
                         .push(candle_type_picker.width(FillPortion(1)))
+                        .push(custom_timeframe_input.width(FillPortion(1)))
+                        .push(bar_kind_picker.width(FillPortion(1)))
+                        .push(bar_size_input.width(FillPortion(1)))
                         .push(ma_controls.width(FillPortion(8)))
                         .push(prediction_display.width(FillPortion(2)));
                     let chart_body = Column::new()
@@ -331,7 +741,14 @@ impl Futurx {
                 // 좌측 사이드바 패널 (코인 정보)
                 Pane::LeftSidebar => {
                     let coin_info = coin_info(&self);
-                    let left_side_bar = Column::new().spacing(20).padding(20).push(coin_info);
+                    let data_controls = data_controls(self);
+                    let replay_controls = replay_controls(self);
+                    let left_side_bar = Column::new()
+                        .spacing(20)
+                        .padding(20)
+                        .push(coin_info)
+                        .push(data_controls)
+                        .push(replay_controls);
 
                     let title_bar =
                         pane_grid::TitleBar::new(Text::new("코인 정보").size(16)).padding(10);
@@ -372,7 +789,133 @@ impl Futurx {
         .on_resize(10, Message::PaneResized)
         .into()
     }
-    pub fn update(&mut self, message: Message) {
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        let sync = !message.keeps_candles();
+        let backfilled = matches!(message, Message::CandlesBackfilled(..));
+        self.handle_message(message);
+
+        // 지표는 캔들 / 지표 구성이 바뀔 수 있는 메세지 처리 후 바뀐 캔들만 반영
+        // (다시 채우는 중에는 빈 구간이 있으므로 채운 뒤 한 번에 계산)
+        if sync {
+            self.sync_signal_candles();
+        }
+        if sync && !self.backfilling {
+            let mut cache = std::mem::take(&mut self.indicator_cache);
+            cache.update(&self.indicators, self.signal_candles(), self.bar_duration());
+            let events = cache.take_new_events();
+            self.indicator_cache = cache;
+
+            // 새로 마감된 캔들의 교차는 매수 / 매도 시도로 (자동 매매 조건은 TryBuy / TrySell 에서 확인)
+            for event in events {
+                self.handle_indicator_event(event);
+            }
+            if backfilled && self.scored_signals_enabled {
+                self.update_scored_signals();
+            }
+        }
+
+        let mut tasks = Vec::new();
+        if let Some(request) = self.backfill_request.take() {
+            tasks.push(self.backfill_task(request));
+        }
+        if let Some(market_type) = self.coin_list_request.take() {
+            tasks.push(Task::perform(
+                fetch_coin_list_async(market_type),
+                move |coin_list| Message::CoinListLoaded(market_type, coin_list),
+            ));
+        }
+        Task::batch(tasks)
+    }
+
+    //매수 / 매도 신호 점수 다시 계산
+    fn update_scored_signals(&mut self) {
+        self.sync_signal_candles();
+        let (buy_scores, sell_scores) =
+            calculate_scored_signals(self.signal_candles(), true, self.indicator_cache.events());
+        self.buy_scored_signals = buy_scores;
+        self.sell_scored_signals = sell_scores;
+    }
+
+    fn handle_indicator_event(&mut self, event: IndicatorEvent) {
+        let Some(price) = self.candlesticks.values().next_back().map(|c| c.close) else {
+            return;
+        };
+        let indicators = self.trade_indicators();
+        let message = match event.kind {
+            EventKind::Cross(Cross::Up) => Message::TryBuy {
+                price,
+                strength: uc::INDICATOR_CROSS_SCORE,
+                timestamp: event.timestamp,
+                indicators,
+            },
+            EventKind::Cross(Cross::Down) => Message::TrySell {
+                price,
+                strength: uc::INDICATOR_CROSS_SCORE,
+                timestamp: event.timestamp,
+                indicators,
+            },
+            // 스퀴즈는 방향이 없으므로 알림만
+            EventKind::SqueezeOn => {
+                self.add_alert(format!("{} 스퀴즈 시작", event.name), AlertType::Info);
+                return;
+            }
+            EventKind::SqueezeOff => {
+                self.add_alert(format!("{} 스퀴즈 해제", event.name), AlertType::Info);
+                return;
+            }
+        };
+        self.handle_message(message);
+    }
+
+    //ATR 기반 손절 거리 (가격 단위)
+    pub fn stop_distance(&self) -> Option<f64> {
+        atr_stop_distance(
+            &self.candlesticks,
+            uc::STOP_ATR_PERIOD,
+            uc::STOP_ATR_MULTIPLIER,
+        )
+    }
+
+    //알림에 붙일 손절 가격 (ATR 을 계산할 캔들이 부족하면 빈 문자열)
+    fn stop_info(&self, price: f64, trade_type: TradeType) -> String {
+        match self.stop_distance() {
+            Some(distance) => format!(
+                "\n손절 (ATR x{}): {}",
+                uc::STOP_ATR_MULTIPLIER,
+                format_price(
+                    stop_price(price, trade_type, distance),
+                    self.price_precision()
+                )
+            ),
+            None => String::new(),
+        }
+    }
+
+    //주문 알림에 같이 보낼 지표 값 (RSI 는 차트에 올린 RSI 기준)
+    fn trade_indicators(&self) -> TradeIndicators {
+        let closes: Vec<f64> = self.candlesticks.values().rev().map(|c| c.close).collect();
+        let average = |values: &[f64]| values.iter().sum::<f64>() / values.len().max(1) as f64;
+        let volumes: Vec<f64> = self
+            .candlesticks
+            .values()
+            .rev()
+            .take(20)
+            .map(|c| c.volume)
+            .collect();
+        let average_volume = average(&volumes);
+
+        TradeIndicators {
+            rsi: self.indicator_cache.latest("RSI").unwrap_or_default(),
+            ma5: average(&closes[..closes.len().min(5)]),
+            ma20: average(&closes[..closes.len().min(20)]),
+            volume_ratio: match volumes.first() {
+                Some(volume) if average_volume > 0.0 => volume / average_volume,
+                _ => 0.0,
+            },
+        }
+    }
+
+    fn handle_message(&mut self, message: Message) {
         match message {
             Message::PaneDragged(drag_event) => match drag_event {
                 pane_grid::DragEvent::Dropped { pane, target } => {
@@ -397,6 +940,12 @@ impl Futurx {
             Message::UpdateAveragePrice(symbol, price) => {
                 self.average_prices.insert(symbol, price);
             }
+            Message::MarketBuy | Message::MarketSell if self.replay_enabled => {
+                self.add_alert(
+                    "Orders are disabled in replay mode".to_string(),
+                    AlertType::Error,
+                );
+            }
             Message::MarketBuy => market_buy(self),
             Message::MarketSell => market_sell(self),
             Message::ToggleAutoTrading => {
@@ -417,33 +966,33 @@ impl Futurx {
             } => {
                 self.add_alert(
                     format!(
-                        "매수 신호 감지!\n가격: {:.2} USDT\n강도: {:.2}\nRSI: {:.2}",
-                        price, strength, indicators.rsi
+                        "매수 신호 감지!\n가격: {} {}\n강도: {:.2}\nRSI: {:.2}{}",
+                        format_price(price, self.price_precision()),
+                        self.quote_asset(),
+                        strength,
+                        indicators.rsi,
+                        self.stop_info(price, TradeType::Buy)
                     ),
                     AlertType::Buy,
                 );
 
-                if self.auto_trading_enabled {
+                // 리플레이 중에는 실제 주문을 내지 않음
+                if self.auto_trading_enabled && !self.replay_enabled {
                     let can_trade = self
                         .last_trade_time
                         .map(|time| time.elapsed() > Duration::from_secs(60))
                         .unwrap_or(true);
 
                     if can_trade {
-                        let amount = 0.001;
-                        let selected_coin = self.selected_coin.clone();
+                        let amount = self.auto_trade_amount();
+                        let symbol = self.symbol();
                         let alert_sender = self.alert_sender.clone();
 
                         let runtime = tokio::runtime::Handle::current();
                         runtime.spawn(async move {
-                            if let Err(e) = execute_trade(
-                                selected_coin,
-                                TradeType::Buy,
-                                price,
-                                amount,
-                                alert_sender,
-                            )
-                            .await
+                            if let Err(e) =
+                                execute_trade(symbol, TradeType::Buy, price, amount, alert_sender)
+                                    .await
                             {
                                 println!("{}", ul::ORDER_FAIL);
                                 println!("매수 실패: {:?}", e);
@@ -467,8 +1016,12 @@ impl Futurx {
 
                 println!("=== 강한 매도 신호 감지! ===");
                 println!("시간: {}", dt.format("%Y-%m-%d %H:%M:%S"));
-                println!("코인: {}", self.selected_coin);
-                println!("가격: {:.2} USDT", price);
+                println!("코인: {}", self.selected_symbol);
+                println!(
+                    "가격: {} {}",
+                    format_price(price, self.price_precision()),
+                    self.quote_asset()
+                );
                 println!("신호 강도: {:.2}", strength);
                 println!("RSI: {:.2}", indicators.rsi);
                 println!("MA5/MA20: {:.2}/{:.2}", indicators.ma5, indicators.ma20);
@@ -477,33 +1030,33 @@ impl Futurx {
 
                 self.add_alert(
                     format!(
-                        "매도 신호 감지!\n가격: {:.2} USDT\n강도: {:.2}\nRSI: {:.2}",
-                        price, strength, indicators.rsi
+                        "매도 신호 감지!\n가격: {} {}\n강도: {:.2}\nRSI: {:.2}{}",
+                        format_price(price, self.price_precision()),
+                        self.quote_asset(),
+                        strength,
+                        indicators.rsi,
+                        self.stop_info(price, TradeType::Sell)
                     ),
                     AlertType::Sell,
                 );
 
-                if self.auto_trading_enabled {
+                // 리플레이 중에는 실제 주문을 내지 않음
+                if self.auto_trading_enabled && !self.replay_enabled {
                     let can_trade = self
                         .last_trade_time
                         .map(|time| time.elapsed() > Duration::from_secs(60))
                         .unwrap_or(true);
 
                     if can_trade {
-                        let amount = 0.001;
-                        let selected_coin = self.selected_coin.clone();
+                        let amount = self.auto_trade_amount();
+                        let symbol = self.symbol();
                         let alert_sender = self.alert_sender.clone();
 
                         let runtime = tokio::runtime::Handle::current();
                         runtime.spawn(async move {
-                            if let Err(e) = execute_trade(
-                                selected_coin,
-                                TradeType::Sell,
-                                price,
-                                amount,
-                                alert_sender,
-                            )
-                            .await
+                            if let Err(e) =
+                                execute_trade(symbol, TradeType::Sell, price, amount, alert_sender)
+                                    .await
                             {
                                 println!("매도 실패: {:?}", e);
                             }
@@ -516,6 +1069,18 @@ impl Futurx {
             Message::UpdateAccountInfo(info) => {
                 self.account_info = Some(info);
             }
+            Message::UpdateSpotAccountInfo(info) => {
+                self.spot_account_info = Some(info);
+            }
+            Message::UpdateCoinMAccountInfo(info) => {
+                self.coin_m_account_info = Some(info);
+            }
+            Message::UpdateBybitAccountInfo(info) => {
+                self.bybit_account_info = Some(info);
+            }
+            Message::UpdateOkxAccountInfo(info) => {
+                self.okx_account_info = Some(info);
+            }
 
             Message::FetchError(error) => {
                 println!("API Error: {}", error);
@@ -535,6 +1100,16 @@ impl Futurx {
             }
 
             Message::Tick => {
+                // 틱 간격이 너무 길면 절전 / 멈춤 후 복귀로 보고 캔들 다시 채움
+                let now = chrono::Utc::now().timestamp_millis() as u64;
+                if self.last_tick_time != 0
+                    && now.saturating_sub(self.last_tick_time) > uc::SLEEP_DETECT_MS
+                {
+                    println!("Resumed after {} ms", now - self.last_tick_time);
+                    self.needs_backfill = true;
+                }
+                self.last_tick_time = now;
+
                 // 5초 이상 된 알림 제거
                 while let Some(alert) = self.alerts.front() {
                     if alert.timestamp.elapsed() > Duration::from_secs(5) {
@@ -546,24 +1121,19 @@ impl Futurx {
             }
 
             Message::LoadMoreCandles => {
-                if !self.loading_more {
-                    // 가장 오래된 캔들의 날짜를 찾아서 to 파라미터로 사용
+                if !self.loading_more && self.bar_builder.is_none() {
+                    // 가장 오래된 캔들 이전 시각을 end_time 으로 사용
                     if let Some((&oldest_timestamp, _)) = self.candlesticks.iter().next() {
                         self.loading_more = true;
-                        let datetime = chrono::NaiveDateTime::from_timestamp_opt(
-                            (oldest_timestamp / 1000) as i64,
-                            0,
-                        )
-                        .unwrap();
-                        let date_str = datetime.format("%Y-%m-%dT%H:%M:%S").to_string();
+                        let end_time = oldest_timestamp.saturating_sub(1);
 
                         // 클론해서 async 클로저에 전달
-                        let market = format!("USDT-{}", self.selected_coin);
+                        let symbol = self.symbol();
                         let candle_type = self.selected_candle_type.clone();
 
                         let runtime = tokio::runtime::Handle::current();
                         runtime.spawn(async move {
-                            match fetch_candles_async(&market, &candle_type, Some(date_str)).await {
+                            match fetch_candles_async(&symbol, &candle_type, Some(end_time)).await {
                                 Ok(new_candles) => Message::MoreCandlesLoaded(new_candles),
                                 Err(_) => Message::Error,
                             }
@@ -576,69 +1146,283 @@ impl Futurx {
                     self.candlesticks.append(&mut new_candles);
                 }
             }
+            Message::CandlesBackfilled(id, symbol, candle_type, result) => {
+                // 이후 요청이 있으면 버림 (그 요청이 끝날 때까지 다시 채우는 중)
+                if id != self.backfill_id {
+                    return;
+                }
+                self.backfilling = false;
+                // 조회 중에 심볼 / 캔들 타입이 바뀌었거나 리플레이 / 비시간 캔들로 바뀌었으면 버림
+                if symbol != self.selected_symbol
+                    || candle_type != self.selected_candle_type
+                    || self.replay_enabled
+                    || self.bar_builder.is_some()
+                {
+                    return;
+                }
+                match result {
+                    Ok(candles) => {
+                        println!("Backfilled {} candles for {}", candles.len(), symbol);
+                        // 조회 후에 들어온 체결이 더 많으면 실시간 캔들 유지
+                        for (open_time, candle) in candles {
+                            match self.candlesticks.get(&open_time) {
+                                Some(live) if live.volume > candle.volume => {}
+                                _ => {
+                                    self.candlesticks.insert(open_time, candle);
+                                }
+                            }
+                        }
+                        self.update_oldest_date();
+                        self.auto_scroll = true;
+                    }
+                    Err(e) => {
+                        println!("Backfill error for {}: {}", symbol, e);
+                        self.add_alert(
+                            format!("Failed to load {} candles: {}", candle_type, e),
+                            AlertType::Error,
+                        );
+                    }
+                }
+            }
 
             //이동평슌선 5,10,20,200일선
-            Message::ToggleMA5 => self.show_ma5 = !self.show_ma5,
-            Message::ToggleMA10 => self.show_ma10 = !self.show_ma10,
-            Message::ToggleMA20 => self.show_ma20 = !self.show_ma20,
-            Message::ToggleMA200 => self.show_ma200 = !self.show_ma200,
+            Message::SelectChartStyle(style) => self.chart_style = style,
+            Message::ToggleTransformedIndicators => {
+                self.transformed_indicators = !self.transformed_indicators
+            }
+            Message::ToggleIndicator(index) => {
+                if let Some(config) = self.indicators.get_mut(index) {
+                    config.visible = !config.visible;
+                    self.save_chart_indicators();
+                }
+            }
+            Message::SelectIndicatorSource(index, source) => {
+                if let Some(config) = self.indicators.get_mut(index) {
+                    config.source = source;
+                    self.save_chart_indicators();
+                }
+            }
+            Message::SelectMaType(index, ma_type) => {
+                if let Some(config) = self.indicators.get_mut(index) {
+                    config.key = ma_type.key().to_string();
+                    self.save_chart_indicators();
+                }
+            }
+            Message::IndicatorParamChanged(index, param, value) => {
+                self.param_edit = Some((index, param, value));
+            }
+            Message::ApplyIndicatorParam => {
+                let Some((index, param, value)) = self.param_edit.take() else {
+                    return;
+                };
+                let Some(config) = self.indicators.get_mut(index) else {
+                    return;
+                };
+                let Some(defaults) = config.build().map(|indicator| indicator.params()) else {
+                    return;
+                };
+                let Some(kind) = defaults.get(param).map(|p| p.kind) else {
+                    return;
+                };
+                // 범위를 벗어난 값은 저장 전에 거름 (큰 기간은 버퍼 할당에서 멈춤)
+                match value
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(|n| kind.validate(n))
+                {
+                    Some(number) => {
+                        // 비어 있는 앞쪽 파라미터는 지표 기본값으로 채움
+                        config
+                            .params
+                            .extend(defaults.iter().skip(config.params.len()).map(|p| p.value));
+                        config.params[param] = number;
+                        self.save_chart_indicators();
+                    }
+                    None => self.add_alert(
+                        format!("Invalid value: {} ({})", value, kind.describe()),
+                        AlertType::Error,
+                    ),
+                }
+            }
+            Message::SelectIndicatorColor(index, palette) => {
+                if let Some(config) = self.indicators.get_mut(index) {
+                    config.color = Some(palette.color);
+                    self.save_chart_indicators();
+                }
+            }
+            Message::AddIndicator(key) => {
+                let color = self.unused_color();
+                self.indicators
+                    .push(IndicatorConfig::new(key, &[]).with_color(color));
+                self.save_chart_indicators();
+            }
+            Message::ToggleFootprint => {
+                self.footprint_enabled = !self.footprint_enabled;
+            }
+            Message::ToggleAnchorPick => {
+                self.anchor_pick = !self.anchor_pick;
+            }
+            Message::ChartClicked(timestamp) => {
+                if self.anchor_pick {
+                    self.anchor_pick = false;
+
+                    // 앵커 VWAP 이 있으면 시작 캔들만 바꾸고, 없으면 추가
+                    let anchor = timestamp as f64;
+                    match self.indicators.iter_mut().find(|c| c.key == "AVWAP") {
+                        Some(config) => {
+                            match config.params.first_mut() {
+                                Some(param) => *param = anchor,
+                                None => config.params.push(anchor),
+                            }
+                            config.visible = true;
+                        }
+                        None => {
+                            let color = self.unused_color();
+                            self.indicators
+                                .push(IndicatorConfig::new("AVWAP", &[anchor]).with_color(color));
+                        }
+                    }
+                    self.param_edit = None;
+                    self.save_chart_indicators();
+                }
+            }
+            Message::RemoveIndicator(index) => {
+                if index < self.indicators.len() {
+                    self.indicators.remove(index);
+                    self.param_edit = None;
+                    self.save_chart_indicators();
+                }
+            }
             Message::SelectCandleType(candle_type) => {
                 println!("Changing candle type to: {}", candle_type);
                 self.selected_candle_type = candle_type.clone();
+                self.load_chart_indicators();
 
                 // 캔들스틱 데이터 새로 불러오기
-                let market = format!("USDT-{}", self.selected_coin);
+                let symbol = self.symbol();
                 println!(
                     "Fetching new candles for market {} with type {}",
-                    market, candle_type
+                    symbol.exchange_symbol, candle_type
                 );
 
-                match fetch_candles(&market, &candle_type, None) {
-                    // None을 추가하여 최신 데이터부터 가져오기
+                // 저장된 캔들을 먼저 표시하고 거래소 동기화는 UI 밖에서
+                match load_candles(&symbol, &candle_type) {
                     Ok(candles) => {
                         println!(
-                            "Successfully fetched {} candles for {}",
+                            "Loaded {} stored candles for {}",
                             candles.len(),
                             candle_type
                         );
                         self.candlesticks = candles;
-
-                        // 가장 오래된 캔들의 날짜 저장
-                        if let Some((&timestamp, _)) = self.candlesticks.iter().next() {
-                            let datetime = chrono::NaiveDateTime::from_timestamp_opt(
-                                (timestamp / 1000) as i64,
-                                0,
-                            )
-                            .unwrap();
-                            self.oldest_date =
-                                Some(datetime.format("%Y-%m-%dT%H:%M:%S").to_string());
-                        } else {
-                            self.oldest_date = None;
-                        }
-
-                        self.auto_scroll = true;
                     }
                     Err(e) => {
-                        println!("Error fetching {} candles: {:?}", candle_type, e);
+                        println!("Error loading {} candles: {:?}", candle_type, e);
+                        self.candlesticks.clear();
                     }
                 }
+                self.update_oldest_date();
+                self.auto_scroll = true;
+                self.request_backfill(BackfillRequest::Sync);
+                self.reset_bars();
             }
+            Message::SelectBarKind(kind) => {
+                self.bar_kind = kind;
+                if kind == BarKind::Time {
+                    // 시간 캔들로 복귀
+                    self.bar_builder = None;
+                    self.bar_size.clear();
+                    self.handle_message(Message::SelectCandleType(
+                        self.selected_candle_type.clone(),
+                    ));
+                } else {
+                    self.bar_size = kind.default_size().to_string();
+                    self.bar_builder = Some(BarBuilder::new(kind, kind.default_size()));
+                    self.reset_bars();
+                }
+            }
+            Message::BarSizeChanged(value) => self.bar_size = value,
+            Message::ApplyBarSize => match self.bar_size.trim().parse::<f64>() {
+                Ok(size) if size > 0.0 => {
+                    if let Some(builder) = self.bar_builder.as_mut() {
+                        builder.size = size;
+                    }
+                    self.reset_bars();
+                }
+                _ => self.add_alert(
+                    format!("Invalid bar size: {}", self.bar_size),
+                    AlertType::Error,
+                ),
+            },
+            Message::CustomTimeframeChanged(value) => self.custom_timeframe = value,
+            Message::ApplyCustomTimeframe => match CandleType::parse(&self.custom_timeframe) {
+                Some(candle_type) => self.handle_message(Message::SelectCandleType(candle_type)),
+                None => self.add_alert(
+                    format!("Invalid timeframe: {}", self.custom_timeframe),
+                    AlertType::Error,
+                ),
+            },
             Message::UpdatePrice(symbol, price, change_rate) => {
                 if let Some(info) = self.coin_list.get_mut(&symbol) {
                     info.price = price;
                 }
             }
+            Message::StreamConnected => {
+                // 끊긴 동안의 체결은 다음 체결 수신 시 (캔들이 바뀌었으면) REST 로 채움
+                self.needs_backfill = true;
+            }
             Message::WebSocketInit(sender) => {
                 println!("WebSocket sender initialized!");
+                // 거래소 변경으로 새 스트림이 시작되면 현재 심볼로 구독
+                if let Err(e) = sender.clone().try_send(self.symbol()) {
+                    println!("Error sending WebSocket subscription: {:?}", e);
+                }
                 self.ws_sender = Some(sender);
             }
+            Message::SelectMarket(market_type) => {
+                if market_type == self.selected_market {
+                    // 불러오던 다른 마켓 목록은 버림
+                    self.loading_market = None;
+                    return;
+                }
+                if self.loading_market == Some(market_type) {
+                    return;
+                }
+                // 목록은 UI 밖에서 조회하고 CoinListLoaded 에서 마켓 변경
+                println!("Loading coin list for: {}", market_type);
+                self.loading_market = Some(market_type);
+                self.coin_list_request = Some(market_type);
+            }
+            Message::CoinListLoaded(market_type, coin_list) => {
+                // 조회 중에 다른 마켓을 골랐으면 버림
+                if self.loading_market != Some(market_type) {
+                    return;
+                }
+                self.loading_market = None;
+                println!("Switching to market: {}", market_type);
+                self.selected_market = market_type;
+                self.coin_list = coin_list;
+
+                // 새 마켓에 같은 코인이 없으면 기본 코인으로 변경
+                // (USDⓈ-M BTCUSDT -> 현물 BTCUSDT 처럼 같은 심볼이 있으면 유지)
+                let symbol = if self.coin_list.contains_key(&self.selected_symbol) {
+                    self.selected_symbol.clone()
+                } else {
+                    market_type.default_symbol().to_string()
+                };
+                self.buy_scored_signals.clear();
+                self.sell_scored_signals.clear();
+                self.handle_message(Message::SelectCoin(symbol));
+            }
             Message::SelectCoin(symbol) => {
                 println!("Switching to coin: {}", symbol);
-                self.selected_coin = symbol.clone();
+                self.selected_symbol = symbol.clone();
+                self.load_chart_indicators();
+                let exchange_symbol = self.symbol();
 
                 if let Some(sender) = &self.ws_sender {
                     println!("Sending WebSocket subscription for: {}", symbol);
-                    if let Err(e) = sender.clone().try_send(symbol.clone()) {
+                    if let Err(e) = sender.clone().try_send(exchange_symbol.clone()) {
                         println!("ERROR sending WebSocket subscription: {:?}", e);
                     } else {
                         println!("WebSocket subscription sent successfully");
@@ -648,44 +1432,25 @@ impl Futurx {
                 }
                 self.candlesticks.clear();
 
-                match fetch_candles(
-                    &format!("USDT-{}", symbol),
-                    &self.selected_candle_type,
-                    None,
-                ) {
+                // 저장된 캔들을 먼저 표시하고 거래소 동기화는 UI 밖에서
+                match load_candles(&exchange_symbol, &self.selected_candle_type) {
                     Ok(candles) => {
-                        if candles.is_empty() {
-                            println!("Warning: No candles received for {}", symbol);
-                        } else {
-                            println!(
-                                "Successfully loaded {} candles for {}",
-                                candles.len(),
-                                symbol
-                            );
-                            self.candlesticks = candles;
-
-                            // 가장 오래된 캔들의 날짜 저장
-                            if let Some((&timestamp, _)) = self.candlesticks.iter().next() {
-                                let datetime = chrono::NaiveDateTime::from_timestamp_opt(
-                                    (timestamp / 1000) as i64,
-                                    0,
-                                )
-                                .unwrap();
-                                self.oldest_date =
-                                    Some(datetime.format("%Y-%m-%dT%H:%M:%S").to_string());
-                            }
-                        }
+                        println!("Loaded {} stored candles for {}", candles.len(), symbol);
+                        self.candlesticks = candles;
                     }
                     Err(e) => {
-                        println!("Error fetching candles for {}: {:?}", symbol, e);
+                        println!("Error loading candles for {}: {:?}", symbol, e);
                     }
                 }
+                self.update_oldest_date();
+                self.request_backfill(BackfillRequest::Sync);
 
                 if let Some(sender) = &self.ws_sender {
-                    if let Err(e) = sender.clone().try_send(symbol.clone()) {
+                    if let Err(e) = sender.clone().try_send(exchange_symbol.clone()) {
                         println!("Error sending WebSocket subscription: {:?}", e);
                     }
                 }
+                self.reset_bars();
                 self.auto_scroll = true;
             }
             Message::UpdateCoinPrice(symbol, price, change) => {
@@ -695,61 +1460,32 @@ impl Futurx {
             }
             Message::AddCandlestick(trade) => {
                 let (timestamp, trade_data) = trade;
-                let current_market = format!("{}USDT", self.selected_coin);
-
-                if trade_data.symbol != current_market {
+                if trade_data.symbol != self.selected_symbol {
                     return;
                 }
 
-                if self.scored_signals_enabled {
-                    println!("📊 Scored signals enabled! Calculating...");
-                    println!("📊 Candlesticks count: {}", self.candlesticks.len());
-
-                    let (buy_scores, sell_scores) = calculate_scored_signals(
-                        &self.candlesticks,
-                        true,
-                        &self.selected_candle_type,
-                    );
-
-                    println!(
-                        "📊 Calculated buy_scores: {}, sell_scores: {}",
-                        buy_scores.len(),
-                        sell_scores.len()
-                    );
-
-                    // 계산된 점수들 출력
-                    for (timestamp, score) in &buy_scores {
-                        println!("📊 Buy score at {}: {:.1}", timestamp, score.total_score);
-                    }
-                    for (timestamp, score) in &sell_scores {
-                        println!("📊 Sell score at {}: {:.1}", timestamp, score.total_score);
+                // 재연결 / 절전 후 또는 캔들을 건너뛰었으면 REST 로 채움 (update 에서 Task 로 실행)
+                if !self.backfilling {
+                    if let Some(from) = self.backfill_from(timestamp) {
+                        self.request_backfill(BackfillRequest::From(from));
                     }
-
-                    self.buy_scored_signals = buy_scores;
-                    self.sell_scored_signals = sell_scores;
-                } else {
-                    println!("📊 Scored signals DISABLED");
+                    self.needs_backfill = false;
                 }
 
-                if self.candlesticks.is_empty() {
-                    // 초기 데이터 로드
-                    if let Ok(candles) = fetch_candles(
-                        &format!("USDT-{}", self.selected_coin),
-                        &self.selected_candle_type,
-                        None,
-                    ) {
+                if self.candlesticks.is_empty()
+                    && !self.backfilling
+                    && !self.replay_enabled
+                    && self.bar_builder.is_none()
+                {
+                    // 초기 데이터 로드 (저장된 캔들, 동기화는 UI 밖에서)
+                    if let Ok(candles) = load_candles(&self.symbol(), &self.selected_candle_type) {
                         self.candlesticks = candles;
                     }
+                    self.request_backfill(BackfillRequest::Sync);
                 }
-                if self.scored_signals_enabled {
-                    let (buy_scores, sell_scores) = calculate_scored_signals(
-                        &self.candlesticks,
-                        true,
-                        &self.selected_candle_type,
-                    );
-
-                    self.buy_scored_signals = buy_scores;
-                    self.sell_scored_signals = sell_scores;
+                // 다시 채우는 중에는 빈 구간이 있으므로 채운 뒤 계산 (update)
+                if self.scored_signals_enabled && !self.backfilling {
+                    self.update_scored_signals();
 
                     // 최신 신호 확인
                     if let Some(&last_timestamp) = self.candlesticks.keys().last() {
@@ -778,31 +1514,42 @@ impl Futurx {
                         }
                     }
                 }
-                let current_timestamp = timestamp;
-                let candle_timestamp = match self.selected_candle_type {
-                    CandleType::Minute1 => current_timestamp - (current_timestamp % 60000),
-                    CandleType::Minute3 => current_timestamp - (current_timestamp % 180000),
-                    CandleType::Day => current_timestamp - (current_timestamp % 86400000),
-                };
-
-                let trade_price = trade_data.price.parse::<f32>().unwrap_or_default();
-                let trade_volume = trade_data.quantity.parse::<f32>().unwrap_or_default();
+                let candle_timestamp = self.selected_candle_type.open_time(timestamp);
+
+                let trade_price = trade_data.price.parse::<f64>().unwrap_or_default();
+                let trade_volume = trade_data.quantity.parse::<f64>().unwrap_or_default();
+
+                let trade_count = trade_data.trade_count();
+                let taker_buy = !trade_data.is_buyer_maker;
+
+                if let Some(builder) = self.bar_builder.as_mut() {
+                    builder.push(
+                        &mut self.candlesticks,
+                        timestamp,
+                        trade_price,
+                        trade_volume,
+                        trade_count,
+                        taker_buy,
+                    );
+                    // 체결이 들어간 캔들 (비시간 캔들은 마지막 캔들)
+                    if let Some(&bar_timestamp) = self.candlesticks.keys().next_back() {
+                        self.add_footprint(bar_timestamp, trade_price, trade_volume, taker_buy);
+                    }
+                    self.auto_scroll = true;
+                    return;
+                }
+                self.add_footprint(candle_timestamp, trade_price, trade_volume, taker_buy);
 
                 self.candlesticks
                     .entry(candle_timestamp)
-                    .and_modify(|candle| {
-                        candle.high = candle.high.max(trade_price);
-                        candle.low = candle.low.min(trade_price);
-                        candle.close = trade_price;
-                        candle.volume += trade_volume;
-                    })
                     .or_insert(Candlestick {
                         open: trade_price,
                         high: trade_price,
                         low: trade_price,
                         close: trade_price,
-                        volume: trade_volume,
-                    });
+                        ..Default::default()
+                    })
+                    .add_trade(trade_price, trade_volume, trade_count, taker_buy);
                 self.auto_scroll = true;
             }
             Message::RemoveCandlestick => {
@@ -822,6 +1569,138 @@ impl Futurx {
                     self.sell_scored_signals.clear();
                 }
             }
+            Message::ExportFromChanged(value) => self.export_from = value,
+            Message::ExportToChanged(value) => self.export_to = value,
+            Message::ImportPathChanged(value) => self.import_path = value,
+            Message::ExportCandles(format) => {
+                let symbol = self.symbol();
+                // 날짜 미입력 시 전체 기간, 종료일은 그날 마지막 시각까지 포함
+                let from = parse_time(&self.export_from).unwrap_or(0);
+                let to = match parse_time(&self.export_to) {
+                    Some(to) if self.export_to.trim().len() == 10 => to + 86_399_999,
+                    Some(to) => to,
+                    None => u64::MAX >> 1,
+                };
+
+                // 실시간으로 갱신된 캔들까지 저장 후 내보내기
+                // (비시간 캔들은 저장하지 않고 저장된 시간 캔들만 내보냄)
+                let result = CandleStore::open()
+                    .and_then(|mut store| match self.bar_builder {
+                        Some(_) => Ok(()),
+                        None => store.save(&symbol, &self.selected_candle_type, &self.candlesticks),
+                    })
+                    .map_err(|e| e.into())
+                    .and_then(|_| {
+                        export_candles(&symbol, &self.selected_candle_type, from, to, format)
+                    });
+                match result {
+                    Ok((path, count)) => self.add_alert(
+                        format!("Exported {} candles to {}", count, path.display()),
+                        AlertType::Info,
+                    ),
+                    Err(e) => {
+                        self.add_alert(format!("{} export failed: {}", format, e), AlertType::Error)
+                    }
+                }
+            }
+            Message::ImportCandles => {
+                let symbol = self.symbol();
+                let path = std::path::PathBuf::from(self.import_path.trim());
+
+                // 현재 심볼 / 캔들 타입으로 저장소에 저장 후 차트에 합침
+                let result = import_candles(&path).and_then(|candles| {
+                    CandleStore::open()?.save(&symbol, &self.selected_candle_type, &candles)?;
+                    Ok(candles)
+                });
+                match result {
+                    Ok(mut candles) => {
+                        let count = candles.len();
+                        if self.bar_builder.is_none() {
+                            self.candlesticks.append(&mut candles);
+                        }
+                        self.add_alert(
+                            format!("Imported {} candles into {}", count, symbol),
+                            AlertType::Info,
+                        );
+                    }
+                    Err(e) => self.add_alert(format!("Import failed: {}", e), AlertType::Error),
+                }
+            }
+            Message::ToggleReplay => {
+                self.replay_enabled = !self.replay_enabled;
+                self.replay_sender = None;
+                if !self.replay_enabled {
+                    // 실시간 데이터로 복귀 (웹소켓 재구독 / 캔들 재로드)
+                    self.ws_sender = None;
+                    self.handle_message(Message::SelectCoin(self.selected_symbol.clone()));
+                }
+            }
+            Message::ReplayInit(mut sender) => {
+                let _ = sender.try_send(ReplayCommand::Speed(self.replay_speed));
+                self.replay_sender = Some(sender);
+            }
+            Message::ReplayInputChanged(value) => self.replay_input = value,
+            Message::SelectReplaySpeed(speed) => {
+                self.replay_speed = speed;
+                if let Some(sender) = self.replay_sender.as_mut() {
+                    let _ = sender.try_send(ReplayCommand::Speed(speed));
+                }
+            }
+            Message::StartReplay => {
+                // 파일이 있으면 tape, 아니면 날짜로 보고 저장된 캔들 재생
+                let input = self.replay_input.trim();
+                let path = std::path::PathBuf::from(input);
+                let source = if path.is_file() {
+                    ReplaySource::Tape(path)
+                } else if let Some(from) = parse_time(input) {
+                    ReplaySource::Candles { from }
+                } else {
+                    self.add_alert(
+                        "Enter a tape file path or a start date".to_string(),
+                        AlertType::Error,
+                    );
+                    return;
+                };
+
+                let command =
+                    ReplayCommand::Start(source, self.symbol(), self.selected_candle_type.clone());
+                match self.replay_sender.as_mut() {
+                    Some(sender) => {
+                        if let Err(e) = sender.try_send(command) {
+                            println!("Error sending replay command: {:?}", e);
+                        }
+                    }
+                    None => self.add_alert("Replay is not ready yet".to_string(), AlertType::Error),
+                }
+            }
+            Message::StepReplay => {
+                if let Some(sender) = self.replay_sender.as_mut() {
+                    let _ = sender.try_send(ReplayCommand::Step);
+                }
+            }
+            Message::ReplayStarted(first_time) => {
+                // 리플레이 시작 시각 이전 캔들만 보여주고 이후는 재생으로 채움
+                let symbol = self.symbol();
+                let first_open = self.selected_candle_type.open_time(first_time);
+                self.candlesticks = CandleStore::open()
+                    .and_then(|store| {
+                        store.load_before(
+                            &symbol,
+                            &self.selected_candle_type,
+                            first_open,
+                            uc::CANDLE_VIEW_LIMIT,
+                        )
+                    })
+                    .unwrap_or_default();
+                self.buy_scored_signals.clear();
+                self.sell_scored_signals.clear();
+                self.reset_bars();
+                self.auto_scroll = true;
+                self.add_alert(format!("Replay started for {}", symbol), AlertType::Info);
+            }
+            Message::ReplayFinished => {
+                self.add_alert("Replay finished".to_string(), AlertType::Info);
+            }
         }
     }
 
@@ -844,7 +1723,20 @@ impl std::fmt::Display for CandleType {
         match self {
             CandleType::Minute1 => write!(f, "1Minute"),
             CandleType::Minute3 => write!(f, "3Minute"), // 표시 텍스트 변경
+            CandleType::Minute5 => write!(f, "5Minute"),
+            CandleType::Minute15 => write!(f, "15Minute"),
+            CandleType::Minute30 => write!(f, "30Minute"),
+            CandleType::Hour1 => write!(f, "1Hour"),
+            CandleType::Hour2 => write!(f, "2Hour"),
+            CandleType::Hour4 => write!(f, "4Hour"),
+            CandleType::Hour6 => write!(f, "6Hour"),
+            CandleType::Hour8 => write!(f, "8Hour"),
+            CandleType::Hour12 => write!(f, "12Hour"),
             CandleType::Day => write!(f, "Day"),
+            CandleType::Day3 => write!(f, "3Day"),
+            CandleType::Week => write!(f, "Week"),
+            CandleType::Month => write!(f, "Month"),
+            CandleType::Custom(_) => write!(f, "{}", self.interval()),
         }
     }
 }
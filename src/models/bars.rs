@@ -0,0 +1,329 @@
+use crate::Candlestick;
+use std::collections::BTreeMap;
+
+//캔들 생성 방식
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum BarKind {
+    #[default]
+    Time, // 시간 (CandleType)
+    Tick,   // 체결 N건
+    Volume, // 거래량 (기초 자산 수량)
+    Dollar, // 거래대금 (가격 * 수량)
+    Range,  // 고가 - 저가 폭 (가격 대비 %)
+    Renko,  // 벽돌 (가격 대비 %)
+}
+
+impl BarKind {
+    pub const ALL: [BarKind; 6] = [
+        BarKind::Time,
+        BarKind::Tick,
+        BarKind::Volume,
+        BarKind::Dollar,
+        BarKind::Range,
+        BarKind::Renko,
+    ];
+
+    //기본 크기
+    pub fn default_size(&self) -> f64 {
+        match self {
+            BarKind::Time => 0.0,
+            BarKind::Tick => 500.0,
+            BarKind::Volume => 100.0,
+            BarKind::Dollar => 1_000_000.0,
+            BarKind::Range => 0.2,
+            BarKind::Renko => 0.1,
+        }
+    }
+}
+
+impl std::fmt::Display for BarKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BarKind::Time => write!(f, "Time"),
+            BarKind::Tick => write!(f, "Tick"),
+            BarKind::Volume => write!(f, "Volume"),
+            BarKind::Dollar => write!(f, "Dollar"),
+            BarKind::Range => write!(f, "Range"),
+            BarKind::Renko => write!(f, "Renko"),
+        }
+    }
+}
+
+/*
+체결 -> 비시간 캔들
+- 캔들 키는 첫 체결 시각 (같은 ms 에 여러 캔들이면 1ms 씩 뒤로)
+- 체결 하나는 나누지 않음 (마지막 체결로 기준을 넘으면 그 캔들에서 마감)
+- Range / Renko 크기는 첫 체결 가격 대비 % 로 정해서 고정
+*/
+#[derive(Debug, Clone)]
+pub struct BarBuilder {
+    pub kind: BarKind,
+    pub size: f64,
+    closed: bool,               // 현재 캔들 마감 여부
+    count: usize,               // 현재 캔들 체결 수
+    accumulated: f64,           // 현재 캔들 거래량 / 거래대금
+    brick: f64,                 // Range / Renko 가격 폭
+    renko_close: f64,           // 마지막 벽돌 종가
+    renko_up: Option<bool>,     // 마지막 벽돌 방향 (첫 벽돌 전에는 None)
+    renko_pending: Candlestick, // 다음 벽돌에 들어갈 거래량 / 체결 수
+}
+
+impl BarBuilder {
+    pub fn new(kind: BarKind, size: f64) -> Self {
+        Self {
+            kind,
+            size,
+            closed: true,
+            count: 0,
+            accumulated: 0.0,
+            brick: 0.0,
+            renko_close: 0.0,
+            renko_up: None,
+            renko_pending: Candlestick::default(),
+        }
+    }
+
+    //처음부터 다시 (코인 / 크기 변경 시)
+    pub fn reset(&mut self) {
+        *self = Self::new(self.kind, self.size);
+    }
+
+    //체결 하나 반영
+    pub fn push(
+        &mut self,
+        bars: &mut BTreeMap<u64, Candlestick>,
+        timestamp: u64,
+        price: f64,
+        quantity: f64,
+        trades: u32,
+        taker_buy: bool,
+    ) {
+        if price <= 0.0 || self.size <= 0.0 {
+            return;
+        }
+        if self.brick == 0.0 {
+            self.brick = price * self.size / 100.0;
+            self.renko_close = price;
+        }
+
+        if self.kind == BarKind::Renko {
+            return self.push_renko(bars, timestamp, price, quantity, trades, taker_buy);
+        }
+
+        if self.closed {
+            let key = next_key(bars, timestamp);
+            bars.insert(
+                key,
+                Candlestick {
+                    open: price,
+                    high: price,
+                    low: price,
+                    close: price,
+                    ..Default::default()
+                },
+            );
+            self.closed = false;
+            self.count = 0;
+            self.accumulated = 0.0;
+        }
+        let Some((_, bar)) = bars.iter_mut().next_back() else {
+            return;
+        };
+        bar.add_trade(price, quantity, trades, taker_buy);
+
+        self.count += 1;
+        self.closed = match self.kind {
+            BarKind::Tick => self.count as f64 >= self.size,
+            BarKind::Volume => {
+                self.accumulated += quantity;
+                self.accumulated >= self.size
+            }
+            BarKind::Dollar => {
+                self.accumulated += price * quantity;
+                self.accumulated >= self.size
+            }
+            BarKind::Range => bar.high - bar.low >= self.brick,
+            BarKind::Time | BarKind::Renko => false,
+        };
+    }
+
+    //같은 방향은 한 칸, 반대 방향은 두 칸 움직여야 벽돌 생성
+    fn push_renko(
+        &mut self,
+        bars: &mut BTreeMap<u64, Candlestick>,
+        timestamp: u64,
+        price: f64,
+        quantity: f64,
+        trades: u32,
+        taker_buy: bool,
+    ) {
+        // 벽돌이 생길 때까지의 거래량 / 체결 수를 모아서 다음 벽돌에 넣음
+        self.renko_pending.volume += quantity;
+        self.renko_pending.quote_volume += price * quantity;
+        self.renko_pending.trades += trades;
+        if taker_buy {
+            self.renko_pending.taker_buy_volume += quantity;
+        }
+
+        loop {
+            let (up_bricks, down_bricks) = match self.renko_up {
+                Some(true) => (1.0, 2.0),
+                Some(false) => (2.0, 1.0),
+                None => (1.0, 1.0),
+            };
+            let up_target = self.renko_close + up_bricks * self.brick;
+            let down_target = self.renko_close - down_bricks * self.brick;
+            let up = if price >= up_target {
+                true
+            } else if price <= down_target {
+                false
+            } else {
+                break;
+            };
+
+            let close = if up { up_target } else { down_target };
+            let open = if up {
+                close - self.brick
+            } else {
+                close + self.brick
+            };
+            let key = next_key(bars, timestamp);
+            bars.insert(
+                key,
+                Candlestick {
+                    open,
+                    high: open.max(close),
+                    low: open.min(close),
+                    close,
+                    ..std::mem::take(&mut self.renko_pending)
+                },
+            );
+            self.renko_close = close;
+            self.renko_up = Some(up);
+        }
+    }
+}
+
+//기존 캔들과 겹치지 않는 키
+fn next_key(bars: &BTreeMap<u64, Candlestick>, timestamp: u64) -> u64 {
+    match bars.keys().next_back() {
+        Some(&last) if last >= timestamp => last + 1,
+        _ => timestamp,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //(시각, 가격, 수량) 체결 반영
+    fn build(kind: BarKind, size: f64, trades: &[(u64, f64, f64)]) -> BTreeMap<u64, Candlestick> {
+        let mut builder = BarBuilder::new(kind, size);
+        let mut bars = BTreeMap::new();
+        for &(timestamp, price, quantity) in trades {
+            builder.push(&mut bars, timestamp, price, quantity, 1, true);
+        }
+        bars
+    }
+
+    fn ohlc(bars: &BTreeMap<u64, Candlestick>) -> Vec<(u64, f64, f64, f64, f64)> {
+        bars.iter()
+            .map(|(&time, bar)| (time, bar.open, bar.high, bar.low, bar.close))
+            .collect()
+    }
+
+    #[test]
+    fn tick_bars() {
+        // 체결 2건마다 마감, 같은 시각에 시작하는 캔들은 1ms 뒤로
+        let bars = build(
+            BarKind::Tick,
+            2.0,
+            &[
+                (10, 100.0, 1.0),
+                (10, 101.0, 1.0),
+                (10, 99.0, 1.0),
+                (12, 98.0, 1.0),
+                (13, 97.0, 1.0),
+            ],
+        );
+        assert_eq!(
+            ohlc(&bars),
+            [
+                (10, 100.0, 101.0, 100.0, 101.0),
+                (11, 99.0, 99.0, 98.0, 98.0),
+                (13, 97.0, 97.0, 97.0, 97.0)
+            ]
+        );
+        assert_eq!(bars[&10].trades, 2);
+        assert_eq!(bars[&10].taker_buy_volume, 2.0);
+    }
+
+    #[test]
+    fn volume_and_dollar_bars() {
+        // 마지막 체결은 나누지 않고 그 캔들에 포함
+        let trades = [
+            (1, 10.0, 3.0),
+            (2, 10.0, 4.0),
+            (3, 10.0, 1.0),
+            (4, 10.0, 1.0),
+        ];
+        let bars = build(BarKind::Volume, 5.0, &trades);
+        let volumes: Vec<_> = bars.values().map(|bar| bar.volume).collect();
+        assert_eq!(volumes, [7.0, 2.0]);
+
+        let bars = build(BarKind::Dollar, 50.0, &trades);
+        let volumes: Vec<_> = bars.values().map(|bar| bar.quote_volume).collect();
+        assert_eq!(volumes, [70.0, 20.0]);
+    }
+
+    #[test]
+    fn range_bars() {
+        // 폭은 첫 가격의 1% (1.0) 로 고정
+        let bars = build(
+            BarKind::Range,
+            1.0,
+            &[
+                (1, 100.0, 1.0),
+                (2, 100.5, 1.0),
+                (3, 99.9, 1.0),
+                (4, 99.0, 1.0),
+                (5, 99.5, 1.0),
+            ],
+        );
+        assert_eq!(
+            ohlc(&bars),
+            [(1, 100.0, 100.5, 99.0, 99.0), (5, 99.5, 99.5, 99.5, 99.5)]
+        );
+    }
+
+    #[test]
+    fn renko_bricks() {
+        /*
+        벽돌 1.0 (100 의 1%)
+        - 102.5 : 상승 벽돌 2개 (101, 102)
+        - 101.2 : 반대 방향은 두 칸 (100 이하) 이 필요해서 벽돌 없음
+        - 99.8 : 하락 벽돌 (102 -> 101 이 아니라 101 -> 100)
+        */
+        let bars = build(
+            BarKind::Renko,
+            1.0,
+            &[
+                (1, 100.0, 1.0),
+                (2, 102.5, 2.0),
+                (3, 101.2, 1.0),
+                (4, 99.8, 3.0),
+            ],
+        );
+        assert_eq!(
+            ohlc(&bars),
+            [
+                (2, 100.0, 101.0, 100.0, 101.0),
+                (3, 101.0, 102.0, 101.0, 102.0),
+                (4, 101.0, 101.0, 100.0, 100.0)
+            ]
+        );
+        // 벽돌 사이 거래량은 다음 벽돌에 모음
+        let volumes: Vec<_> = bars.values().map(|bar| bar.volume).collect();
+        assert_eq!(volumes, [3.0, 0.0, 4.0]);
+    }
+}
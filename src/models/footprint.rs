@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+/*
+풋프린트 (캔들 안 가격대별 시장가 매도 x 시장가 매수 거래량)
+- 실시간 체결로만 만들어짐 (과거 캔들에는 체결 단위 정보가 없음)
+- 가격대 크기는 캔들 첫 체결 가격의 약 0.05% 를 10 의 거듭제곱으로 맞춤 (최소 tick)
+*/
+#[derive(Debug, Clone, Default)]
+pub struct Footprint {
+    pub row: f64,                          // 가격대 크기
+    pub levels: BTreeMap<i64, (f64, f64)>, // 가격대 번호 -> (매도, 매수)
+}
+
+impl Footprint {
+    pub fn new(price: f64, price_precision: u32) -> Self {
+        let tick = 10f64.powi(-(price_precision as i32));
+        let target = price * 0.0005;
+        let row = if target > tick {
+            10f64.powf(target.log10().floor()).max(tick)
+        } else {
+            tick
+        };
+        Self {
+            row,
+            levels: BTreeMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, price: f64, quantity: f64, taker_buy: bool) {
+        let level = (price / self.row).floor() as i64;
+        let (sell, buy) = self.levels.entry(level).or_default();
+        if taker_buy {
+            *buy += quantity;
+        } else {
+            *sell += quantity;
+        }
+    }
+
+    //가격대 하단 가격
+    pub fn price(&self, level: i64) -> f64 {
+        level as f64 * self.row
+    }
+
+    //가격대 중 가장 큰 거래량 (표시 농도 기준)
+    pub fn max_volume(&self) -> f64 {
+        self.levels
+            .values()
+            .map(|(sell, buy)| sell + buy)
+            .fold(0.0, f64::max)
+    }
+}
+
+//차트의 최근 캔들 풋프린트 (차트가 바뀌면 비움)
+#[derive(Debug, Default)]
+pub struct FootprintStore {
+    chart: String,
+    pub bars: BTreeMap<u64, Footprint>,
+}
+
+impl FootprintStore {
+    pub fn add(
+        &mut self,
+        chart: &str,
+        timestamp: u64,
+        price: f64,
+        quantity: f64,
+        taker_buy: bool,
+        price_precision: u32,
+    ) {
+        if self.chart != chart {
+            self.chart = chart.to_string();
+            self.bars.clear();
+        }
+        self.bars
+            .entry(timestamp)
+            .or_insert_with(|| Footprint::new(price, price_precision))
+            .add(price, quantity, taker_buy);
+        while self.bars.len() > crate::uc::FOOTPRINT_MAX_BARS {
+            self.bars.pop_first();
+        }
+    }
+
+    //현재 차트의 풋프린트
+    pub fn get(&self, chart: &str) -> Option<&BTreeMap<u64, Footprint>> {
+        (self.chart == chart).then_some(&self.bars)
+    }
+
+    pub fn clear(&mut self) {
+        self.bars.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn footprint_levels() {
+        // 60000 * 0.05% = 30 -> 10 단위 가격대
+        let mut footprint = Footprint::new(60_000.0, 1);
+        assert_eq!(footprint.row, 10.0);
+        footprint.add(60_001.0, 1.0, true);
+        footprint.add(60_009.9, 2.0, false);
+        footprint.add(60_010.0, 0.5, true);
+        assert_eq!(footprint.levels[&6000], (2.0, 1.0));
+        assert_eq!(footprint.levels[&6001], (0.0, 0.5));
+        assert_eq!(footprint.price(6001), 60_010.0);
+        assert_eq!(footprint.max_volume(), 3.0);
+
+        // 가격이 낮으면 최소 tick
+        assert_eq!(Footprint::new(1.0, 2).row, 0.01);
+    }
+
+    #[test]
+    fn store_clears_on_chart_change() {
+        let mut store = FootprintStore::default();
+        store.add("BTCUSDT:1m", 0, 100.0, 1.0, true, 2);
+        store.add("BTCUSDT:1m", 60_000, 100.0, 1.0, false, 2);
+        assert_eq!(store.get("BTCUSDT:1m").map(|bars| bars.len()), Some(2));
+        assert!(store.get("ETHUSDT:1m").is_none());
+
+        store.add("ETHUSDT:1m", 0, 10.0, 1.0, true, 2);
+        assert_eq!(store.get("ETHUSDT:1m").map(|bars| bars.len()), Some(1));
+        assert!(store.get("BTCUSDT:1m").is_none());
+    }
+}
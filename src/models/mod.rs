@@ -1,12 +1,17 @@
 use crate::Candlestick;
+pub mod bars;
+pub mod footprint;
+pub mod symbol;
+pub use symbol::{ContractType, Symbol};
 
 #[derive(Clone, Debug)]
 pub struct SignalScoring {
-    pub bullish_engulfing: f32, // 0-100
-    pub bearish_engulfing: f32, // 0-100
-    pub morning_star: f32,      // 0-100
-    pub evening_star: f32,      // 0-100
-    pub total_score: f32,       // 0-100
+    pub bullish_engulfing: f64, // 0-100
+    pub bearish_engulfing: f64, // 0-100
+    pub morning_star: f64,      // 0-100
+    pub evening_star: f64,      // 0-100
+    pub indicator_cross: f64,   // 지표 교차 (MACD 등)
+    pub total_score: f64,       // 0-100
 }
 
 impl SignalScoring {
@@ -16,6 +21,7 @@ impl SignalScoring {
             bearish_engulfing: 0.0,
             morning_star: 0.0,
             evening_star: 0.0,
+            indicator_cross: 0.0,
             total_score: 0.0,
         }
     }
@@ -25,7 +31,7 @@ pub struct CandlestickPatterns;
 
 impl CandlestickPatterns {
     // 상승 포용선 패턴 감지
-    pub fn detect_bullish_engulfing(data: &[(&u64, &Candlestick)], current_idx: usize) -> f32 {
+    pub fn detect_bullish_engulfing(data: &[(&u64, &Candlestick)], current_idx: usize) -> f64 {
         if current_idx == 0 {
             return 0.0;
         }
@@ -57,7 +63,7 @@ impl CandlestickPatterns {
     }
 
     // 하락 포용선 패턴 감지
-    pub fn detect_bearish_engulfing(data: &[(&u64, &Candlestick)], current_idx: usize) -> f32 {
+    pub fn detect_bearish_engulfing(data: &[(&u64, &Candlestick)], current_idx: usize) -> f64 {
         if current_idx == 0 {
             return 0.0;
         }
@@ -88,7 +94,7 @@ impl CandlestickPatterns {
     }
 
     // 샛별 패턴 감지 (3캔들 패턴)
-    pub fn detect_morning_star(data: &[(&u64, &Candlestick)], current_idx: usize) -> f32 {
+    pub fn detect_morning_star(data: &[(&u64, &Candlestick)], current_idx: usize) -> f64 {
         if current_idx < 2 {
             return 0.0;
         }
@@ -132,7 +138,7 @@ impl CandlestickPatterns {
     }
 
     // 저녁별 패턴 감지 (3캔들 패턴)
-    pub fn detect_evening_star(data: &[(&u64, &Candlestick)], current_idx: usize) -> f32 {
+    pub fn detect_evening_star(data: &[(&u64, &Candlestick)], current_idx: usize) -> f64 {
         if current_idx < 2 {
             return 0.0;
         }
@@ -0,0 +1,150 @@
+use crate::api::MarketType;
+
+//계약 종류
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ContractType {
+    Spot,      // 현물
+    Perpetual, // 무기한
+    Delivery,  // 분기물 (만기 존재)
+}
+
+impl ContractType {
+    //exchangeInfo 의 contractType 문자열 변환 (현물은 빈 문자열)
+    pub fn from_binance(contract_type: &str) -> Self {
+        match contract_type {
+            "" => ContractType::Spot,
+            t if t.starts_with("PERPETUAL") || t.ends_with("PERPETUAL") => ContractType::Perpetual,
+            _ => ContractType::Delivery,
+        }
+    }
+}
+
+/*
+거래 심볼
+- base / quote : 기초 자산 / 호가 자산 (1000PEPE / USDT, BTC / USD)
+- exchange_symbol : 거래소 API 에 쓰는 심볼 (1000PEPEUSDT, BTCUSD_PERP, BTC-USDT-SWAP)
+- display_name : 화면 표시 이름
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    pub market: MarketType,
+    pub base: String,
+    pub quote: String,
+    pub contract_type: ContractType,
+    pub exchange_symbol: String,
+    pub display_name: String,
+    pub contract_size: f64, // 계약 1개당 가치 (COIN-M: USD, OKX: 기초 자산, 그 외 1.0)
+    pub delivery_date: Option<u64>, // 분기물 만기일 (ms)
+    pub quantity_precision: u32,
+    pub price_precision: u32,
+}
+
+impl Symbol {
+    pub fn new(
+        market: MarketType,
+        base: &str,
+        quote: &str,
+        contract_type: ContractType,
+        exchange_symbol: &str,
+    ) -> Self {
+        let display_name = match contract_type {
+            ContractType::Spot => format!("{}/{}", base, quote),
+            ContractType::Perpetual => format!("{}/{} Perp", base, quote),
+            // 분기물은 심볼 뒤의 만기 표기 (BTCUSD_250926 -> 250926)
+            ContractType::Delivery => format!(
+                "{}/{} {}",
+                base,
+                quote,
+                exchange_symbol.rsplit('_').next().unwrap_or_default()
+            ),
+        };
+
+        Self {
+            market,
+            base: base.to_string(),
+            quote: quote.to_string(),
+            contract_type,
+            exchange_symbol: exchange_symbol.to_string(),
+            display_name,
+            contract_size: 1.0,
+            delivery_date: None,
+            quantity_precision: 3,
+            price_precision: 2,
+        }
+    }
+
+    //exchangeInfo 조회 실패 시 사용하는 기본 심볼 (BTC -> BTCUSDT / BTCUSD_PERP)
+    pub fn fallback(market: MarketType, base: &str) -> Self {
+        match market {
+            MarketType::Futures | MarketType::BybitLinear => Symbol::new(
+                market,
+                base,
+                "USDT",
+                ContractType::Perpetual,
+                &format!("{}USDT", base),
+            ),
+            MarketType::Spot => Symbol::new(
+                market,
+                base,
+                "USDT",
+                ContractType::Spot,
+                &format!("{}USDT", base),
+            ),
+            MarketType::CoinM => {
+                let mut symbol = Symbol::new(
+                    market,
+                    base,
+                    "USD",
+                    ContractType::Perpetual,
+                    &format!("{}USD_PERP", base),
+                );
+                symbol.contract_size = if base == "BTC" { 100.0 } else { 10.0 };
+                symbol.quantity_precision = 0;
+                symbol
+            }
+            MarketType::OkxSwap => Symbol::new(
+                market,
+                base,
+                "USDT",
+                ContractType::Perpetual,
+                &format!("{}-USDT-SWAP", base),
+            ),
+        }
+    }
+
+    //OKX instId -> Symbol (BTC-USDT-SWAP -> BTC / USDT 무기한)
+    pub fn from_okx_inst_id(inst_id: &str) -> Option<Self> {
+        let mut parts = inst_id.split('-');
+        let (base, quote) = (parts.next()?, parts.next()?);
+        if parts.next()? != "SWAP" {
+            return None;
+        }
+        Some(Symbol::new(
+            MarketType::OkxSwap,
+            base,
+            quote,
+            ContractType::Perpetual,
+            inst_id,
+        ))
+    }
+
+    //COIN-M 인버스 계약 여부 (손익 / 증거금이 기초 자산)
+    pub fn is_inverse(&self) -> bool {
+        self.market == MarketType::CoinM
+    }
+
+    //증거금 / 잔고 자산 (인버스는 기초 자산)
+    pub fn margin_asset(&self) -> &str {
+        if self.is_inverse() {
+            &self.base
+        } else {
+            &self.quote
+        }
+    }
+}
+
+impl std::fmt::Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -0,0 +1,630 @@
+use crate::api::binance::fetch_native_candles_async;
+use crate::models::Symbol;
+use crate::store::resample::resample;
+use crate::uc;
+use crate::{CandleType, Candlestick};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/*
+로컬 캔들 저장소 (SQLite)
+- (venue, symbol, interval, open_time) 키로 저장
+- 세션이 끝나도 유지되어 과거 데이터가 계속 쌓임
+- 사용자 지정 타임프레임은 저장하지 않고 기준 캔들을 읽어서 리샘플링
+*/
+pub struct CandleStore {
+    conn: Connection,
+}
+
+impl CandleStore {
+    pub fn open() -> rusqlite::Result<Self> {
+        if let Some(dir) = Path::new(uc::CANDLE_STORE_PATH).parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        Self::with_connection(Connection::open(uc::CANDLE_STORE_PATH)?)
+    }
+
+    //테이블이 없으면 생성
+    fn with_connection(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS candles (
+                venue     TEXT    NOT NULL,
+                symbol    TEXT    NOT NULL,
+                interval  TEXT    NOT NULL,
+                open_time INTEGER NOT NULL,
+                open      REAL    NOT NULL,
+                high      REAL    NOT NULL,
+                low       REAL    NOT NULL,
+                close     REAL    NOT NULL,
+                volume    REAL    NOT NULL,
+                quote_volume     REAL    NOT NULL DEFAULT 0,
+                trades           INTEGER NOT NULL DEFAULT 0,
+                taker_buy_volume REAL    NOT NULL DEFAULT 0,
+                PRIMARY KEY (venue, symbol, interval, open_time)
+            ) WITHOUT ROWID;
+            CREATE TABLE IF NOT EXISTS missing_ranges (
+                venue      TEXT    NOT NULL,
+                symbol     TEXT    NOT NULL,
+                interval   TEXT    NOT NULL,
+                start_time INTEGER NOT NULL,
+                end_time   INTEGER NOT NULL,
+                PRIMARY KEY (venue, symbol, interval, start_time)
+            ) WITHOUT ROWID;",
+        )?;
+
+        // 이전 버전 저장소에는 거래대금 / 체결 수 / 시장가 매수 거래량 열이 없음
+        let columns: Vec<String> = conn
+            .prepare("SELECT name FROM pragma_table_info('candles')")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        for (column, kind) in [
+            ("quote_volume", "REAL"),
+            ("trades", "INTEGER"),
+            ("taker_buy_volume", "REAL"),
+        ] {
+            if !columns.iter().any(|name| name == column) {
+                conn.execute_batch(&format!(
+                    "ALTER TABLE candles ADD COLUMN {} {} NOT NULL DEFAULT 0;",
+                    column, kind
+                ))?;
+            }
+        }
+        Ok(Self { conn })
+    }
+
+    //마지막으로 저장된 캔들 시각
+    pub fn latest_open_time(
+        &self,
+        symbol: &Symbol,
+        candle_type: &CandleType,
+    ) -> rusqlite::Result<Option<u64>> {
+        if let Some(base) = candle_type.resample_base(symbol.market) {
+            return self.latest_open_time(symbol, &base);
+        }
+        self.conn
+            .query_row(
+                "SELECT MAX(open_time) FROM candles
+                 WHERE venue = ?1 AND symbol = ?2 AND interval = ?3",
+                params![
+                    symbol.market.venue_key(),
+                    symbol.exchange_symbol,
+                    candle_type.interval()
+                ],
+                |row| row.get::<_, Option<i64>>(0),
+            )
+            .optional()
+            .map(|time| time.flatten().map(|time| time as u64))
+    }
+
+    /*
+    저장된 캔들 사이의 빈 구간 (이전 캔들 시각, 다음 캔들 시각)
+    - 다음 캔들 시각이 이전 캔들의 다음 시작 시각과 다르면 빈 구간
+    - 거래소에도 없는 구간 (mark_missing) 은 제외
+    */
+    pub fn gaps(
+        &self,
+        symbol: &Symbol,
+        candle_type: &CandleType,
+    ) -> rusqlite::Result<Vec<(u64, u64)>> {
+        let missing: HashSet<(u64, u64)> = self
+            .conn
+            .prepare(
+                "SELECT start_time, end_time FROM missing_ranges
+                 WHERE venue = ?1 AND symbol = ?2 AND interval = ?3",
+            )?
+            .query_map(
+                params![
+                    symbol.market.venue_key(),
+                    symbol.exchange_symbol,
+                    candle_type.interval()
+                ],
+                |row| Ok((row.get::<_, i64>(0)? as u64, row.get::<_, i64>(1)? as u64)),
+            )?
+            .collect::<rusqlite::Result<_>>()?;
+
+        Ok(self
+            .open_times(symbol, candle_type, 0, u64::MAX >> 1)?
+            .windows(2)
+            .filter(|pair| candle_type.next_open_time(pair[0]) < pair[1])
+            .map(|pair| (pair[0], pair[1]))
+            .filter(|gap| !missing.contains(gap))
+            .collect())
+    }
+
+    //[from, to] 안에 남은 빈 구간을 거래소에도 없는 구간으로 저장 (다음 동기화부터 건너뜀)
+    pub fn mark_missing(
+        &mut self,
+        symbol: &Symbol,
+        candle_type: &CandleType,
+        from: u64,
+        to: u64,
+    ) -> rusqlite::Result<()> {
+        let open_times = self.open_times(symbol, candle_type, from, to)?;
+        let transaction = self.conn.transaction()?;
+        {
+            let mut statement = transaction.prepare(
+                "INSERT OR REPLACE INTO missing_ranges
+                 (venue, symbol, interval, start_time, end_time)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            for pair in open_times.windows(2) {
+                if candle_type.next_open_time(pair[0]) < pair[1] {
+                    statement.execute(params![
+                        symbol.market.venue_key(),
+                        symbol.exchange_symbol,
+                        candle_type.interval(),
+                        pair[0] as i64,
+                        pair[1] as i64
+                    ])?;
+                }
+            }
+        }
+        transaction.commit()
+    }
+
+    //기간 내 저장된 캔들 시각 [from, to]
+    fn open_times(
+        &self,
+        symbol: &Symbol,
+        candle_type: &CandleType,
+        from: u64,
+        to: u64,
+    ) -> rusqlite::Result<Vec<u64>> {
+        let mut statement = self.conn.prepare(
+            "SELECT open_time FROM candles
+             WHERE venue = ?1 AND symbol = ?2 AND interval = ?3
+               AND open_time BETWEEN ?4 AND ?5
+             ORDER BY open_time",
+        )?;
+        let rows = statement.query_map(
+            params![
+                symbol.market.venue_key(),
+                symbol.exchange_symbol,
+                candle_type.interval(),
+                from as i64,
+                to as i64
+            ],
+            |row| row.get::<_, i64>(0).map(|time| time as u64),
+        )?;
+        rows.collect()
+    }
+
+    //최근 캔들 limit 개
+    pub fn load(
+        &self,
+        symbol: &Symbol,
+        candle_type: &CandleType,
+        limit: usize,
+    ) -> rusqlite::Result<BTreeMap<u64, Candlestick>> {
+        if let Some(base) = candle_type.resample_base(symbol.market) {
+            let candles = self.load(
+                symbol,
+                &base,
+                (limit + 1) * candle_type.resample_factor(symbol.market),
+            )?;
+            return Ok(last(resample(&candles, candle_type), limit));
+        }
+        let mut statement = self.conn.prepare(
+            "SELECT open_time, open, high, low, close, volume, quote_volume, trades, taker_buy_volume
+             FROM candles
+             WHERE venue = ?1 AND symbol = ?2 AND interval = ?3
+             ORDER BY open_time DESC LIMIT ?4",
+        )?;
+        let rows = statement.query_map(
+            params![
+                symbol.market.venue_key(),
+                symbol.exchange_symbol,
+                candle_type.interval(),
+                limit as i64
+            ],
+            candle_row,
+        )?;
+        rows.collect()
+    }
+
+    //before 이전 캔들 limit 개 (리플레이 시작 전 구간)
+    pub fn load_before(
+        &self,
+        symbol: &Symbol,
+        candle_type: &CandleType,
+        before: u64,
+        limit: usize,
+    ) -> rusqlite::Result<BTreeMap<u64, Candlestick>> {
+        if let Some(base) = candle_type.resample_base(symbol.market) {
+            let candles = self.load_before(
+                symbol,
+                &base,
+                candle_type.open_time(before),
+                (limit + 1) * candle_type.resample_factor(symbol.market),
+            )?;
+            return Ok(last(resample(&candles, candle_type), limit));
+        }
+        let mut statement = self.conn.prepare(
+            "SELECT open_time, open, high, low, close, volume, quote_volume, trades, taker_buy_volume
+             FROM candles
+             WHERE venue = ?1 AND symbol = ?2 AND interval = ?3 AND open_time < ?4
+             ORDER BY open_time DESC LIMIT ?5",
+        )?;
+        let rows = statement.query_map(
+            params![
+                symbol.market.venue_key(),
+                symbol.exchange_symbol,
+                candle_type.interval(),
+                before as i64,
+                limit as i64
+            ],
+            candle_row,
+        )?;
+        rows.collect()
+    }
+
+    //기간 내 캔들 [from, to]
+    pub fn load_range(
+        &self,
+        symbol: &Symbol,
+        candle_type: &CandleType,
+        from: u64,
+        to: u64,
+    ) -> rusqlite::Result<BTreeMap<u64, Candlestick>> {
+        if let Some(base) = candle_type.resample_base(symbol.market) {
+            let candles = self.load_range(symbol, &base, candle_type.open_time(from), to)?;
+            return Ok(resample(&candles, candle_type));
+        }
+        let mut statement = self.conn.prepare(
+            "SELECT open_time, open, high, low, close, volume, quote_volume, trades, taker_buy_volume
+             FROM candles
+             WHERE venue = ?1 AND symbol = ?2 AND interval = ?3
+               AND open_time BETWEEN ?4 AND ?5",
+        )?;
+        let rows = statement.query_map(
+            params![
+                symbol.market.venue_key(),
+                symbol.exchange_symbol,
+                candle_type.interval(),
+                from as i64,
+                to as i64
+            ],
+            candle_row,
+        )?;
+        rows.collect()
+    }
+
+    //캔들 저장 (같은 시각은 덮어씀 - 진행 중이던 캔들 갱신)
+    //사용자 지정 타임프레임은 기준 캔들에서 만들어지므로 저장하지 않음
+    pub fn save(
+        &mut self,
+        symbol: &Symbol,
+        candle_type: &CandleType,
+        candles: &BTreeMap<u64, Candlestick>,
+    ) -> rusqlite::Result<()> {
+        if candle_type.resample_base(symbol.market).is_some() {
+            return Ok(());
+        }
+        let transaction = self.conn.transaction()?;
+        {
+            let mut statement = transaction.prepare(
+                "INSERT OR REPLACE INTO candles
+                 (venue, symbol, interval, open_time, open, high, low, close, volume,
+                  quote_volume, trades, taker_buy_volume)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            )?;
+            for (open_time, candle) in candles {
+                statement.execute(params![
+                    symbol.market.venue_key(),
+                    symbol.exchange_symbol,
+                    candle_type.interval(),
+                    *open_time as i64,
+                    candle.open,
+                    candle.high,
+                    candle.low,
+                    candle.close,
+                    candle.volume,
+                    candle.quote_volume,
+                    candle.trades,
+                    candle.taker_buy_volume
+                ])?;
+            }
+        }
+        transaction.commit()
+    }
+}
+
+//마지막 limit 개만 남김
+fn last(mut candles: BTreeMap<u64, Candlestick>, limit: usize) -> BTreeMap<u64, Candlestick> {
+    while candles.len() > limit {
+        candles.pop_first();
+    }
+    candles
+}
+
+//(open_time, open, high, low, close, volume, quote_volume, trades, taker_buy_volume) 행 -> 캔들
+fn candle_row(row: &rusqlite::Row) -> rusqlite::Result<(u64, Candlestick)> {
+    Ok((
+        row.get::<_, i64>(0)? as u64,
+        Candlestick {
+            open: row.get(1)?,
+            high: row.get(2)?,
+            low: row.get(3)?,
+            close: row.get(4)?,
+            volume: row.get(5)?,
+            quote_volume: row.get(6)?,
+            trades: row.get(7)?,
+            taker_buy_volume: row.get(8)?,
+        },
+    ))
+}
+
+/*
+저장소 기반 캔들 로드
+- 저장된 캔들이 없으면 최신 1페이지만 조회
+- 있으면 최신부터 거꾸로 마지막 저장 캔들까지 빠진 구간만 조회
+  (마지막 저장 캔들은 진행 중이었을 수 있으므로 다시 받음)
+- 네트워크 오류 시 저장된 캔들만 반환
+- 사용자 지정 타임프레임은 기준 캔들을 동기화 후 리샘플링
+  (저장된 캔들이 없으면 화면을 채울 만큼 여러 페이지 조회)
+- 페이지 제한으로 중간에 빈 구간이 남으면 최신 구간부터 채움 (fill_gaps)
+*/
+pub async fn sync_candles_async(
+    symbol: &Symbol,
+    candle_type: &CandleType,
+) -> Result<BTreeMap<u64, Candlestick>, Box<dyn std::error::Error>> {
+    let fetch_type = candle_type
+        .resample_base(symbol.market)
+        .unwrap_or(candle_type.clone());
+    let wanted = uc::CANDLE_VIEW_LIMIT * candle_type.resample_factor(symbol.market);
+    let latest = CandleStore::open()?.latest_open_time(symbol, &fetch_type)?;
+
+    let mut fetched = BTreeMap::new();
+    let mut end_time = None;
+    for _ in 0..uc::CANDLE_SYNC_MAX_PAGES {
+        let page = match fetch_native_candles_async(symbol, &fetch_type, end_time).await {
+            Ok(page) => page,
+            Err(e) if latest.is_none() && fetched.is_empty() => return Err(e),
+            Err(e) => {
+                println!("Candle sync stopped for {}: {}", symbol, e);
+                break;
+            }
+        };
+        let Some(&oldest) = page.keys().next() else {
+            break;
+        };
+        fetched.extend(page);
+
+        match latest {
+            Some(latest) if oldest > latest => end_time = Some(oldest - 1),
+            None if fetched.len() < wanted
+                && candle_type.resample_base(symbol.market).is_some() =>
+            {
+                end_time = Some(oldest - 1)
+            }
+            _ => break,
+        }
+    }
+
+    let mut store = CandleStore::open()?;
+    store.save(symbol, &fetch_type, &fetched)?;
+    if let Err(e) = fill_gaps(&mut store, symbol, &fetch_type).await {
+        println!("Candle gap fill stopped for {}: {}", symbol, e);
+    }
+    Ok(store.load(symbol, candle_type, uc::CANDLE_VIEW_LIMIT)?)
+}
+
+/*
+저장된 캔들 사이 빈 구간 채우기
+- 빈 구간 끝에서 거꾸로 구간 시작까지 조회, 페이지마다 저장
+- 한 번에 최대 CANDLE_GAP_MAX_PAGES 페이지 (남은 구간은 다음 동기화에서 이어서 채움)
+- 구간 시작까지 조회해도 남은 빈 구간 (점검, 가져온 CSV 등) 은 저장해 두고 다시 조회하지 않음
+*/
+async fn fill_gaps(
+    store: &mut CandleStore,
+    symbol: &Symbol,
+    candle_type: &CandleType,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut pages = 0;
+    for (before, after) in store.gaps(symbol, candle_type)?.into_iter().rev() {
+        let mut end_time = after - 1;
+        loop {
+            if pages >= uc::CANDLE_GAP_MAX_PAGES {
+                return Ok(());
+            }
+            pages += 1;
+            let page = fetch_native_candles_async(symbol, candle_type, Some(end_time)).await?;
+            store.save(symbol, candle_type, &page)?;
+            match page.keys().next() {
+                Some(&oldest) if oldest > before => end_time = oldest - 1,
+                _ => break,
+            }
+        }
+        store.mark_missing(symbol, candle_type, before, after)?;
+    }
+    Ok(())
+}
+
+/*
+끊긴 구간 다시 채우기
+- 최신부터 거꾸로 from 이 포함될 때까지 조회 후 저장
+- from 이후 캔들 반환 (진행 중인 캔들 포함)
+*/
+pub async fn backfill_candles_async(
+    symbol: &Symbol,
+    candle_type: &CandleType,
+    from: u64,
+) -> Result<BTreeMap<u64, Candlestick>, Box<dyn std::error::Error>> {
+    let fetch_type = candle_type
+        .resample_base(symbol.market)
+        .unwrap_or(candle_type.clone());
+    let from = candle_type.open_time(from);
+
+    let mut fetched = BTreeMap::new();
+    let mut end_time = None;
+    for _ in 0..uc::CANDLE_SYNC_MAX_PAGES {
+        let page = fetch_native_candles_async(symbol, &fetch_type, end_time).await?;
+        let Some(&oldest) = page.keys().next() else {
+            break;
+        };
+        fetched.extend(page);
+        if oldest <= from {
+            break;
+        }
+        end_time = Some(oldest - 1);
+    }
+
+    let mut store = CandleStore::open()?;
+    store.save(symbol, &fetch_type, &fetched)?;
+    Ok(store.load_range(symbol, candle_type, from, u64::MAX >> 1)?)
+}
+
+//저장된 최근 캔들 (거래소와 동기화는 sync_candles_async 로 UI 밖에서)
+pub fn load_candles(
+    symbol: &Symbol,
+    candle_type: &CandleType,
+) -> Result<BTreeMap<u64, Candlestick>, Box<dyn std::error::Error>> {
+    Ok(CandleStore::open()?.load(symbol, candle_type, uc::CANDLE_VIEW_LIMIT)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::MarketType;
+
+    const MINUTE_MS: u64 = 60_000;
+
+    fn store() -> CandleStore {
+        CandleStore::with_connection(Connection::open_in_memory().unwrap()).unwrap()
+    }
+
+    fn candles(minutes: &[u64]) -> BTreeMap<u64, Candlestick> {
+        minutes
+            .iter()
+            .map(|&minute| {
+                let price = minute as f64;
+                let candle = Candlestick {
+                    open: price,
+                    high: price + 1.0,
+                    low: price - 1.0,
+                    close: price,
+                    volume: 1.0,
+                    ..Default::default()
+                };
+                (minute * MINUTE_MS, candle)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn load_latest_candles() {
+        let mut store = store();
+        let symbol = Symbol::fallback(MarketType::Futures, "BTC");
+        store
+            .save(&symbol, &CandleType::Minute1, &candles(&[1, 2, 3]))
+            .unwrap();
+
+        let loaded = store.load(&symbol, &CandleType::Minute1, 2).unwrap();
+        assert_eq!(
+            loaded.keys().copied().collect::<Vec<_>>(),
+            [2 * MINUTE_MS, 3 * MINUTE_MS]
+        );
+        assert_eq!(
+            store
+                .latest_open_time(&symbol, &CandleType::Minute1)
+                .unwrap(),
+            Some(3 * MINUTE_MS)
+        );
+
+        // 다른 거래소 / 타임프레임은 따로 저장
+        let other = Symbol::fallback(MarketType::BybitLinear, "BTC");
+        assert_eq!(
+            store
+                .latest_open_time(&other, &CandleType::Minute1)
+                .unwrap(),
+            None
+        );
+        assert!(store
+            .load(&symbol, &CandleType::Day, 10)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn save_replaces_in_progress_candle() {
+        let mut store = store();
+        let symbol = Symbol::fallback(MarketType::Futures, "BTC");
+        store
+            .save(&symbol, &CandleType::Minute1, &candles(&[1, 2]))
+            .unwrap();
+
+        let mut updated = candles(&[2]);
+        updated.get_mut(&(2 * MINUTE_MS)).unwrap().close = 10.0;
+        store.save(&symbol, &CandleType::Minute1, &updated).unwrap();
+
+        let loaded = store.load(&symbol, &CandleType::Minute1, 10).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[&(2 * MINUTE_MS)].close, 10.0);
+    }
+
+    #[test]
+    fn load_range_is_inclusive() {
+        let mut store = store();
+        let symbol = Symbol::fallback(MarketType::Futures, "BTC");
+        store
+            .save(&symbol, &CandleType::Minute1, &candles(&[1, 2, 3, 4]))
+            .unwrap();
+
+        let loaded = store
+            .load_range(&symbol, &CandleType::Minute1, 2 * MINUTE_MS, 3 * MINUTE_MS)
+            .unwrap();
+        assert_eq!(
+            loaded.keys().copied().collect::<Vec<_>>(),
+            [2 * MINUTE_MS, 3 * MINUTE_MS]
+        );
+    }
+
+    #[test]
+    fn detect_gaps() {
+        // 3, 4 분 / 7 분 캔들 없음
+        let mut store = store();
+        let symbol = Symbol::fallback(MarketType::Futures, "BTC");
+        store
+            .save(&symbol, &CandleType::Minute1, &candles(&[1, 2, 5, 6, 8]))
+            .unwrap();
+        assert_eq!(
+            store.gaps(&symbol, &CandleType::Minute1).unwrap(),
+            [
+                (2 * MINUTE_MS, 5 * MINUTE_MS),
+                (6 * MINUTE_MS, 8 * MINUTE_MS)
+            ]
+        );
+    }
+
+    #[test]
+    fn skip_missing_gaps() {
+        let mut store = store();
+        let symbol = Symbol::fallback(MarketType::Futures, "BTC");
+        store
+            .save(&symbol, &CandleType::Minute1, &candles(&[1, 2, 5, 6, 8]))
+            .unwrap();
+
+        // 2 ~ 6 분 구간을 조회했지만 4 분만 채워짐 -> 남은 빈 구간은 건너뜀
+        store
+            .save(&symbol, &CandleType::Minute1, &candles(&[4]))
+            .unwrap();
+        store
+            .mark_missing(&symbol, &CandleType::Minute1, 2 * MINUTE_MS, 6 * MINUTE_MS)
+            .unwrap();
+        assert_eq!(
+            store.gaps(&symbol, &CandleType::Minute1).unwrap(),
+            [(6 * MINUTE_MS, 8 * MINUTE_MS)]
+        );
+
+        // 다른 거래소는 따로
+        let other = Symbol::fallback(MarketType::BybitLinear, "BTC");
+        store
+            .save(&other, &CandleType::Minute1, &candles(&[1, 2, 5]))
+            .unwrap();
+        assert_eq!(
+            store.gaps(&other, &CandleType::Minute1).unwrap(),
+            [(2 * MINUTE_MS, 5 * MINUTE_MS)]
+        );
+    }
+}
@@ -0,0 +1,467 @@
+use crate::models::Symbol;
+use crate::store::candles::CandleStore;
+use crate::uc;
+use crate::{CandleType, Candlestick};
+use parquet::basic::Compression;
+use parquet::data_type::{DoubleType, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::file::writer::SerializedFileWriter;
+use parquet::record::Field;
+use parquet::schema::parser::parse_message_type;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+//pandas / Polars 에서 바로 읽을 수 있는 캔들 스키마 (open_time 은 UTC ms)
+const PARQUET_SCHEMA: &str = "
+message candles {
+    REQUIRED INT64 open_time (TIMESTAMP(MILLIS,true));
+    REQUIRED DOUBLE open;
+    REQUIRED DOUBLE high;
+    REQUIRED DOUBLE low;
+    REQUIRED DOUBLE close;
+    REQUIRED DOUBLE volume;
+    REQUIRED DOUBLE quote_volume;
+    REQUIRED INT64 trades;
+    REQUIRED DOUBLE taker_buy_volume;
+}";
+
+//내보내기 / 가져오기 파일 형식
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum FileFormat {
+    Csv,
+    Parquet,
+}
+
+impl FileFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FileFormat::Csv => "csv",
+            FileFormat::Parquet => "parquet",
+        }
+    }
+
+    //확장자로 형식 판단
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "csv" => Some(FileFormat::Csv),
+            "parquet" | "pq" => Some(FileFormat::Parquet),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for FileFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileFormat::Csv => write!(f, "CSV"),
+            FileFormat::Parquet => write!(f, "Parquet"),
+        }
+    }
+}
+
+/*
+저장소의 캔들을 파일로 내보내기
+- 기간 [from, to] (ms)
+- data/export/{venue}_{symbol}_{interval}_{from}_{to}.{csv|parquet}
+*/
+pub fn export_candles(
+    symbol: &Symbol,
+    candle_type: &CandleType,
+    from: u64,
+    to: u64,
+    format: FileFormat,
+) -> Result<(PathBuf, usize), Box<dyn std::error::Error>> {
+    let candles = CandleStore::open()?.load_range(symbol, candle_type, from, to)?;
+    if candles.is_empty() {
+        return Err("No candles in the selected range".into());
+    }
+
+    std::fs::create_dir_all(uc::CANDLE_EXPORT_DIR)?;
+    let path = Path::new(uc::CANDLE_EXPORT_DIR).join(format!(
+        "{}_{}_{}_{}_{}.{}",
+        symbol.market.venue_key(),
+        symbol.exchange_symbol,
+        candle_type.interval(),
+        format_date(from),
+        format_date(to),
+        format.extension()
+    ));
+
+    match format {
+        FileFormat::Csv => write_csv(&path, &candles)?,
+        FileFormat::Parquet => write_parquet(&path, &candles)?,
+    }
+    Ok((path, candles.len()))
+}
+
+//외부 OHLCV 파일 읽기 (형식은 확장자로 판단)
+pub fn import_candles(
+    path: &Path,
+) -> Result<BTreeMap<u64, Candlestick>, Box<dyn std::error::Error>> {
+    let candles = match FileFormat::from_path(path) {
+        Some(FileFormat::Csv) => read_csv(path)?,
+        Some(FileFormat::Parquet) => read_parquet(path)?,
+        None => return Err(format!("Unsupported file: {}", path.display()).into()),
+    };
+
+    if candles.is_empty() {
+        Err("No valid candles in file".into())
+    } else {
+        Ok(candles)
+    }
+}
+
+pub fn write_csv(
+    path: &Path,
+    candles: &BTreeMap<u64, Candlestick>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = csv::Writer::from_path(path)?;
+    writer.write_record([
+        "open_time",
+        "open",
+        "high",
+        "low",
+        "close",
+        "volume",
+        "quote_volume",
+        "trades",
+        "taker_buy_volume",
+    ])?;
+    for (open_time, candle) in candles {
+        writer.write_record([
+            open_time.to_string(),
+            candle.open.to_string(),
+            candle.high.to_string(),
+            candle.low.to_string(),
+            candle.close.to_string(),
+            candle.volume.to_string(),
+            candle.quote_volume.to_string(),
+            candle.trades.to_string(),
+            candle.taker_buy_volume.to_string(),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/*
+CSV 읽기
+- 헤더 이름으로 열을 찾음 (대소문자 무시)
+- 시간 열 : open_time / timestamp / time / date / datetime
+- volume / quote_volume / trades / taker_buy_volume 열은 없으면 0
+*/
+pub fn read_csv(path: &Path) -> Result<BTreeMap<u64, Candlestick>, Box<dyn std::error::Error>> {
+    let mut reader = csv::Reader::from_path(path)?;
+    let headers: Vec<String> = reader
+        .headers()?
+        .iter()
+        .map(|header| header.trim().to_lowercase())
+        .collect();
+    let column = |names: &[&str]| headers.iter().position(|h| names.contains(&h.as_str()));
+
+    let time = column(&["open_time", "timestamp", "time", "date", "datetime"])
+        .ok_or("Missing time column")?;
+    let open = column(&["open"]).ok_or("Missing open column")?;
+    let high = column(&["high"]).ok_or("Missing high column")?;
+    let low = column(&["low"]).ok_or("Missing low column")?;
+    let close = column(&["close"]).ok_or("Missing close column")?;
+    let volume = column(&["volume", "vol"]);
+    let quote_volume = column(&["quote_volume", "quote_asset_volume"]);
+    let trades = column(&["trades", "number_of_trades", "count"]);
+    let taker_buy_volume = column(&["taker_buy_volume", "taker_buy_base_asset_volume"]);
+
+    let mut candles = BTreeMap::new();
+    for record in reader.records() {
+        let record = record?;
+        let value = |i: usize| record.get(i).and_then(|v| v.trim().parse::<f64>().ok());
+
+        let Some(open_time) = record.get(time).and_then(parse_time) else {
+            continue;
+        };
+        if let (Some(open), Some(high), Some(low), Some(close)) =
+            (value(open), value(high), value(low), value(close))
+        {
+            candles.insert(
+                open_time,
+                Candlestick {
+                    open,
+                    high,
+                    low,
+                    close,
+                    volume: volume.and_then(value).unwrap_or(0.0),
+                    quote_volume: quote_volume.and_then(value).unwrap_or(0.0),
+                    trades: trades.and_then(value).unwrap_or(0.0) as u32,
+                    taker_buy_volume: taker_buy_volume.and_then(value).unwrap_or(0.0),
+                },
+            );
+        }
+    }
+    Ok(candles)
+}
+
+pub fn write_parquet(
+    path: &Path,
+    candles: &BTreeMap<u64, Candlestick>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let schema = Arc::new(parse_message_type(PARQUET_SCHEMA)?);
+    let properties = Arc::new(
+        WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build(),
+    );
+    let mut writer = SerializedFileWriter::new(File::create(path)?, schema, properties)?;
+
+    let open_times: Vec<i64> = candles.keys().map(|&t| t as i64).collect();
+    let trades: Vec<i64> = candles.values().map(|c| c.trades as i64).collect();
+    let values: [Vec<f64>; 7] = [
+        candles.values().map(|c| c.open).collect(),
+        candles.values().map(|c| c.high).collect(),
+        candles.values().map(|c| c.low).collect(),
+        candles.values().map(|c| c.close).collect(),
+        candles.values().map(|c| c.volume).collect(),
+        candles.values().map(|c| c.quote_volume).collect(),
+        candles.values().map(|c| c.taker_buy_volume).collect(),
+    ];
+
+    // 스키마 열 순서대로 기록
+    let mut row_group = writer.next_row_group()?;
+    let mut index = 0;
+    while let Some(mut column) = row_group.next_column()? {
+        match index {
+            0 => column
+                .typed::<Int64Type>()
+                .write_batch(&open_times, None, None)?,
+            7 => column
+                .typed::<Int64Type>()
+                .write_batch(&trades, None, None)?,
+            8 => column
+                .typed::<DoubleType>()
+                .write_batch(&values[6], None, None)?,
+            _ => column
+                .typed::<DoubleType>()
+                .write_batch(&values[index - 1], None, None)?,
+        };
+        column.close()?;
+        index += 1;
+    }
+    row_group.close()?;
+    writer.close()?;
+    Ok(())
+}
+
+//Parquet 읽기 (열 이름 규칙은 CSV 와 같음)
+pub fn read_parquet(path: &Path) -> Result<BTreeMap<u64, Candlestick>, Box<dyn std::error::Error>> {
+    let reader = SerializedFileReader::new(File::open(path)?)?;
+
+    let mut candles = BTreeMap::new();
+    for row in reader.get_row_iter(None)? {
+        let row = row?;
+        let (mut open_time, mut open, mut high, mut low, mut close) =
+            (None, None, None, None, None);
+        let mut extra = Candlestick::default();
+
+        for (name, field) in row.get_column_iter() {
+            match name.to_lowercase().as_str() {
+                "open_time" | "timestamp" | "time" | "date" | "datetime" => {
+                    open_time = field_time(field)
+                }
+                "open" => open = field_f64(field),
+                "high" => high = field_f64(field),
+                "low" => low = field_f64(field),
+                "close" => close = field_f64(field),
+                "volume" | "vol" => extra.volume = field_f64(field).unwrap_or(0.0),
+                "quote_volume" | "quote_asset_volume" => {
+                    extra.quote_volume = field_f64(field).unwrap_or(0.0)
+                }
+                "trades" | "number_of_trades" | "count" => {
+                    extra.trades = field_f64(field).unwrap_or(0.0) as u32
+                }
+                "taker_buy_volume" | "taker_buy_base_asset_volume" => {
+                    extra.taker_buy_volume = field_f64(field).unwrap_or(0.0)
+                }
+                _ => {}
+            }
+        }
+
+        if let (Some(open_time), Some(open), Some(high), Some(low), Some(close)) =
+            (open_time, open, high, low, close)
+        {
+            candles.insert(
+                open_time,
+                Candlestick {
+                    open,
+                    high,
+                    low,
+                    close,
+                    ..extra
+                },
+            );
+        }
+    }
+    Ok(candles)
+}
+
+fn field_f64(field: &Field) -> Option<f64> {
+    match field {
+        Field::Double(v) => Some(*v),
+        Field::Float(v) => Some(*v as f64),
+        Field::Long(v) => Some(*v as f64),
+        Field::Int(v) => Some(*v as f64),
+        Field::Str(v) => v.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn field_time(field: &Field) -> Option<u64> {
+    match field {
+        Field::TimestampMillis(v) => Some(*v as u64),
+        Field::TimestampMicros(v) => Some((*v / 1000) as u64),
+        Field::Date(days) => Some(*days as u64 * 86_400_000),
+        Field::Long(v) => parse_time(&v.to_string()),
+        Field::Int(v) => parse_time(&v.to_string()),
+        Field::Str(v) => parse_time(v),
+        _ => None,
+    }
+}
+
+/*
+시간 문자열 -> UTC ms
+- 숫자 : 초 / 밀리초 / 마이크로초 / 나노초 자릿수로 판단
+- 문자열 : RFC3339, "YYYY-MM-DD HH:MM:SS", "YYYY-MM-DD"
+*/
+pub fn parse_time(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if let Ok(number) = value.parse::<f64>() {
+        let number = number as u64;
+        return Some(match number {
+            n if n < 100_000_000_000 => n * 1000,
+            n if n < 100_000_000_000_000 => n,
+            n if n < 100_000_000_000_000_000 => n / 1000,
+            n => n / 1_000_000,
+        });
+    }
+    if let Ok(datetime) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(datetime.timestamp_millis() as u64);
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S"] {
+        if let Ok(datetime) = chrono::NaiveDateTime::parse_from_str(value, format) {
+            return Some(datetime.and_utc().timestamp_millis() as u64);
+        }
+    }
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| datetime.and_utc().timestamp_millis() as u64)
+}
+
+//ms -> YYYYMMDD (파일 이름용)
+fn format_date(time: u64) -> String {
+    chrono::DateTime::from_timestamp_millis(time as i64)
+        .map(|datetime| datetime.format("%Y%m%d").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles() -> BTreeMap<u64, Candlestick> {
+        [
+            (1_704_067_200_000, 42_000.5, 12.25, 310),
+            (1_704_067_260_000, 42_010.0, 3.5, 42),
+        ]
+        .into_iter()
+        .map(|(open_time, price, volume, trades)| {
+            let candle = Candlestick {
+                open: price,
+                high: price + 10.0,
+                low: price - 10.0,
+                close: price + 5.0,
+                volume,
+                quote_volume: price * volume,
+                trades,
+                taker_buy_volume: volume / 2.0,
+            };
+            (open_time, candle)
+        })
+        .collect()
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("futurx_{}_{}", std::process::id(), name))
+    }
+
+    fn assert_same(actual: &BTreeMap<u64, Candlestick>, expected: &BTreeMap<u64, Candlestick>) {
+        assert_eq!(actual.len(), expected.len());
+        for ((time, a), (expected_time, b)) in actual.iter().zip(expected) {
+            assert_eq!(time, expected_time);
+            assert_eq!(
+                (a.open, a.high, a.low, a.close, a.volume),
+                (b.open, b.high, b.low, b.close, b.volume)
+            );
+            assert_eq!(
+                (a.quote_volume, a.trades, a.taker_buy_volume),
+                (b.quote_volume, b.trades, b.taker_buy_volume)
+            );
+        }
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let path = temp_path("round_trip.csv");
+        write_csv(&path, &candles()).unwrap();
+        let imported = import_candles(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_same(&imported, &candles());
+    }
+
+    #[test]
+    fn parquet_round_trip() {
+        let path = temp_path("round_trip.parquet");
+        write_parquet(&path, &candles()).unwrap();
+        let imported = import_candles(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_same(&imported, &candles());
+    }
+
+    #[test]
+    fn csv_with_other_headers() {
+        // 대소문자 / 다른 열 이름, 초 단위 시각, 거래량 없음, 잘못된 줄은 건너뜀
+        let path = temp_path("headers.csv");
+        std::fs::write(
+            &path,
+            "Timestamp,Open,High,Low,Close\n1704067200,1,2,0.5,1.5\nbad,1,2,0.5,1.5\n",
+        )
+        .unwrap();
+        let imported = import_candles(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(imported.len(), 1);
+        let candle = &imported[&1_704_067_200_000];
+        assert_eq!((candle.open, candle.close, candle.volume), (1.0, 1.5, 0.0));
+    }
+
+    #[test]
+    fn parse_time_formats() {
+        let expected = Some(1_704_067_200_000);
+        for value in [
+            "1704067200",
+            "1704067200000",
+            "1704067200000000",
+            "2024-01-01T00:00:00Z",
+            "2024-01-01 00:00:00",
+            "2024-01-01",
+        ] {
+            assert_eq!(parse_time(value), expected, "{}", value);
+        }
+        assert_eq!(parse_time("yesterday"), None);
+        assert_eq!(
+            FileFormat::from_path(Path::new("a.PQ")),
+            Some(FileFormat::Parquet)
+        );
+        assert_eq!(FileFormat::from_path(Path::new("a.txt")), None);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::indicators::IndicatorConfig;
+use crate::models::Symbol;
+use crate::uc;
+use crate::CandleType;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/*
+차트별 지표 구성 (JSON)
+- 키: {venue}:{symbol}:{interval}
+- 저장된 구성이 없는 차트는 기본 구성 사용
+*/
+type SavedConfigs = BTreeMap<String, Vec<IndicatorConfig>>;
+
+fn chart_key(symbol: &Symbol, candle_type: &CandleType) -> String {
+    format!(
+        "{}:{}:{}",
+        symbol.market.venue_key(),
+        symbol.exchange_symbol,
+        candle_type.interval()
+    )
+}
+
+fn read_all() -> Result<SavedConfigs, Box<dyn std::error::Error>> {
+    let path = Path::new(uc::INDICATOR_CONFIG_PATH);
+    if !path.exists() {
+        return Ok(SavedConfigs::new());
+    }
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+//저장된 차트 지표 구성 (범위를 벗어난 파라미터는 보정)
+pub fn load_indicators(symbol: &Symbol, candle_type: &CandleType) -> Option<Vec<IndicatorConfig>> {
+    match read_all() {
+        Ok(mut saved) => saved
+            .remove(&chart_key(symbol, candle_type))
+            .map(|mut configs| {
+                configs.iter_mut().for_each(IndicatorConfig::clamp_params);
+                configs
+            }),
+        Err(e) => {
+            println!("Indicator config load error: {}", e);
+            None
+        }
+    }
+}
+
+//차트 지표 구성 저장 (다른 차트 구성은 유지)
+pub fn save_indicators(
+    symbol: &Symbol,
+    candle_type: &CandleType,
+    configs: &[IndicatorConfig],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut saved = read_all().unwrap_or_default();
+    saved.insert(chart_key(symbol, candle_type), configs.to_vec());
+
+    let path = Path::new(uc::INDICATOR_CONFIG_PATH);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&saved)?)?;
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod candles;
+pub mod export;
+pub mod indicators;
+pub mod resample;
+pub mod tape;
@@ -0,0 +1,70 @@
+use crate::{CandleType, Candlestick};
+use std::collections::BTreeMap;
+
+/*
+기준 캔들 -> 긴 타임프레임 캔들 (OHLCV 집계)
+- 시가 : 첫 캔들 시가, 종가 : 마지막 캔들 종가
+- 고가 / 저가 : 최대 / 최소, 거래량 / 거래대금 / 체결 수 : 합계
+- 기준 캔들이 중간부터 시작하는 앞쪽 캔들은 버림 (부분 캔들)
+- 마지막 캔들은 진행 중인 캔들로 그대로 둠 (실시간 캔들과 같음)
+*/
+pub fn resample(
+    candles: &BTreeMap<u64, Candlestick>,
+    candle_type: &CandleType,
+) -> BTreeMap<u64, Candlestick> {
+    let mut result: BTreeMap<u64, Candlestick> = BTreeMap::new();
+    for (&open_time, candle) in candles {
+        result
+            .entry(candle_type.open_time(open_time))
+            .and_modify(|bar| bar.merge(candle))
+            .or_insert_with(|| candle.clone());
+    }
+
+    if let Some(&first) = candles.keys().next() {
+        let first_bucket = candle_type.open_time(first);
+        if first_bucket != first {
+            result.remove(&first_bucket);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE_MS: u64 = 60_000;
+
+    #[test]
+    fn resample_minutes() {
+        // 1분봉 3 ~ 10분 -> 5분봉 (0분 버킷은 부분 캔들이라 버림, 10분 버킷은 진행 중)
+        let candles: BTreeMap<u64, Candlestick> = (3..=10)
+            .map(|minute| {
+                let price = minute as f64;
+                let candle = Candlestick {
+                    open: price,
+                    close: price + 0.5,
+                    high: price + 1.0,
+                    low: price - 1.0,
+                    volume: 1.0,
+                    ..Default::default()
+                };
+                (minute * MINUTE_MS, candle)
+            })
+            .collect();
+
+        let result = resample(&candles, &CandleType::Minute5);
+        assert_eq!(
+            result.keys().copied().collect::<Vec<_>>(),
+            [5 * MINUTE_MS, 10 * MINUTE_MS]
+        );
+
+        let bar = &result[&(5 * MINUTE_MS)];
+        assert_eq!(
+            (bar.open, bar.close, bar.high, bar.low),
+            (5.0, 9.5, 10.0, 4.0)
+        );
+        assert_eq!(bar.volume, 5.0);
+        assert_eq!(result[&(10 * MINUTE_MS)].volume, 1.0);
+    }
+}
@@ -0,0 +1,252 @@
+use crate::api::MarketType;
+use crate::uc;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+//기록할 이벤트 종류
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TapeEvent {
+    Trade,     // 체결 (aggTrade / publicTrade / trades)
+    Depth,     // 호가
+    MarkPrice, // 마크 가격
+}
+
+impl TapeEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TapeEvent::Trade => "trade",
+            TapeEvent::Depth => "depth",
+            TapeEvent::MarkPrice => "mark",
+        }
+    }
+}
+
+/*
+원본 체결 기록기 (tape)
+- TAPE_RECORD=1 일 때만 동작
+- TAPE_STREAMS=depth,mark 로 호가 / 마크 가격 이벤트도 구독해서 기록
+- 한 줄에 하나씩 {"recv":수신시각(ms),"kind":종류,"data":원본 메세지} (JSON Lines)
+- gzip 압축, 추가 전용, 1시간 또는 일정 크기마다 새 파일
+  data/tape/{venue}_{YYYYMMDD-HHMMSS}.jsonl.gz
+*/
+pub struct TapeRecorder {
+    dir: PathBuf,
+    rotate_bytes: usize, // 압축 전 크기 기준 파일 교체
+    streams: Vec<TapeEvent>,
+    market: Option<MarketType>,
+    writer: Option<GzEncoder<File>>,
+    written: usize,   // 현재 파일에 쓴 바이트 (압축 전)
+    opened_hour: i64, // 현재 파일을 연 시각 (시간 단위)
+    pending: usize,   // flush 되지 않은 줄 수
+}
+
+impl TapeRecorder {
+    //환경 변수로 설정 (기록하지 않으면 None)
+    pub fn from_env() -> Option<Self> {
+        if !env::var("TAPE_RECORD").is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true")) {
+            return None;
+        }
+
+        let streams = env::var("TAPE_STREAMS")
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|stream| match stream.trim() {
+                "depth" => Some(TapeEvent::Depth),
+                "mark" => Some(TapeEvent::MarkPrice),
+                _ => None,
+            })
+            .collect();
+
+        Some(Self::new(PathBuf::from(uc::TAPE_DIR), streams))
+    }
+
+    pub fn new(dir: PathBuf, streams: Vec<TapeEvent>) -> Self {
+        Self {
+            dir,
+            rotate_bytes: uc::TAPE_ROTATE_BYTES,
+            streams,
+            market: None,
+            writer: None,
+            written: 0,
+            opened_hour: 0,
+            pending: 0,
+        }
+    }
+
+    //추가 구독할 이벤트 여부
+    pub fn subscribes(&self, event: TapeEvent) -> bool {
+        event == TapeEvent::Trade || self.streams.contains(&event)
+    }
+
+    //원본 메세지 한 줄 기록
+    pub fn record(&mut self, market: MarketType, event: TapeEvent, raw: &str) {
+        let now = chrono::Utc::now();
+        let hour = now.timestamp() / 3600;
+
+        if self.writer.is_none()
+            || self.market != Some(market)
+            || self.opened_hour != hour
+            || self.written >= self.rotate_bytes
+        {
+            self.rotate(market, &now);
+        }
+        let Some(writer) = self.writer.as_mut() else {
+            return;
+        };
+
+        let line = format!(
+            "{{\"recv\":{},\"kind\":\"{}\",\"data\":{}}}\n",
+            now.timestamp_millis(),
+            event.as_str(),
+            raw.trim()
+        );
+        if let Err(e) = writer.write_all(line.as_bytes()) {
+            println!("Tape write error: {}", e);
+            self.writer = None;
+            return;
+        }
+        self.written += line.len();
+
+        // 비정상 종료 시 손실을 줄이기 위해 주기적으로 flush
+        self.pending += 1;
+        if self.pending >= uc::TAPE_FLUSH_LINES {
+            let _ = writer.flush();
+            self.pending = 0;
+        }
+    }
+
+    //현재 파일을 닫고 새 파일 열기
+    fn rotate(&mut self, market: MarketType, now: &chrono::DateTime<chrono::Utc>) {
+        self.finish();
+
+        let path = self.dir.join(format!(
+            "{}_{}.jsonl.gz",
+            market.venue_key(),
+            now.format("%Y%m%d-%H%M%S")
+        ));
+        let file = std::fs::create_dir_all(&self.dir)
+            .and_then(|_| OpenOptions::new().create(true).append(true).open(&path));
+
+        match file {
+            Ok(file) => {
+                println!("Recording tape to {}", path.display());
+                self.writer = Some(GzEncoder::new(file, Compression::default()));
+            }
+            Err(e) => println!("Tape open error: {}", e),
+        }
+        self.market = Some(market);
+        self.written = 0;
+        self.opened_hour = now.timestamp() / 3600;
+        self.pending = 0;
+    }
+
+    //gzip 종료 블록까지 기록
+    fn finish(&mut self) {
+        if let Some(writer) = self.writer.take() {
+            if let Err(e) = writer.finish() {
+                println!("Tape close error: {}", e);
+            }
+        }
+    }
+}
+
+impl Drop for TapeRecorder {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::MultiGzDecoder;
+    use std::io::{BufRead, BufReader};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("futurx_tape_{}_{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    //디렉터리 안 파일별 줄 (파일 이름 순)
+    fn read_lines(dir: &PathBuf) -> Vec<(String, Vec<serde_json::Value>)> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        paths.sort();
+        paths
+            .into_iter()
+            .map(|path| {
+                let reader = BufReader::new(MultiGzDecoder::new(File::open(&path).unwrap()));
+                let lines = reader
+                    .lines()
+                    .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+                    .collect();
+                let name = path.file_name().unwrap().to_string_lossy().to_string();
+                (name, lines)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn records_json_lines() {
+        let dir = temp_dir("lines");
+        let mut recorder = TapeRecorder::new(dir.clone(), vec![TapeEvent::Depth]);
+        assert!(recorder.subscribes(TapeEvent::Trade));
+        assert!(recorder.subscribes(TapeEvent::Depth));
+        assert!(!recorder.subscribes(TapeEvent::MarkPrice));
+
+        recorder.record(
+            MarketType::Futures,
+            TapeEvent::Trade,
+            "{\"e\":\"aggTrade\"}\n",
+        );
+        recorder.record(
+            MarketType::Futures,
+            TapeEvent::Depth,
+            "{\"e\":\"depthUpdate\"}",
+        );
+        drop(recorder);
+
+        let files = read_lines(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].0.starts_with("binance_usdm_") && files[0].0.ends_with(".jsonl.gz"));
+        let lines = &files[0].1;
+        assert_eq!(lines[0]["kind"], "trade");
+        assert_eq!(lines[0]["data"]["e"], "aggTrade");
+        assert_eq!(lines[1]["kind"], "depth");
+        assert!(lines[0]["recv"].as_u64().is_some());
+    }
+
+    #[test]
+    fn rotates_on_market_and_size() {
+        // 거래소가 바뀌면 새 파일, 크기를 넘으면 새 gzip 블록 (같은 초에는 같은 파일 이름에 추가)
+        let dir = temp_dir("rotate");
+        let mut recorder = TapeRecorder::new(dir.clone(), Vec::new());
+        recorder.rotate_bytes = 1;
+        recorder.record(MarketType::Futures, TapeEvent::Trade, "{\"n\":1}");
+        let line = recorder.written;
+        recorder.record(MarketType::Futures, TapeEvent::Trade, "{\"n\":2}");
+        assert_eq!(recorder.written, line);
+        recorder.record(MarketType::BybitLinear, TapeEvent::Trade, "{\"n\":3}");
+        drop(recorder);
+
+        let files = read_lines(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let count = |venue: &str| -> usize {
+            files
+                .iter()
+                .filter(|(name, _)| name.starts_with(venue))
+                .map(|(_, lines)| lines.len())
+                .sum()
+        };
+        assert_eq!(count(MarketType::Futures.venue_key()), 2);
+        assert_eq!(count(MarketType::BybitLinear.venue_key()), 1);
+    }
+}
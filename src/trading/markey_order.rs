@@ -1,5 +1,7 @@
+use crate::api::MarketType;
 use crate::execute_trade;
-use crate::trading::TradeType;
+use crate::models::Symbol;
+use crate::trading::{contracts_for_notional, TradeType};
 use crate::uc;
 use crate::AlertType;
 use crate::Futurx;
@@ -12,16 +14,20 @@ use crate::Futurx;
 
 */
 pub fn market_buy(r: &mut Futurx) {
+    if r.selected_market == MarketType::Spot {
+        return spot_market_buy(r);
+    }
     //선택한 코인의 정보가 있는지 확인
-    if let Some(info) = r.coin_list.get(&r.selected_coin) {
+    if let Some(info) = r.coin_list.get(&r.selected_symbol) {
         //계정 정보가 있는지 확인
-        if let Some(account_info) = &r.account_info {
-            let symbol = format!("{}USDT", r.selected_coin);
+        if let Some(account_info) = r.futures_account_info() {
+            let symbol = &info.symbol.exchange_symbol;
             let price = info.price;
+            let new_quantity = order_quantity(&info.symbol, price, uc::MARKET_BUY_ORDER_PRICE);
 
             // 현재 포지션 확인
             let total_quantity = if let Some(position) =
-                account_info.positions.iter().find(|p| p.symbol == symbol)
+                account_info.positions.iter().find(|p| &p.symbol == symbol)
             {
                 let current_position = position.position_amt.parse::<f64>().unwrap_or(0.0);
                 if current_position < 0.0 {
@@ -29,21 +35,21 @@ pub fn market_buy(r: &mut Futurx) {
                     current_position.abs()
                 } else {
                     // 숏 포지션이 없다면 새로운 롱 포지션
-                    uc::MARKET_BUY_ORDER_PRICE / price
+                    new_quantity
                 }
             } else {
                 // 포지션이 없다면 새로운 롱 포지션
-                uc::MARKET_BUY_ORDER_PRICE / price
+                new_quantity
             };
 
             if total_quantity > 0.0 {
-                let selected_coin = r.selected_coin.clone();
+                let order_symbol = info.symbol.clone();
                 let alert_sender = r.alert_sender.clone();
 
                 let runtime = tokio::runtime::Handle::current();
                 runtime.spawn(async move {
                     if let Err(e) = execute_trade(
-                        selected_coin.clone(),
+                        order_symbol,
                         TradeType::Buy,
                         price,
                         total_quantity,
@@ -55,22 +61,19 @@ pub fn market_buy(r: &mut Futurx) {
                     }
                 });
 
-                let message =
-                    if account_info.positions.iter().any(|p| {
-                        p.symbol == symbol && p.position_amt.parse::<f64>().unwrap_or(0.0) < 0.0
-                    }) {
-                        format!(
-                        "Closing Short Position:\nQuantity: {:.8} {}\nEstimated Cost: {:.4} USDT",
-                        total_quantity, r.selected_coin, total_quantity * price
+                let message = if account_info.positions.iter().any(|p| {
+                    &p.symbol == symbol && p.position_amt.parse::<f64>().unwrap_or(0.0) < 0.0
+                }) {
+                    format!(
+                        "Closing Short Position:\n{}",
+                        order_summary(&info.symbol, price, total_quantity)
+                    )
+                } else {
+                    format!(
+                        "New Long Position:\n{}",
+                        order_summary(&info.symbol, price, total_quantity)
                     )
-                    } else {
-                        format!(
-                            "New Long Position:\nQuantity: {:.8} {}\nEstimated Cost: {:.4} USDT",
-                            total_quantity,
-                            r.selected_coin,
-                            total_quantity * price
-                        )
-                    };
+                };
 
                 r.add_alert(message, AlertType::Info);
             }
@@ -90,13 +93,17 @@ pub fn market_buy(r: &mut Futurx) {
 
 */
 pub fn market_sell(r: &mut Futurx) {
-    if let Some(info) = r.coin_list.get(&r.selected_coin) {
-        if let Some(account_info) = &r.account_info {
-            let symbol = format!("{}USDT", r.selected_coin);
+    if r.selected_market == MarketType::Spot {
+        return spot_market_sell(r);
+    }
+    if let Some(info) = r.coin_list.get(&r.selected_symbol) {
+        if let Some(account_info) = r.futures_account_info() {
+            let symbol = &info.symbol.exchange_symbol;
             let price = info.price;
+            let new_quantity = order_quantity(&info.symbol, price, uc::MARKET_SELL_ORDER_PRICE);
 
             let total_quantity = if let Some(position) =
-                account_info.positions.iter().find(|p| p.symbol == symbol)
+                account_info.positions.iter().find(|p| &p.symbol == symbol)
             {
                 let current_position = position.position_amt.parse::<f64>().unwrap_or(0.0);
                 if current_position > 0.0 {
@@ -104,21 +111,21 @@ pub fn market_sell(r: &mut Futurx) {
                     current_position
                 } else {
                     // 롱 포지션이 없다면 새로운 숏 포지션
-                    uc::MARKET_SELL_ORDER_PRICE / price
+                    new_quantity
                 }
             } else {
                 // 포지션이 없다면 새로운 숏 포지션
-                uc::MARKET_SELL_ORDER_PRICE / price
+                new_quantity
             };
 
             if total_quantity > 0.0 {
-                let selected_coin = r.selected_coin.clone();
+                let order_symbol = info.symbol.clone();
                 let alert_sender = r.alert_sender.clone();
 
                 let runtime = tokio::runtime::Handle::current();
                 runtime.spawn(async move {
                     if let Err(e) = execute_trade(
-                        selected_coin.clone(),
+                        order_symbol,
                         TradeType::Sell,
                         price,
                         total_quantity,
@@ -130,22 +137,19 @@ pub fn market_sell(r: &mut Futurx) {
                     }
                 });
 
-                let message =
-                    if account_info.positions.iter().any(|p| {
-                        p.symbol == symbol && p.position_amt.parse::<f64>().unwrap_or(0.0) > 0.0
-                    }) {
-                        format!(
-                        "Closing Long Position:\nQuantity: {:.8} {}\nEstimated Cost: {:.4} USDT",
-                        total_quantity, r.selected_coin, total_quantity * price
+                let message = if account_info.positions.iter().any(|p| {
+                    &p.symbol == symbol && p.position_amt.parse::<f64>().unwrap_or(0.0) > 0.0
+                }) {
+                    format!(
+                        "Closing Long Position:\n{}",
+                        order_summary(&info.symbol, price, total_quantity)
                     )
-                    } else {
-                        format!(
-                            "New Short Position:\nQuantity: {:.8} {}\nEstimated Cost: {:.4} USDT",
-                            total_quantity,
-                            r.selected_coin,
-                            total_quantity * price
-                        )
-                    };
+                } else {
+                    format!(
+                        "New Short Position:\n{}",
+                        order_summary(&info.symbol, price, total_quantity)
+                    )
+                };
 
                 r.add_alert(message, AlertType::Info);
             }
@@ -157,3 +161,157 @@ pub fn market_sell(r: &mut Futurx) {
         }
     }
 }
+
+/*
+주문 수량 계산
+- USDⓈ-M : 주문 금액 / 현재가 (코인 수량)
+- COIN-M : 주문 금액 / 계약 크기 (계약 수, 최소 1계약)
+*/
+fn order_quantity(symbol: &Symbol, price: f64, notional: f64) -> f64 {
+    if symbol.is_inverse() {
+        contracts_for_notional(notional, symbol.contract_size)
+    } else {
+        notional / price
+    }
+}
+
+/*
+주문 알림 수량 / 금액
+- USDⓈ-M : 코인 수량, 호가 자산 금액
+- COIN-M : 계약 수, 계약 금액 (USD) 과 증거금 자산 환산 값
+*/
+fn order_summary(symbol: &Symbol, price: f64, quantity: f64) -> String {
+    if symbol.is_inverse() {
+        let notional = quantity * symbol.contract_size;
+        let margin = if price > 0.0 { notional / price } else { 0.0 };
+        format!(
+            "Quantity: {} Cont. ({})\nEstimated Value: {:.2} {} (≈ {:.8} {})",
+            quantity,
+            symbol,
+            notional,
+            symbol.quote,
+            margin,
+            symbol.margin_asset()
+        )
+    } else {
+        format!(
+            "Quantity: {:.8} {}\nEstimated Cost: {:.4} {}",
+            quantity,
+            symbol,
+            quantity * price,
+            symbol.quote
+        )
+    }
+}
+
+/*
+현물 시장가 매수
+- 설정된 주문 금액만큼 매수 (호가 자산 잔고 확인)
+*/
+fn spot_market_buy(r: &mut Futurx) {
+    let Some(info) = r.coin_list.get(&r.selected_symbol) else {
+        return;
+    };
+    let Some(account_info) = &r.spot_account_info else {
+        r.add_alert(
+            "Account information cannot be registered.".to_string(),
+            AlertType::Error,
+        );
+        return;
+    };
+
+    let price = info.price;
+    let (quote_free, _) = account_info.balance(&info.symbol.quote);
+    if quote_free < uc::MARKET_BUY_ORDER_PRICE || price <= 0.0 {
+        r.add_alert(
+            format!(
+                "Insufficient {} balance: {:.2}",
+                info.symbol.quote, quote_free
+            ),
+            AlertType::Error,
+        );
+        return;
+    }
+
+    let total_quantity = uc::MARKET_BUY_ORDER_PRICE / price;
+    let order_symbol = info.symbol.clone();
+    let message = format!(
+        "Spot Buy:\nQuantity: {:.8} {}\nEstimated Cost: {:.4} {}",
+        total_quantity,
+        info.symbol.base,
+        total_quantity * price,
+        info.symbol.quote
+    );
+    let alert_sender = r.alert_sender.clone();
+
+    let runtime = tokio::runtime::Handle::current();
+    runtime.spawn(async move {
+        if let Err(e) = execute_trade(
+            order_symbol,
+            TradeType::Buy,
+            price,
+            total_quantity,
+            alert_sender,
+        )
+        .await
+        {
+            println!("현물 시장가 매수 실패: {:?}", e);
+        }
+    });
+
+    r.add_alert(message, AlertType::Info);
+}
+
+/*
+현물 시장가 매도
+- 보유 중인 코인 전량 매도 (현물은 숏 불가)
+*/
+fn spot_market_sell(r: &mut Futurx) {
+    let Some(info) = r.coin_list.get(&r.selected_symbol) else {
+        return;
+    };
+    let Some(account_info) = &r.spot_account_info else {
+        r.add_alert(
+            "Account information cannot be registered.".to_string(),
+            AlertType::Error,
+        );
+        return;
+    };
+
+    let price = info.price;
+    let (total_quantity, _) = account_info.balance(&info.symbol.base);
+    if total_quantity <= 0.0 {
+        r.add_alert(
+            format!("No {} balance to sell", info.symbol.base),
+            AlertType::Error,
+        );
+        return;
+    }
+
+    let order_symbol = info.symbol.clone();
+    let message = format!(
+        "Spot Sell:\nQuantity: {:.8} {}\nEstimated Value: {:.4} {}",
+        total_quantity,
+        info.symbol.base,
+        total_quantity * price,
+        info.symbol.quote
+    );
+    let alert_sender = r.alert_sender.clone();
+
+    let runtime = tokio::runtime::Handle::current();
+    runtime.spawn(async move {
+        if let Err(e) = execute_trade(
+            order_symbol,
+            TradeType::Sell,
+            price,
+            total_quantity,
+            alert_sender,
+        )
+        .await
+        {
+            println!("현물 시장가 매도 실패: {:?}", e);
+        }
+    });
+
+    r.add_alert(message, AlertType::Info);
+}
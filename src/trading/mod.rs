@@ -1,7 +1,47 @@
 pub mod markey_order;
 
+use crate::indicators::atr;
+use crate::Candlestick;
+use std::collections::BTreeMap;
+
 #[derive(Debug, Clone, Copy)]
 pub enum TradeType {
     Buy,
     Sell,
-}
\ No newline at end of file
+}
+/*
+인버스(COIN-M) 계약 미실현 손익 - 기초 자산 단위
+- contracts: 포지션 계약 수 (롱 +, 숏 -)
+- contract_size: 계약 1개당 USD 가치
+*/
+pub fn inverse_pnl(contracts: f64, contract_size: f64, entry_price: f64, mark_price: f64) -> f64 {
+    if entry_price <= 0.0 || mark_price <= 0.0 {
+        return 0.0;
+    }
+    contracts * contract_size * (1.0 / entry_price - 1.0 / mark_price)
+}
+
+//USD 주문 금액 -> 계약 수 (최소 1계약)
+pub fn contracts_for_notional(notional: f64, contract_size: f64) -> f64 {
+    if contract_size <= 0.0 {
+        return 0.0;
+    }
+    (notional / contract_size).floor().max(1.0)
+}
+
+//ATR 기반 손절 거리 (가격 단위, ATR(period) * multiplier, 캔들이 period 개 미만이면 None)
+pub fn atr_stop_distance(
+    candles: &BTreeMap<u64, Candlestick>,
+    period: usize,
+    multiplier: f64,
+) -> Option<f64> {
+    atr::latest(candles, period).map(|atr| atr * multiplier)
+}
+
+//손절 가격 (매수는 진입가 아래, 매도는 위)
+pub fn stop_price(entry_price: f64, trade_type: TradeType, distance: f64) -> f64 {
+    match trade_type {
+        TradeType::Buy => (entry_price - distance).max(0.0),
+        TradeType::Sell => entry_price + distance,
+    }
+}
@@ -1,6 +1,11 @@
+use crate::indicators::{moving_average::MaType, Source, PALETTE, REGISTRY};
+use crate::ui::transform::ChartStyle;
 use crate::Futurx;
 use crate::Message;
-use iced::widget::{checkbox, Column, Container, Row};
+use iced::widget::{
+    button, checkbox, pick_list, text, text_input, tooltip, Column, Container, Row,
+};
+use iced::Alignment;
 
 //지표
 pub fn ma_controls(r: &Futurx) -> Container<'static, Message> {
@@ -8,16 +13,110 @@ pub fn ma_controls(r: &Futurx) -> Container<'static, Message> {
         Column::new()
             .spacing(5)
             .push(
-                Row::new()
-                    .spacing(10)
-                    .push(checkbox("MA5", r.show_ma5).on_toggle(|_| Message::ToggleMA5))
-                    .push(checkbox("MA10", r.show_ma10).on_toggle(|_| Message::ToggleMA10)),
+                // 구성된 지표마다 토글 / 종류 / 파라미터 / 입력 값 / 색 / 삭제
+                Row::with_children(r.indicators.iter().enumerate().map(|(i, config)| {
+                    let params = config
+                        .build()
+                        .map(|indicator| indicator.params())
+                        .unwrap_or_default();
+                    let param_inputs = params.into_iter().enumerate().map(|(p, param)| {
+                        let value = match &r.param_edit {
+                            Some((index, edit, value)) if *index == i && *edit == p => {
+                                value.clone()
+                            }
+                            _ => param.value.to_string(),
+                        };
+                        tooltip(
+                            text_input(param.name, &value)
+                                .on_input(move |value| Message::IndicatorParamChanged(i, p, value))
+                                .on_submit(Message::ApplyIndicatorParam)
+                                .size(12)
+                                .width(45),
+                            text(param.name).size(12),
+                            tooltip::Position::Bottom,
+                        )
+                        .into()
+                    });
+                    let color = PALETTE
+                        .iter()
+                        .find(|palette| config.color == Some(palette.color))
+                        .copied();
+
+                    Row::new()
+                        .spacing(4)
+                        .align_y(Alignment::Center)
+                        .push(tooltip(
+                            checkbox(config.name(), config.visible)
+                                .on_toggle(move |_| Message::ToggleIndicator(i)),
+                            text(config.description()).size(12),
+                            tooltip::Position::Bottom,
+                        ))
+                        .push_maybe(MaType::from_key(&config.key).map(|ma_type| {
+                            pick_list(MaType::ALL.to_vec(), Some(ma_type), move |ma_type| {
+                                Message::SelectMaType(i, ma_type)
+                            })
+                            .text_size(12)
+                        }))
+                        .push(Row::with_children(param_inputs).spacing(2))
+                        .push(
+                            pick_list(Source::ALL.to_vec(), Some(config.source), move |source| {
+                                Message::SelectIndicatorSource(i, source)
+                            })
+                            .text_size(12),
+                        )
+                        .push(
+                            pick_list(PALETTE.to_vec(), color, move |palette| {
+                                Message::SelectIndicatorColor(i, palette)
+                            })
+                            .placeholder("Color")
+                            .text_size(12),
+                        )
+                        .push(
+                            button(text("x").size(12))
+                                .on_press(Message::RemoveIndicator(i))
+                                .padding([2, 6]),
+                        )
+                        .into()
+                }))
+                .spacing(10)
+                .wrap(),
             )
             .push(
                 Row::new()
                     .spacing(10)
-                    .push(checkbox("MA20", r.show_ma20).on_toggle(|_| Message::ToggleMA20))
-                    .push(checkbox("MA200", r.show_ma200).on_toggle(|_| Message::ToggleMA200)),
+                    .push(
+                        pick_list(
+                            REGISTRY.iter().map(|spec| spec.key).collect::<Vec<_>>(),
+                            None::<&'static str>,
+                            Message::AddIndicator,
+                        )
+                        .placeholder("Add indicator")
+                        .text_size(12),
+                    )
+                    .push(
+                        button(
+                            text(if r.anchor_pick {
+                                "Click a candle..."
+                            } else {
+                                "Anchor VWAP"
+                            })
+                            .size(12),
+                        )
+                        .on_press(Message::ToggleAnchorPick),
+                    )
+                    .push(pick_list(
+                        ChartStyle::ALL.to_vec(),
+                        Some(r.chart_style),
+                        Message::SelectChartStyle,
+                    ))
+                    .push(
+                        checkbox("Indicators on transformed", r.transformed_indicators)
+                            .on_toggle(|_| Message::ToggleTransformedIndicators),
+                    )
+                    .push(
+                        checkbox("Footprint", r.footprint_enabled)
+                            .on_toggle(|_| Message::ToggleFootprint),
+                    ),
             ),
     )
     .padding(10);
@@ -1,5 +1,8 @@
-use crate::utils::constant as uc;
-use crate::{CandleType, Candlestick, Chart, ChartState};
+use crate::indicators::{IndicatorCache, IndicatorSeries, LineStyle, RenderHint};
+use crate::ui::transform::ChartStyle;
+use crate::ui::ChartOptions;
+use crate::utils::format_price;
+use crate::{CandleType, Candlestick, Chart, ChartState, Message};
 use iced::{
     mouse,
     widget::{
@@ -11,136 +14,67 @@ use iced::{
     },
     Color, Pixels, Point, Rectangle, Size,
 };
-use std::collections::{BTreeMap, VecDeque};
-
-pub fn calculate_rsi(
-    candlesticks: &BTreeMap<u64, Candlestick>,
-    period: usize,
-) -> BTreeMap<u64, f32> {
-    let mut rsi_values = BTreeMap::new();
-    if candlesticks.len() < period + 1 {
-        return rsi_values;
-    }
-
-    let mut gains = Vec::new();
-    let mut losses = Vec::new();
-    let mut prev_close = None;
-    let mut timestamps = Vec::new();
-
-    // 가격 변화 계산
-    for (timestamp, candle) in candlesticks.iter() {
-        if let Some(prev) = prev_close {
-            let change = candle.close - prev;
-            timestamps.push(*timestamp);
-            if change >= 0.0 {
-                gains.push(change);
-                losses.push(0.0);
-            } else {
-                gains.push(0.0);
-                losses.push(-change);
-            }
-        }
-        prev_close = Some(candle.close);
-    }
-
-    // RSI 계산
-    for i in period..timestamps.len() {
-        let avg_gain: f32 = gains[i - period..i].iter().sum::<f32>() / period as f32;
-        let avg_loss: f32 = losses[i - period..i].iter().sum::<f32>() / period as f32;
-
-        let rs = if avg_loss == 0.0 {
-            100.0
-        } else {
-            avg_gain / avg_loss
-        };
-
-        let rsi = 100.0 - (100.0 / (1.0 + rs));
-        rsi_values.insert(timestamps[i], rsi);
-    }
-
-    rsi_values
-}
-
-pub fn calculate_moving_average(
-    candlesticks: &BTreeMap<u64, Candlestick>,
-    period: usize,
-) -> BTreeMap<u64, f32> {
-    let mut result = BTreeMap::new();
-    if period == 0 || candlesticks.is_empty() {
-        return result;
-    }
-
-    let data: Vec<(&u64, &Candlestick)> = candlesticks.iter().collect();
-
-    // 모든 캔들에 대해 이동평균 계산
-    for i in 0..data.len() {
-        if i >= period - 1 {
-            let sum: f32 = data[i + 1 - period..=i]
-                .iter()
-                .map(|(_, candle)| candle.close)
-                .sum();
-            let avg = sum / period as f32;
-            result.insert(*data[i].0, avg);
-        }
-    }
+use std::collections::{BTreeMap, HashSet};
 
-    result
-}
-
-impl Chart {
+impl<'a> Chart<'a> {
+    //candlesticks 는 표시 방식에 맞게 변환된 캔들 (Heikin-Ashi 등은 TransformCache)
     pub fn new(
-        candlesticks: BTreeMap<u64, Candlestick>,
-        candle_type: CandleType,
-        show_ma5: bool,
-        show_ma10: bool,
-        show_ma20: bool,
-        show_ma200: bool,
-        scored_signals_enabled: bool,
-        buy_scored_signals: BTreeMap<u64, SignalScoring>,
-        sell_scored_signals: BTreeMap<u64, SignalScoring>,
+        candlesticks: &'a BTreeMap<u64, Candlestick>,
+        indicators: &IndicatorCache,
+        buy_scored_signals: &'a BTreeMap<u64, SignalScoring>,
+        sell_scored_signals: &'a BTreeMap<u64, SignalScoring>,
+        options: ChartOptions,
     ) -> Self {
-        let ma5_values = calculate_moving_average(&candlesticks, 5);
-        let ma10_values = calculate_moving_average(&candlesticks, 10);
-        let ma20_values = calculate_moving_average(&candlesticks, 20);
-        let ma200_values = calculate_moving_average(&candlesticks, 200);
-        let rsi_values = calculate_rsi(&candlesticks, 14);
+        let ChartOptions {
+            candle_type,
+            style,
+            price_precision,
+            scored_signals_enabled,
+        } = options;
+        let max_data_points = 1000; // 저장할 최대 데이터 수
+
+        // 최근 max_data_points 개만 (복사 없이 참조)
+        let skip = candlesticks.len().saturating_sub(max_data_points);
+        let recent_candles: Vec<(u64, &Candlestick)> = candlesticks
+            .iter()
+            .skip(skip)
+            .map(|(&timestamp, candle)| (timestamp, candle))
+            .collect();
 
-        let price_range = if candlesticks.is_empty() {
+        // 지표는 캐시에서 보이는 구간만
+        let from = recent_candles
+            .first()
+            .map(|(timestamp, _)| *timestamp)
+            .unwrap_or_default();
+        let indicators: Vec<IndicatorSeries> = indicators
+            .series()
+            .map(|series| series.window(from))
+            .collect();
+
+        let price_range = if recent_candles.is_empty() {
             Some((0.0, 100.0))
         } else {
-            let (min, max) = candlesticks.values().fold((f32::MAX, f32::MIN), |acc, c| {
-                (acc.0.min(c.low), acc.1.max(c.high))
-            });
-
-            let ma_min = [&ma5_values, &ma10_values, &ma20_values, &ma200_values]
+            let (min, max) = recent_candles
                 .iter()
-                .filter(|ma| !ma.is_empty())
-                .flat_map(|ma| ma.values())
-                .fold(min, |acc, &x| acc.min(x));
+                .fold((f64::MAX, f64::MIN), |acc, (_, c)| {
+                    (acc.0.min(c.low), acc.1.max(c.high))
+                });
 
-            let ma_max = [&ma5_values, &ma10_values, &ma20_values, &ma200_values]
+            // 가격 차트 위 지표도 범위에 포함
+            let (ma_min, ma_max) = indicators
                 .iter()
-                .filter(|ma| !ma.is_empty())
-                .flat_map(|ma| ma.values())
-                .fold(max, |acc, &x| acc.max(x));
+                .filter(|series| series.render == RenderHint::Overlay)
+                .flat_map(|series| series.lines.iter())
+                .filter(|line| line.style != LineStyle::Hidden)
+                .flat_map(|line| line.values.values())
+                .fold((min, max), |acc, &x| (acc.0.min(x), acc.1.max(x)));
 
             let margin = (ma_max - ma_min) * 0.1;
             Some((ma_min - margin, ma_max + margin))
         };
-        let max_data_points = 1000; // 저장할 최대 데이터 수
-        let mut candlestick_deque: VecDeque<(u64, Candlestick)> =
-            VecDeque::with_capacity(max_data_points);
-
-        // 정렬된 데이터를 VecDeque에 추가
-        for (timestamp, candle) in candlesticks.into_iter() {
-            if candlestick_deque.len() >= max_data_points {
-                candlestick_deque.pop_front(); // 가장 오래된 데이터 제거
-            }
-            candlestick_deque.push_back((timestamp, candle));
-        }
 
         Self {
-            candlesticks: candlestick_deque,
+            candlesticks: recent_candles,
             max_data_points,
             state: ChartState {
                 auto_scroll: true,
@@ -148,23 +82,42 @@ impl Chart {
             },
             price_range,
             candle_type,
-            show_ma5,
-            show_ma10,
-            show_ma20,
-            show_ma200,
-            ma5_values,
-            ma10_values,
-            ma20_values,
-            ma200_values,
-            rsi_values,
-            show_rsi: true,
+            style,
+            price_precision,
+            indicators,
+            footprints: None,
             scored_signals_enabled,
             buy_scored_signals,
             sell_scored_signals,
         }
     }
+
+    //화면 x 위치 (차트 기준) 의 캔들 시각
+    fn candle_at(&self, state: &ChartState, bounds: Rectangle, x: f32) -> Option<u64> {
+        let (scroll_offset, first_x) = candle_layout(state, bounds);
+        let index = ((x - first_x) / 10.0).floor();
+        if index < 0.0 {
+            return None;
+        }
+        self.candlesticks
+            .iter()
+            .skip(scroll_offset)
+            .nth(index as usize)
+            .map(|(ts, _)| *ts)
+    }
+}
+
+//캔들 배치 (건너뛸 캔들 수, 첫 캔들 x) - draw 의 여백 / 캔들 폭 (50, 20, 10) 과 같아야 함
+fn candle_layout(state: &ChartState, bounds: Rectangle) -> (usize, f32) {
+    let (left_margin, right_margin, base_candle_width) = (50.0, 20.0, 10.0);
+    let candles_per_screen = 1000;
+    let available_width = bounds.width - left_margin - right_margin;
+    let initial_offset = available_width * 0.95 - candles_per_screen as f32 * base_candle_width;
+    let scroll_offset = (-state.offset / base_candle_width) as usize;
+    (scroll_offset, left_margin + initial_offset + state.offset)
 }
-impl<Message> Program<Message> for Chart {
+
+impl Program<Message> for Chart<'_> {
     type State = ChartState;
 
     fn update(
@@ -190,8 +143,17 @@ impl<Message> Program<Message> for Chart {
                     (event::Status::Captured, None)
                 }
                 mouse::Event::ButtonReleased(mouse::Button::Left) => {
+                    // 거의 움직이지 않았으면 클릭 (앵커 VWAP 시작 캔들 선택 등)
+                    let clicked = state.dragging
+                        && (cursor_position.x - state.drag_start.x).abs() < 3.0
+                        && (cursor_position.y - state.drag_start.y).abs() < 3.0;
                     state.dragging = false;
-                    (event::Status::Captured, None)
+                    let message = cursor
+                        .position_in(bounds)
+                        .filter(|_| clicked)
+                        .and_then(|position| self.candle_at(state, bounds, position.x))
+                        .map(Message::ChartClicked);
+                    (event::Status::Captured, message)
                 }
                 mouse::Event::CursorMoved { .. } => {
                     if state.dragging {
@@ -238,8 +200,7 @@ impl<Message> Program<Message> for Chart {
 
         // 차트 영역 설정
         let price_chart_height = bounds.height * 0.5;
-        let volume_height = 100.0;
-        let rsi_height = 80.0;
+        let volume_height: f32 = 100.0;
         let charts_gap = 20.0;
         let margin = 20.0;
 
@@ -251,7 +212,6 @@ impl<Message> Program<Message> for Chart {
         let volume_area_start = price_area_end + charts_gap;
         let volume_area_end = volume_area_start + volume_area_height;
         let rsi_area_start = volume_area_end + charts_gap;
-        let rsi_area_end = bounds.height - bottom_margin;
 
         // 배경 그리기
         frame.fill_rectangle(
@@ -264,7 +224,7 @@ impl<Message> Program<Message> for Chart {
         let (mut min_price, mut max_price) = self
             .candlesticks
             .iter()
-            .fold((f32::MAX, f32::MIN), |acc, (_, c)| {
+            .fold((f64::MAX, f64::MIN), |acc, (_, c)| {
                 (acc.0.min(c.low), acc.1.max(c.high))
             });
 
@@ -278,41 +238,26 @@ impl<Message> Program<Message> for Chart {
             .candlesticks
             .iter()
             .map(|(_, c)| c.volume)
-            .fold(0.0, f32::max);
+            .fold(0.0, f64::max);
 
         // 캔들스틱 크기 계산
-        let available_width = bounds.width - left_margin - right_margin;
         let candles_per_screen = 1000;
-        let base_candle_width = match self.candle_type {
-            CandleType::Minute1 => 10.0,
-            CandleType::Minute3 => 10.0,
-            CandleType::Day => 10.0,
-        };
-        let target_position = available_width * 0.95;
-        let total_chart_width = candles_per_screen as f32 * base_candle_width;
-        let initial_offset = target_position - total_chart_width;
+        let base_candle_width = 10.0;
 
         let body_width = base_candle_width * 0.8;
 
         // 스케일링 계산
-        let price_diff = (max_price - min_price).max(f32::EPSILON);
-        let y_scale = (price_chart_height / price_diff).min(1e6);
-        let volume_scale = (volume_height / max_volume).min(1e6);
-        let price_format = |price: f32| {
-            if price < 0.0001 {
-                format!("{:.8}", price) // 매우 작은 가격
-            } else if price < 0.01 {
-                format!("{:.6}", price) // 작은 가격
-            } else if price < 1.0 {
-                format!("{:.4}", price) // 중간 가격
-            } else {
-                format!("{:.2}", price) // 큰 가격
-            }
-        };
+        // 가격 / 거래량은 f64, 화면 좌표로 바꿀 때만 f32
+        let price_diff = (max_price - min_price).max(f64::EPSILON);
+        let y_scale = (price_chart_height as f64 / price_diff).min(1e9);
+        let volume_scale = (volume_height as f64 / max_volume).min(1e9);
+        let price_y = |price: f64| top_margin + ((max_price - price) * y_scale) as f32;
+        // 가격 표시 자릿수는 심볼의 tick size 기준
+        let price_format = |price: f64| format_price(price, self.price_precision);
         // 가격 차트 그리드 라인
         for i in 0..=10 {
             let y = top_margin + (price_chart_height * (i as f32 / 10.0));
-            let price = max_price - (price_diff * (i as f32 / 10.0));
+            let price = max_price - (price_diff * (i as f64 / 10.0));
 
             frame.stroke(
                 &canvas::Path::new(|p| {
@@ -334,7 +279,7 @@ impl<Message> Program<Message> for Chart {
         }
 
         // 현재 스크롤 위치 계산
-        let scroll_offset = (-state.offset / base_candle_width) as usize;
+        let (scroll_offset, first_x) = candle_layout(state, bounds);
 
         // visible_candlesticks 생성
         let visible_candlesticks: Vec<(u64, &Candlestick)> = self
@@ -342,146 +287,162 @@ impl<Message> Program<Message> for Chart {
             .iter()
             .skip(scroll_offset)
             .take(candles_per_screen)
-            .map(|(ts, candle)| (*ts, candle))
+            .copied()
             .collect();
         // visible_candlesticks 그리기 이후에 다음 코드 추가
 
-        // 이동평균선 그리기
-        if self.show_ma5 {
-            let ma_points: Vec<Point> = visible_candlesticks
-                .iter()
-                .enumerate()
-                .filter_map(|(i, (ts, _))| {
-                    self.ma5_values.get(ts).map(|&ma| {
-                        Point::new(
-                            left_margin
-                                + (i as f32 * base_candle_width)
-                                + initial_offset
-                                + state.offset,
-                            top_margin + ((max_price - ma) * y_scale),
-                        )
-                    })
-                })
-                .collect();
-
-            if ma_points.len() >= 2 {
+        let candle_x = |i: usize| first_x + i as f32 * base_candle_width;
+        let stroke_line = |frame: &mut canvas::Frame, points: &[Point], color: Color| {
+            if points.len() >= 2 {
                 frame.stroke(
                     &canvas::Path::new(|p| {
-                        p.move_to(ma_points[0]);
-                        for point in ma_points.iter().skip(1) {
+                        p.move_to(points[0]);
+                        for point in points.iter().skip(1) {
                             p.line_to(*point);
                         }
                     }),
-                    canvas::Stroke::default()
-                        .with_color(uc::ORNAGE) // 주황색
-                        .with_width(1.0),
+                    canvas::Stroke::default().with_color(color).with_width(1.0),
                 );
             }
-        }
-
-        if self.show_ma10 {
-            let ma_points: Vec<Point> = visible_candlesticks
-                .iter()
-                .enumerate()
-                .filter_map(|(i, (ts, _))| {
-                    self.ma10_values.get(ts).map(|&ma| {
-                        Point::new(
-                            left_margin
-                                + (i as f32 * base_candle_width)
-                                + initial_offset
-                                + state.offset,
-                            top_margin + ((max_price - ma) * y_scale),
-                        )
-                    })
-                })
-                .collect();
+        };
 
-            if ma_points.len() >= 2 {
-                frame.stroke(
-                    &canvas::Path::new(|p| {
-                        p.move_to(ma_points[0]);
-                        for point in ma_points.iter().skip(1) {
-                            p.line_to(*point);
-                        }
-                    }),
-                    canvas::Stroke::default()
-                        .with_color(uc::YELLOW) // 노란색
-                        .with_width(1.0),
-                );
+        // 가격 차트 위 지표 (이동평균 등)
+        for series in self
+            .indicators
+            .iter()
+            .filter(|series| series.render == RenderHint::Overlay)
+        {
+            // 밴드 사이 채우기 (위 선을 따라가고 아래 선을 거꾸로)
+            if let Some((upper, lower)) = series.fill {
+                if let (Some(upper), Some(lower)) =
+                    (series.lines.get(upper), series.lines.get(lower))
+                {
+                    let band: Vec<(Point, Point)> = visible_candlesticks
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(i, (ts, _))| {
+                            let x = candle_x(i);
+                            let high = upper.values.get(ts)?;
+                            let low = lower.values.get(ts)?;
+                            Some((Point::new(x, price_y(*high)), Point::new(x, price_y(*low))))
+                        })
+                        .collect();
+                    if band.len() > 1 {
+                        let path = canvas::Path::new(|p| {
+                            p.move_to(band[0].0);
+                            for (high, _) in &band[1..] {
+                                p.line_to(*high);
+                            }
+                            for (_, low) in band.iter().rev() {
+                                p.line_to(*low);
+                            }
+                            p.close();
+                        });
+                        frame.fill(
+                            &path,
+                            Color {
+                                a: 0.1,
+                                ..upper.color
+                            },
+                        );
+                    }
+                }
             }
-        }
 
-        if self.show_ma20 {
-            let ma_points: Vec<Point> = visible_candlesticks
+            for line in series
+                .lines
                 .iter()
-                .enumerate()
-                .filter_map(|(i, (ts, _))| {
-                    self.ma20_values.get(ts).map(|&ma| {
-                        Point::new(
-                            left_margin
-                                + (i as f32 * base_candle_width)
-                                + initial_offset
-                                + state.offset,
-                            top_margin + ((max_price - ma) * y_scale),
-                        )
+                .filter(|line| line.style != LineStyle::Hidden)
+            {
+                let points: Vec<Point> = visible_candlesticks
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, (ts, _))| {
+                        line.values
+                            .get(ts)
+                            .map(|&value| Point::new(candle_x(i), price_y(value)))
                     })
-                })
-                .collect();
-
-            if ma_points.len() >= 2 {
-                frame.stroke(
-                    &canvas::Path::new(|p| {
-                        p.move_to(ma_points[0]);
-                        for point in ma_points.iter().skip(1) {
-                            p.line_to(*point);
-                        }
-                    }),
-                    canvas::Stroke::default()
-                        .with_color(uc::DAKR_RED) // 빨간색
-                        .with_width(1.0),
-                );
+                    .collect();
+                stroke_line(&mut frame, &points, line.color);
             }
         }
 
-        if self.show_ma200 {
-            let ma_points: Vec<Point> = visible_candlesticks
+        // 하단 지표 영역 (RSI 등) - 지표 수만큼 나눔
+        let sub_panes: Vec<&IndicatorSeries> = self
+            .indicators
+            .iter()
+            .filter(|series| series.render != RenderHint::Overlay)
+            .collect();
+        let pane_height = rsi_area_height / sub_panes.len().max(1) as f32;
+        for (pane, series) in sub_panes.iter().enumerate() {
+            let RenderHint::SubPane {
+                range,
+                levels,
+                band,
+            } = &series.render
+            else {
+                continue;
+            };
+            let pane_start = rsi_area_start + pane as f32 * pane_height;
+            let pane_end = pane_start + pane_height
+                - if pane + 1 < sub_panes.len() {
+                    charts_gap
+                } else {
+                    0.0
+                };
+
+            // 고정 범위가 없으면 보이는 값 기준 (히스토그램이 있으면 0 포함)
+            let has_histogram = series
+                .lines
                 .iter()
-                .enumerate()
-                .filter_map(|(i, (ts, _))| {
-                    self.ma200_values.get(ts).map(|&ma| {
-                        Point::new(
-                            left_margin
-                                + (i as f32 * base_candle_width)
-                                + initial_offset
-                                + state.offset,
-                            top_margin + ((max_price - ma) * y_scale),
-                        )
+                .any(|line| line.style == LineStyle::Histogram);
+            let (low, high) = range.unwrap_or_else(|| {
+                let (low, high) = visible_candlesticks
+                    .iter()
+                    .flat_map(|(ts, _)| {
+                        series
+                            .lines
+                            .iter()
+                            .filter(|line| line.style != LineStyle::Hidden)
+                            .filter_map(|line| line.values.get(ts))
                     })
-                })
-                .collect();
-
-            if ma_points.len() >= 2 {
-                frame.stroke(
-                    &canvas::Path::new(|p| {
-                        p.move_to(ma_points[0]);
-                        for point in ma_points.iter().skip(1) {
-                            p.line_to(*point);
-                        }
-                    }),
-                    canvas::Stroke::default()
-                        .with_color(Color::from_rgb(0.0, 0.0, 1.0)) // 파란색
-                        .with_width(1.0),
+                    .fold((f64::MAX, f64::MIN), |acc, &value| {
+                        (acc.0.min(value), acc.1.max(value))
+                    });
+                if has_histogram && low <= high {
+                    (low.min(0.0), high.max(0.0))
+                } else {
+                    (low, high)
+                }
+            });
+            if low > high {
+                continue;
+            }
+            let span = (high - low).max(f64::EPSILON);
+            let value_y =
+                |value: f64| pane_end - ((value - low) / span) as f32 * (pane_end - pane_start);
+
+            // 과매수 ~ 과매도 구간 배경
+            if let Some((band_low, band_high)) = band {
+                let top = value_y(*band_high);
+                frame.fill_rectangle(
+                    Point::new(left_margin, top),
+                    Size::new(
+                        bounds.width - left_margin - right_margin,
+                        value_y(*band_low) - top,
+                    ),
+                    Color::from_rgba(0.5, 0.3, 0.8, 0.1),
                 );
             }
-        }
-
-        // RSI 그리기
-        if self.show_rsi {
-            // RSI 영역 그리드 라인
-            for i in 0..=4 {
-                let y = rsi_area_start + (rsi_area_height * (i as f32 / 4.0));
-                let rsi_value = 100.0 - (100.0 * (i as f32 / 4.0));
 
+            // 기준선
+            let grid: Vec<f64> = if levels.is_empty() {
+                (0..=4).map(|i| high - span * (i as f64 / 4.0)).collect()
+            } else {
+                levels.clone()
+            };
+            for level in grid {
+                let y = value_y(level);
                 frame.stroke(
                     &canvas::Path::new(|p| {
                         p.move_to(Point::new(left_margin, y));
@@ -492,8 +453,13 @@ impl<Message> Program<Message> for Chart {
                         .with_width(1.0),
                 );
 
+                let label = if range.is_some() {
+                    format!("{} {:.0}", series.name, level)
+                } else {
+                    format!("{} {:.2}", series.name, level)
+                };
                 frame.fill_text(canvas::Text {
-                    content: format!("RSI {:.0}", rsi_value),
+                    content: label,
                     position: Point::new(5.0, y - 5.0),
                     color: Color::from_rgb(0.7, 0.7, 0.7),
                     size: Pixels(10.0),
@@ -501,73 +467,171 @@ impl<Message> Program<Message> for Chart {
                 });
             }
 
-            // RSI 선 그리기
-            let rsi_points: Vec<Point> = visible_candlesticks
+            for line in series
+                .lines
                 .iter()
-                .enumerate()
-                .filter_map(|(i, (ts, _))| {
-                    self.rsi_values.get(ts).map(|&rsi| {
-                        Point::new(
-                            left_margin
-                                + (i as f32 * base_candle_width)
-                                + initial_offset
-                                + state.offset,
-                            rsi_area_start + (rsi_area_height * (1.0 - rsi / 100.0)),
-                        )
-                    })
-                })
-                .collect();
+                .filter(|line| line.style != LineStyle::Hidden)
+            {
+                if line.style == LineStyle::Histogram {
+                    // 0 기준 막대
+                    let zero_y = value_y(0.0);
+                    for (i, (ts, _)) in visible_candlesticks.iter().enumerate() {
+                        let Some(&value) = line.values.get(ts) else {
+                            continue;
+                        };
+                        let color = if value >= 0.0 {
+                            line.color
+                        } else {
+                            Color::from_rgba(0.8, 0.2, 0.2, 0.6)
+                        };
+                        frame.fill_rectangle(
+                            Point::new(candle_x(i), zero_y),
+                            Size::new(body_width, value_y(value) - zero_y),
+                            color,
+                        );
+                    }
+                    continue;
+                }
 
-            if rsi_points.len() >= 2 {
-                frame.stroke(
-                    &canvas::Path::new(|p| {
-                        p.move_to(rsi_points[0]);
-                        for point in rsi_points.iter().skip(1) {
-                            p.line_to(*point);
-                        }
-                    }),
-                    canvas::Stroke::default()
-                        .with_color(Color::from_rgb(0.0, 0.8, 0.8)) // 청록색
-                        .with_width(1.0),
+                let points: Vec<Point> = visible_candlesticks
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, (ts, _))| {
+                        line.values
+                            .get(ts)
+                            .map(|&value| Point::new(candle_x(i), value_y(value)))
+                    })
+                    .collect();
+                stroke_line(&mut frame, &points, line.color);
+            }
+        }
+        // 풋프린트 - 매수 우세 초록 / 매도 우세 빨강, 거래량이 클수록 진하게
+        for (i, (ts, _)) in visible_candlesticks.iter().enumerate() {
+            let Some(footprint) = self.footprints.and_then(|footprints| footprints.get(ts)) else {
+                continue;
+            };
+            let max_volume = footprint.max_volume().max(f64::EPSILON);
+            let x = candle_x(i);
+            let last = i + 1 == visible_candlesticks.len();
+            for (&level, &(sell, buy)) in &footprint.levels {
+                let top = price_y(footprint.price(level) + footprint.row);
+                let bottom = price_y(footprint.price(level));
+                let alpha = 0.15 + 0.6 * ((sell + buy) / max_volume) as f32;
+                let color = if buy >= sell {
+                    Color::from_rgba(0.0, 0.8, 0.4, alpha)
+                } else {
+                    Color::from_rgba(0.8, 0.2, 0.2, alpha)
+                };
+                frame.fill_rectangle(
+                    Point::new(x - 1.0, top),
+                    Size::new(base_candle_width, (bottom - top).max(1.0)),
+                    color,
                 );
+
+                // 마지막 캔들은 오른쪽에 매도 x 매수 거래량 (칸이 충분히 클 때만)
+                if last && bottom - top >= 9.0 {
+                    frame.fill_text(canvas::Text {
+                        content: format!("{:.2} x {:.2}", sell, buy),
+                        position: Point::new(x + base_candle_width + 2.0, top),
+                        color: Color::from_rgb(0.8, 0.8, 0.8),
+                        size: Pixels(9.0),
+                        ..canvas::Text::default()
+                    });
+                }
             }
         }
+
         // 캔들스틱과 거래량 바 그리기
         for (i, (ts, candlestick)) in visible_candlesticks.iter().enumerate() {
-            let x = left_margin + (i as f32 * base_candle_width) + initial_offset + state.offset;
+            let x = candle_x(i);
 
-            let color = if candlestick.close >= candlestick.open {
+            // 속 빈 캔들은 전 캔들 종가 대비로 색 결정
+            let rising = match self.style {
+                ChartStyle::Hollow if i > 0 => {
+                    candlestick.close >= visible_candlesticks[i - 1].1.close
+                }
+                _ => candlestick.close >= candlestick.open,
+            };
+            let color = if rising {
                 Color::from_rgb(0.8, 0.0, 0.0)
             } else {
                 Color::from_rgb(0.0, 0.0, 0.8)
             };
 
-            let open_y = top_margin + ((max_price - candlestick.open) * y_scale);
-            let close_y = top_margin + ((max_price - candlestick.close) * y_scale);
-            let high_y = top_margin + ((max_price - candlestick.high) * y_scale);
-            let low_y = top_margin + ((max_price - candlestick.low) * y_scale);
-
-            // 심지
+            let open_y = price_y(candlestick.open);
+            let close_y = price_y(candlestick.close);
+            let high_y = price_y(candlestick.high);
+            let low_y = price_y(candlestick.low);
             let center_x = x + (body_width / 2.0);
-            frame.stroke(
-                &canvas::Path::new(|builder| {
-                    builder.move_to(Point::new(center_x, high_y));
-                    builder.line_to(Point::new(center_x, low_y));
-                }),
-                canvas::Stroke::default().with_color(color).with_width(1.0),
-            );
-
-            // 캔들 몸통
-            let body_height = (close_y - open_y).abs().max(1.0);
-            let body_y = close_y.min(open_y);
-            frame.fill_rectangle(
-                Point::new(x, body_y),
-                Size::new(body_width, body_height),
-                color,
-            );
+            let stroke = canvas::Stroke::default().with_color(color).with_width(1.0);
+
+            match self.style {
+                // 종가 선
+                ChartStyle::Line => {
+                    if i > 0 {
+                        let prev_close = visible_candlesticks[i - 1].1.close;
+                        let prev_y = price_y(prev_close);
+                        frame.stroke(
+                            &canvas::Path::line(
+                                Point::new(center_x - base_candle_width, prev_y),
+                                Point::new(center_x, close_y),
+                            ),
+                            canvas::Stroke::default()
+                                .with_color(Color::from_rgb(0.9, 0.9, 0.9))
+                                .with_width(1.5),
+                        );
+                    }
+                }
+                // 세로선 + 왼쪽 시가 / 오른쪽 종가 눈금
+                ChartStyle::Ohlc => {
+                    frame.stroke(
+                        &canvas::Path::new(|builder| {
+                            builder.move_to(Point::new(center_x, high_y));
+                            builder.line_to(Point::new(center_x, low_y));
+                            builder.move_to(Point::new(x, open_y));
+                            builder.line_to(Point::new(center_x, open_y));
+                            builder.move_to(Point::new(center_x, close_y));
+                            builder.line_to(Point::new(x + body_width, close_y));
+                        }),
+                        stroke,
+                    );
+                }
+                ChartStyle::Candle | ChartStyle::HeikinAshi | ChartStyle::Hollow => {
+                    let body_height = (close_y - open_y).abs().max(1.0);
+                    let body_y = close_y.min(open_y);
+
+                    // 심지 (몸통 위 / 아래)
+                    frame.stroke(
+                        &canvas::Path::new(|builder| {
+                            builder.move_to(Point::new(center_x, high_y));
+                            builder.line_to(Point::new(center_x, body_y));
+                            builder.move_to(Point::new(center_x, body_y + body_height));
+                            builder.line_to(Point::new(center_x, low_y));
+                        }),
+                        stroke,
+                    );
+
+                    // 캔들 몸통 (속 빈 캔들의 양봉은 테두리만)
+                    if self.style == ChartStyle::Hollow && candlestick.close >= candlestick.open {
+                        frame.stroke(
+                            &canvas::Path::rectangle(
+                                Point::new(x, body_y),
+                                Size::new(body_width, body_height),
+                            ),
+                            stroke,
+                        );
+                    } else {
+                        frame.fill_rectangle(
+                            Point::new(x, body_y),
+                            Size::new(body_width, body_height),
+                            color,
+                        );
+                    }
+                }
+            }
 
             // 거래량 바
-            let volume_height = candlestick.volume * volume_scale;
+            let volume_height = (candlestick.volume * volume_scale) as f32;
             let volume_color = if candlestick.close >= candlestick.open {
                 Color::from_rgba(0.8, 0.0, 0.0, 0.5)
             } else {
@@ -582,19 +646,13 @@ impl<Message> Program<Message> for Chart {
 
             // 시간 레이블
             if i % 10 == 0 {
+                let dt = chrono::DateTime::from_timestamp((*ts / 1000) as i64, 0)
+                    .unwrap_or_default()
+                    .with_timezone(&chrono::Local);
                 let time_str = match self.candle_type {
-                    CandleType::Minute1 | CandleType::Minute3 => {
-                        let dt = chrono::DateTime::from_timestamp((*ts / 1000) as i64, 0)
-                            .unwrap_or_default()
-                            .with_timezone(&chrono::Local);
-                        dt.format("%H:%M").to_string()
-                    }
-                    CandleType::Day => {
-                        let dt = chrono::DateTime::from_timestamp((*ts / 1000) as i64, 0)
-                            .unwrap_or_default()
-                            .with_timezone(&chrono::Local);
-                        dt.format("%m/%d").to_string()
-                    }
+                    CandleType::Month => dt.format("%Y/%m").to_string(),
+                    ref candle_type if candle_type.is_intraday() => dt.format("%H:%M").to_string(),
+                    _ => dt.format("%m/%d").to_string(),
                 };
 
                 frame.fill_text(canvas::Text {
@@ -609,15 +667,15 @@ impl<Message> Program<Message> for Chart {
             // println!("Sell scored signals count: {}", self.sell_scored_signals.len());
 
             if let Some(buy_score) = self.buy_scored_signals.get(ts) {
-                let signal_y = top_margin + ((max_price - candlestick.low) * y_scale) + 45.0;
+                let signal_y = price_y(candlestick.low) + 45.0;
                 let center_x = x + body_width / 2.0;
 
                 // 점수에 따른 색상 강도
-                let alpha = (buy_score.total_score / 100.0) * 0.8 + 0.2;
-                let color = Color::from_rgba(0., 255., 100., (alpha * 255.0) as f32);
+                let alpha = ((buy_score.total_score / 100.0) * 0.8 + 0.2) as f32;
+                let color = Color::from_rgba(0., 255., 100., alpha * 255.0);
 
                 // 큰 상승 화살표
-                let arrow_size = 8.0 + (buy_score.total_score - 70.0) / 30.0 * 4.0;
+                let arrow_size = (8.0 + (buy_score.total_score - 70.0) / 30.0 * 4.0) as f32;
 
                 frame.fill(
                     &canvas::Path::new(|p| {
@@ -651,13 +709,13 @@ impl<Message> Program<Message> for Chart {
 
             // 점수 기반 매도 신호
             if let Some(sell_score) = self.sell_scored_signals.get(ts) {
-                let signal_y = top_margin + ((max_price - candlestick.high) * y_scale) - 45.0;
+                let signal_y = price_y(candlestick.high) - 45.0;
                 let center_x = x + body_width / 2.0;
 
-                let alpha = (sell_score.total_score / 100.0) * 0.8 + 0.2;
-                let color = Color::from_rgba(255., 50., 50., (alpha * 255.0) as f32);
+                let alpha = ((sell_score.total_score / 100.0) * 0.8 + 0.2) as f32;
+                let color = Color::from_rgba(255., 50., 50., alpha * 255.0);
 
-                let arrow_size = 8.0 + (sell_score.total_score - 70.0) / 30.0 * 4.0;
+                let arrow_size = (8.0 + (sell_score.total_score - 70.0) / 30.0 * 4.0) as f32;
 
                 // 하향 화살표
                 frame.fill(
@@ -693,16 +751,27 @@ impl<Message> Program<Message> for Chart {
     }
 }
 // ui/chart.rs에 추가
+use crate::indicators::{Cross, EventKind, IndicatorEvent};
 use crate::models::{CandlestickPatterns, SignalScoring};
+use crate::utils::constant as uc;
 
-pub fn calculate_scored_signals(
+pub fn calculate_scored_signals<'a>(
     candlesticks: &BTreeMap<u64, Candlestick>,
     is_realtime: bool,
-    candle_type: &CandleType,
+    events: impl IntoIterator<Item = &'a IndicatorEvent>,
 ) -> (BTreeMap<u64, SignalScoring>, BTreeMap<u64, SignalScoring>) {
     let mut buy_scores = BTreeMap::new();
     let mut sell_scores = BTreeMap::new();
 
+    // 지표 교차 (캔들 시각, 방향) - 캔들마다 찾으므로 한 번만 모음
+    let crosses: HashSet<(u64, Cross)> = events
+        .into_iter()
+        .filter_map(|event| match event.kind {
+            EventKind::Cross(cross) => Some((event.timestamp, cross)),
+            _ => None,
+        })
+        .collect();
+
     let data: Vec<(&u64, &Candlestick)> = candlesticks.iter().collect();
     let window_size = 20;
 
@@ -720,8 +789,14 @@ pub fn calculate_scored_signals(
         // 4. 샛별 점수 (0-25점)
         scoring.morning_star = CandlestickPatterns::detect_morning_star(&data, i);
 
+        // 지표 교차 점수 (MACD 골든 크로스 등)
+        let crossed = |cross: Cross| crosses.contains(&(*timestamp, cross));
+        if crossed(Cross::Up) {
+            scoring.indicator_cross = uc::INDICATOR_CROSS_SCORE;
+        }
+
         // 매수 총점 계산
-        let buy_total = scoring.bullish_engulfing + scoring.morning_star;
+        let buy_total = scoring.bullish_engulfing + scoring.morning_star + scoring.indicator_cross;
         scoring.total_score = buy_total;
 
         // 70점 이상일 때만 신호 발생
@@ -735,6 +810,7 @@ pub fn calculate_scored_signals(
                     scoring.bullish_engulfing
                 );
                 println!("  ⭐ Morning Star: {:.1}/25", scoring.morning_star);
+                println!("  ✖ Indicator Cross: {:.1}", scoring.indicator_cross);
                 println!("========================");
             }
         }
@@ -744,8 +820,13 @@ pub fn calculate_scored_signals(
 
         sell_scoring.bearish_engulfing = CandlestickPatterns::detect_bearish_engulfing(&data, i);
         sell_scoring.evening_star = CandlestickPatterns::detect_evening_star(&data, i);
+        if crossed(Cross::Down) {
+            sell_scoring.indicator_cross = uc::INDICATOR_CROSS_SCORE;
+        }
 
-        let sell_total = sell_scoring.bearish_engulfing + sell_scoring.evening_star;
+        let sell_total = sell_scoring.bearish_engulfing
+            + sell_scoring.evening_star
+            + sell_scoring.indicator_cross;
         sell_scoring.total_score = sell_total;
 
         if sell_total >= 70.0 {
@@ -759,6 +840,7 @@ pub fn calculate_scored_signals(
                     sell_scoring.bearish_engulfing
                 );
                 println!("  🌟 Evening Star: {:.1}/25", sell_scoring.evening_star);
+                println!("  ✖ Indicator Cross: {:.1}", sell_scoring.indicator_cross);
                 println!("========================");
             }
         }
@@ -0,0 +1,92 @@
+use crate::api::replay::ReplaySpeed;
+use crate::store::export::FileFormat;
+use crate::Futurx;
+use crate::Message;
+use iced::widget::{button, checkbox, pick_list, text_input, Column, Row, Text};
+use iced::Length;
+
+/*  캔들 데이터 내보내기 / 가져오기
+   - 기간(YYYY-MM-DD) 지정 후 CSV / Parquet 로 내보내기
+   - 파일 경로 입력 후 현재 심볼 / 캔들 타입으로 가져오기
+*/
+pub fn data_controls(r: &Futurx) -> Column<'_, Message> {
+    Column::new()
+        .spacing(10)
+        .push(Text::new("Candle Data").size(16))
+        .push(
+            Row::new()
+                .spacing(10)
+                .push(
+                    text_input("From (YYYY-MM-DD)", &r.export_from)
+                        .on_input(Message::ExportFromChanged)
+                        .width(Length::Fill),
+                )
+                .push(
+                    text_input("To (YYYY-MM-DD)", &r.export_to)
+                        .on_input(Message::ExportToChanged)
+                        .width(Length::Fill),
+                ),
+        )
+        .push(
+            Row::new()
+                .spacing(10)
+                .push(
+                    button(Text::new("Export CSV"))
+                        .width(Length::Fill)
+                        .on_press(Message::ExportCandles(FileFormat::Csv)),
+                )
+                .push(
+                    button(Text::new("Export Parquet"))
+                        .width(Length::Fill)
+                        .on_press(Message::ExportCandles(FileFormat::Parquet)),
+                ),
+        )
+        .push(
+            Row::new()
+                .spacing(10)
+                .push(
+                    text_input("File path (.csv / .parquet)", &r.import_path)
+                        .on_input(Message::ImportPathChanged)
+                        .width(Length::Fill),
+                )
+                .push(button(Text::new("Import")).on_press(Message::ImportCandles)),
+        )
+}
+
+/*  리플레이 모드
+   - tape 파일 경로 또는 시작일(YYYY-MM-DD) 입력 후 시작
+   - 리플레이 중에는 실시간 스트림 / 실제 주문 대신 기록된 데이터로 동작
+*/
+pub fn replay_controls(r: &Futurx) -> Column<'_, Message> {
+    Column::new()
+        .spacing(10)
+        .push(Text::new("Replay").size(16))
+        .push(checkbox("Replay mode", r.replay_enabled).on_toggle(|_| Message::ToggleReplay))
+        .push(
+            text_input("Tape path or start date", &r.replay_input)
+                .on_input(Message::ReplayInputChanged)
+                .width(Length::Fill),
+        )
+        .push(
+            Row::new()
+                .spacing(10)
+                .push(
+                    pick_list(
+                        ReplaySpeed::ALL.to_vec(),
+                        Some(r.replay_speed),
+                        Message::SelectReplaySpeed,
+                    )
+                    .width(Length::Fixed(80.0)),
+                )
+                .push(
+                    button(Text::new("Start"))
+                        .on_press_maybe(r.replay_enabled.then_some(Message::StartReplay)),
+                )
+                .push(
+                    button(Text::new("Step")).on_press_maybe(
+                        (r.replay_enabled && r.replay_speed == ReplaySpeed::Step)
+                            .then_some(Message::StepReplay),
+                    ),
+                ),
+        )
+}
@@ -1,38 +1,82 @@
-use crate::Message;
+use crate::api::MarketType;
+use crate::trading::inverse_pnl;
+use crate::utils::format_price;
 use crate::Futurx;
+use crate::Message;
 
 use iced::{
     widget::{Column, Container, Row, Text},
     Color, Length,
 };
 pub fn coin_info(r: &Futurx) -> Column<'_, Message> {
-    let coin_info = if let Some(info) = r.coin_list.get(&r.selected_coin) {
+    let coin_info = if let Some(info) = r.coin_list.get(&r.selected_symbol) {
         Column::new()
             .spacing(10)
             .push(
                 Container::new(
                     Column::new()
-                        .push(Text::new(&info.name).size(28).width(Length::Fill))
                         .push(
-                            Text::new(&info.symbol)
-                                .size(14)
-                                .color(Color::from_rgb(0.5, 0.5, 0.5)),
+                            Text::new(&info.symbol.display_name)
+                                .size(28)
+                                .width(Length::Fill),
+                        )
+                        .push(
+                            Text::new(format!(
+                                "{} · {}",
+                                info.symbol.exchange_symbol, info.symbol.market
+                            ))
+                            .size(14)
+                            .color(Color::from_rgb(0.5, 0.5, 0.5)),
                         ),
                 )
                 .padding(10)
                 .width(Length::Fill),
             )
             .push(
-                Container::new(Text::new(format!("{:.6} USDT", info.price)).size(32))
-                    .padding(15)
-                    .width(Length::Fill),
+                Container::new(
+                    Text::new(format!(
+                        "{} {}",
+                        format_price(info.price, info.symbol.price_precision),
+                        info.symbol.quote
+                    ))
+                    .size(32),
+                )
+                .padding(15)
+                .width(Length::Fill),
             )
+            .push_maybe(info.symbol.is_inverse().then(|| {
+                // COIN-M 계약 크기 / 만기일
+                let delivery = match info.symbol.delivery_date {
+                    Some(delivery_date) => {
+                        chrono::DateTime::from_timestamp_millis(delivery_date as i64)
+                            .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+                            .unwrap_or_default()
+                    }
+                    None => "Perpetual".to_string(),
+                };
+                Column::new()
+                    .spacing(5)
+                    .push(
+                        Text::new(format!(
+                            "Contract: {} {}",
+                            info.symbol.contract_size, info.symbol.quote
+                        ))
+                        .size(14),
+                    )
+                    .push(Text::new(format!("Delivery: {}", delivery)).size(14))
+            }))
     } else {
         Column::new().push(Text::new("Loading..."))
     };
     coin_info
 }
 pub fn account_info(r: &Futurx) -> Column<'static, Message> {
+    match r.selected_market {
+        MarketType::Spot => return spot_account_info(r),
+        MarketType::CoinM => return coin_m_account_info(r),
+        MarketType::Futures | MarketType::BybitLinear | MarketType::OkxSwap => {}
+    }
+    let quote = r.quote_asset();
     Column::new()
         .spacing(10)
         .push(Text::new("Account Info").size(24))
@@ -41,13 +85,13 @@ pub fn account_info(r: &Futurx) -> Column<'static, Message> {
                 .spacing(10)
                 .push(Text::new("Total Balance:"))
                 .push(
-                    Text::new(if let Some(info) = &r.account_info {
-                        if let Some(asset) = info.assets.iter().find(|a| a.asset == "USDT") {
+                    Text::new(if let Some(info) = r.futures_account_info() {
+                        if let Some(asset) = info.assets.iter().find(|a| a.asset == quote) {
                             let balance = asset.wallet_balance.parse::<f64>().unwrap_or(0.0);
                             let pnl = asset.unrealized_profit.parse::<f64>().unwrap_or(0.0);
-                            format!("{:.2} USDT (PNL: {:.2})", balance, pnl)
+                            format!("{:.2} {} (PNL: {:.2})", balance, quote, pnl)
                         } else {
-                            "0.00 USDT".to_string()
+                            format!("0.00 {}", quote)
                         }
                     } else {
                         "Loading...".to_string()
@@ -58,6 +102,12 @@ pub fn account_info(r: &Futurx) -> Column<'static, Message> {
 }
 
 pub fn current_position(r: &Futurx) -> Container<'static, Message> {
+    match r.selected_market {
+        MarketType::Spot => return spot_current_position(r),
+        MarketType::CoinM => return coin_m_current_position(r),
+        MarketType::Futures | MarketType::BybitLinear | MarketType::OkxSwap => {}
+    }
+    let quote = r.quote_asset();
     Container::new(
         Column::new()
             .spacing(10)
@@ -65,10 +115,10 @@ pub fn current_position(r: &Futurx) -> Container<'static, Message> {
             .push(
                 Row::new()
                     .spacing(10)
-                    .push(Text::new("USDT Balance:"))
+                    .push(Text::new(format!("{} Balance:", quote)))
                     .push(
-                        Text::new(if let Some(info) = &r.account_info {
-                            if let Some(asset) = info.assets.iter().find(|a| a.asset == "USDT") {
+                        Text::new(if let Some(info) = r.futures_account_info() {
+                            if let Some(asset) = info.assets.iter().find(|a| a.asset == quote) {
                                 let available =
                                     asset.available_balance.parse::<f64>().unwrap_or(0.0);
                                 format!("{:.2}", available)
@@ -86,10 +136,11 @@ pub fn current_position(r: &Futurx) -> Container<'static, Message> {
                     .spacing(10)
                     .push(Text::new(format!("Position:")).size(16)) // 직접 format
                     .push(
-                        Text::new(if let Some(info) = &r.account_info {
-                            let symbol = format!("{}USDT", r.selected_coin);
-                            if let Some(position) =
-                                info.positions.iter().find(|p| p.symbol == symbol)
+                        Text::new(if let Some(info) = r.futures_account_info() {
+                            if let Some(position) = info
+                                .positions
+                                .iter()
+                                .find(|p| p.symbol == r.selected_symbol)
                             {
                                 let amt = position.position_amt.parse::<f64>().unwrap_or(0.0);
                                 let entry = position.entry_price.parse::<f64>().unwrap_or(0.0);
@@ -98,10 +149,10 @@ pub fn current_position(r: &Futurx) -> Container<'static, Message> {
                                 if amt != 0.0 {
                                     let direction = if amt > 0.0 { "Long" } else { "Short" };
                                     format!(
-                                        "{} {:.8} @ {:.2} (PNL: {:.2})",
+                                        "{} {:.8} @ {} (PNL: {:.2})",
                                         direction,
                                         amt.abs(),
-                                        entry,
+                                        format_price(entry, r.price_precision()),
                                         pnl
                                     )
                                 } else {
@@ -118,19 +169,19 @@ pub fn current_position(r: &Futurx) -> Container<'static, Message> {
             )
             .push(
                 Row::new().push(Text::new("Size:").size(16)).push(
-                    Text::new(if let Some(info) = r.coin_list.get(&r.selected_coin) {
+                    Text::new(if let Some(info) = r.coin_list.get(&r.selected_symbol) {
                         let current_price = info.price; // 현재 마켓 가격
 
-                        if let Some(position) = r.account_info.as_ref().and_then(|account| {
+                        if let Some(position) = r.futures_account_info().and_then(|account| {
                             account
                                 .positions
                                 .iter()
-                                .find(|p| p.symbol == format!("{}USDT", r.selected_coin))
+                                .find(|p| p.symbol == r.selected_symbol)
                         }) {
                             let amt = position.position_amt.parse::<f64>().unwrap_or(0.0);
                             let size = amt * current_price; // Position Amount * Current Price
                             if size != 0.0 {
-                                format!("{:.6} USDT", size)
+                                format!("{:.6} {}", size, quote)
                             } else {
                                 "No Position".to_string()
                             }
@@ -145,12 +196,15 @@ pub fn current_position(r: &Futurx) -> Container<'static, Message> {
             )
             .push(
                 Row::new().push(Text::new("Entry Price:").size(16)).push(
-                    Text::new(if let Some(info) = &r.account_info {
-                        let symbol = format!("{}USDT", r.selected_coin);
-                        if let Some(position) = info.positions.iter().find(|p| p.symbol == symbol) {
+                    Text::new(if let Some(info) = r.futures_account_info() {
+                        if let Some(position) = info
+                            .positions
+                            .iter()
+                            .find(|p| p.symbol == r.selected_symbol)
+                        {
                             let entry_price = position.entry_price.parse::<f64>().unwrap_or(0.0);
                             if entry_price > 0.0 {
-                                format!("{:.6}", entry_price)
+                                format_price(entry_price, r.price_precision())
                             } else {
                                 "No Position".to_string()
                             }
@@ -165,9 +219,12 @@ pub fn current_position(r: &Futurx) -> Container<'static, Message> {
             )
             .push(
                 Row::new().push(Text::new(format!("ROE:")).size(16)).push(
-                    Text::new(if let Some(info) = &r.account_info {
-                        let symbol = format!("{}USDT", r.selected_coin);
-                        if let Some(position) = info.positions.iter().find(|p| p.symbol == symbol) {
+                    Text::new(if let Some(info) = r.futures_account_info() {
+                        if let Some(position) = info
+                            .positions
+                            .iter()
+                            .find(|p| p.symbol == r.selected_symbol)
+                        {
                             let initial_margin =
                                 position.initial_margin.parse::<f64>().unwrap_or(1.0);
                             let unrealized_profit =
@@ -186,11 +243,11 @@ pub fn current_position(r: &Futurx) -> Container<'static, Message> {
                         "Loading...".to_string()
                     })
                     .size(16)
-                    .color(if let Some(info) = &r.account_info {
+                    .color(if let Some(info) = r.futures_account_info() {
                         if let Some(position) = info
                             .positions
                             .iter()
-                            .find(|p| p.symbol == format!("{}USDT", r.selected_coin))
+                            .find(|p| p.symbol == r.selected_symbol)
                         {
                             let roe = position.unrealized_profit.parse::<f64>().unwrap_or(0.0)
                                 / position.initial_margin.parse::<f64>().unwrap_or(1.0)
@@ -210,3 +267,163 @@ pub fn current_position(r: &Futurx) -> Container<'static, Message> {
             ),
     )
 }
+
+//현물 계좌 정보
+fn spot_account_info(r: &Futurx) -> Column<'static, Message> {
+    let quote = r.quote_asset();
+    Column::new()
+        .spacing(10)
+        .push(Text::new("Spot Account").size(24))
+        .push(
+            Row::new()
+                .spacing(10)
+                .push(Text::new("Total Balance:"))
+                .push(
+                    Text::new(if let Some(info) = &r.spot_account_info {
+                        let (free, locked) = info.balance(&quote);
+                        format!("{:.2} {} (Locked: {:.2})", free + locked, quote, locked)
+                    } else {
+                        "Loading...".to_string()
+                    })
+                    .size(16),
+                ),
+        )
+}
+
+//현물 보유 수량
+fn spot_current_position(r: &Futurx) -> Container<'static, Message> {
+    let quote = r.quote_asset();
+    let (base, price) = r
+        .coin_list
+        .get(&r.selected_symbol)
+        .map(|info| (info.symbol.base.clone(), info.price))
+        .unwrap_or_default();
+    let holdings = r.spot_account_info.as_ref().map(|info| info.balance(&base));
+
+    Container::new(
+        Column::new()
+            .spacing(10)
+            .push(Text::new("Spot Holdings").size(16))
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .push(Text::new(format!("{} Balance:", quote)))
+                    .push(
+                        Text::new(if let Some(info) = &r.spot_account_info {
+                            format!("{:.2}", info.balance(&quote).0)
+                        } else {
+                            "Loading...".to_string()
+                        })
+                        .size(16),
+                    ),
+            )
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .push(Text::new(format!("{}:", base)).size(16))
+                    .push(
+                        Text::new(match holdings {
+                            Some((free, locked)) if free + locked > 0.0 => format!(
+                                "{:.8} (≈ {:.2} {})",
+                                free + locked,
+                                (free + locked) * price,
+                                quote
+                            ),
+                            Some(_) => "No Holdings".to_string(),
+                            None => "Loading...".to_string(),
+                        })
+                        .size(16),
+                    ),
+            ),
+    )
+}
+
+//COIN-M 계좌 정보 (증거금 자산 = 기초 자산)
+fn coin_m_account_info(r: &Futurx) -> Column<'static, Message> {
+    let base_asset = r
+        .current_symbol()
+        .map(|symbol| symbol.margin_asset().to_string())
+        .unwrap_or_default();
+
+    Column::new()
+        .spacing(10)
+        .push(Text::new("COIN-M Account").size(24))
+        .push(
+            Row::new()
+                .spacing(10)
+                .push(Text::new("Total Balance:"))
+                .push(
+                    Text::new(if let Some(info) = &r.coin_m_account_info {
+                        if let Some(asset) = info.assets.iter().find(|a| a.asset == base_asset) {
+                            let balance = asset.wallet_balance.parse::<f64>().unwrap_or(0.0);
+                            let pnl = asset.unrealized_profit.parse::<f64>().unwrap_or(0.0);
+                            format!("{:.8} {} (PNL: {:.8})", balance, base_asset, pnl)
+                        } else {
+                            format!("0.00 {}", base_asset)
+                        }
+                    } else {
+                        "Loading...".to_string()
+                    })
+                    .size(16),
+                ),
+        )
+}
+
+//COIN-M 포지션 (계약 수, 손익은 기초 자산 단위)
+fn coin_m_current_position(r: &Futurx) -> Container<'static, Message> {
+    let info = r.coin_list.get(&r.selected_symbol);
+    let position = r.coin_m_account_info.as_ref().and_then(|account| {
+        account
+            .positions
+            .iter()
+            .find(|p| p.symbol == r.selected_symbol)
+    });
+
+    let position_text = match (position, info) {
+        (Some(position), Some(info)) => {
+            let contract = &info.symbol;
+            let contracts = position.position_amt.parse::<f64>().unwrap_or(0.0);
+            let entry = position.entry_price.parse::<f64>().unwrap_or(0.0);
+            if contracts != 0.0 {
+                let direction = if contracts > 0.0 { "Long" } else { "Short" };
+                let pnl = inverse_pnl(contracts, contract.contract_size, entry, info.price);
+                let margin = position.initial_margin.parse::<f64>().unwrap_or(0.0);
+                let roe = if margin != 0.0 {
+                    pnl / margin * 100.0
+                } else {
+                    0.0
+                };
+                vec![
+                    format!(
+                        "{} {} Cont. @ {}",
+                        direction,
+                        contracts.abs(),
+                        format_price(entry, contract.price_precision)
+                    ),
+                    format!(
+                        "Size: {:.2} {}",
+                        contracts.abs() * contract.contract_size,
+                        contract.quote
+                    ),
+                    format!("PNL: {:.8} {}", pnl, contract.margin_asset()),
+                    format!("ROE: {:.2}%", roe),
+                ]
+            } else {
+                vec!["No Position".to_string()]
+            }
+        }
+        (None, Some(_)) if r.coin_m_account_info.is_some() => {
+            vec!["No Position".to_string()]
+        }
+        _ => vec!["Loading...".to_string()],
+    };
+
+    Container::new(
+        position_text.into_iter().fold(
+            Column::new()
+                .spacing(10)
+                .push(Text::new("Current Positions").size(16)),
+            |column, line| column.push(Text::new(line).size(16)),
+        ),
+    )
+}
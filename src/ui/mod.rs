@@ -1,37 +1,45 @@
-use std::collections::{BTreeMap, VecDeque};
+use chrono::Datelike;
+use std::collections::BTreeMap;
 pub mod chart;
 use iced::Point;
 pub mod buttons;
+pub mod data;
 pub mod trading;
+pub mod transform;
+use crate::api::MarketType;
+use crate::indicators::IndicatorSeries;
+use crate::models::footprint::Footprint;
 use crate::SignalScoring;
+use transform::ChartStyle;
 pub mod infos;
+//차트 표시 설정 (Chart::new 입력)
+pub struct ChartOptions {
+    pub candle_type: CandleType,
+    pub style: ChartStyle,    // 캔들 표시 방식
+    pub price_precision: u32, // 가격 표시 자릿수 (심볼 tick size)
+    pub scored_signals_enabled: bool,
+}
 //chart 구조체
 // ui/mod.rs
-pub struct Chart {
-    pub candlesticks: VecDeque<(u64, Candlestick)>,
+pub struct Chart<'a> {
+    pub candlesticks: Vec<(u64, &'a Candlestick)>,
     pub max_data_points: usize,
     pub state: ChartState,
-    pub price_range: Option<(f32, f32)>,
+    pub price_range: Option<(f64, f64)>,
     pub candle_type: CandleType,
+    pub style: ChartStyle,    // 캔들 표시 방식
+    pub price_precision: u32, // 가격 표시 자릿수 (심볼 tick size)
 
-    // 이동평균선 (기존 유지)
-    pub show_ma5: bool,
-    pub show_ma10: bool,
-    pub show_ma20: bool,
-    pub show_ma200: bool,
-    pub ma5_values: BTreeMap<u64, f32>,
-    pub ma10_values: BTreeMap<u64, f32>,
-    pub ma20_values: BTreeMap<u64, f32>,
-    pub ma200_values: BTreeMap<u64, f32>,
+    // 지표 (가격 차트 위 / 하단 영역)
+    pub indicators: Vec<IndicatorSeries>,
 
-    // RSI (기존 유지)
-    pub rsi_values: BTreeMap<u64, f32>,
-    pub show_rsi: bool,
+    // 풋프린트 (가격대별 매도 x 매수, 켜져 있을 때만)
+    pub footprints: Option<&'a BTreeMap<u64, Footprint>>,
 
     // 점수 기반 신호만 새로 추가
     pub scored_signals_enabled: bool,
-    pub buy_scored_signals: BTreeMap<u64, SignalScoring>,
-    pub sell_scored_signals: BTreeMap<u64, SignalScoring>,
+    pub buy_scored_signals: &'a BTreeMap<u64, SignalScoring>,
+    pub sell_scored_signals: &'a BTreeMap<u64, SignalScoring>,
 }
 
 #[derive(Default, Debug)]
@@ -43,18 +51,337 @@ pub struct ChartState {
     pub auto_scroll: bool,
     pub need_more_data: bool, // 추가
 } // Candlestick 구조체 업데이트
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Candlestick {
-    pub open: f32,
-    pub close: f32,
-    pub high: f32,
-    pub low: f32,
-    pub volume: f32, // 거래량 필드 추가
+    pub open: f64,
+    pub close: f64,
+    pub high: f64,
+    pub low: f64,
+    pub volume: f64,           // 거래량 필드 추가
+    pub quote_volume: f64,     // 거래대금 (호가 자산)
+    pub trades: u32,           // 체결 수
+    pub taker_buy_volume: f64, // 시장가 매수 거래량 (기초 자산)
+}
+
+impl Candlestick {
+    //시장가 매도 거래량
+    pub fn taker_sell_volume(&self) -> f64 {
+        (self.volume - self.taker_buy_volume).max(0.0)
+    }
+
+    //시장가 매수 - 매도 거래량 (체결 정보가 없는 캔들은 None - Bybit / OKX 과거 캔들 등)
+    pub fn volume_delta(&self) -> Option<f64> {
+        (self.trades > 0 || self.taker_buy_volume > 0.0)
+            .then(|| self.taker_buy_volume - self.taker_sell_volume())
+    }
+
+    //체결 하나 반영 (시장가 매수 여부는 is_buyer_maker 의 반대)
+    pub fn add_trade(&mut self, price: f64, quantity: f64, trades: u32, taker_buy: bool) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.volume += quantity;
+        self.quote_volume += price * quantity;
+        self.trades += trades;
+        if taker_buy {
+            self.taker_buy_volume += quantity;
+        }
+    }
+
+    //같은 구간의 다음 캔들 합치기 (리샘플링)
+    pub fn merge(&mut self, next: &Candlestick) {
+        self.high = self.high.max(next.high);
+        self.low = self.low.min(next.low);
+        self.close = next.close;
+        self.volume += next.volume;
+        self.quote_volume += next.quote_volume;
+        self.trades += next.trades;
+        self.taker_buy_volume += next.taker_buy_volume;
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum CandleType {
     Minute1,
     Minute3, // 2분봉을 3분봉으로 변경
+    Minute5,
+    Minute15,
+    Minute30,
+    Hour1,
+    Hour2,
+    Hour4,
+    Hour6,
+    Hour8,
+    Hour12,
     Day,
+    Day3,
+    Week,        // 월요일 00:00 UTC 시작
+    Month,       // 매월 1일 00:00 UTC 시작
+    Custom(u64), // 사용자 지정 (분 단위), 기준 캔들을 리샘플링
+}
+
+const MINUTE_MS: u64 = 60_000;
+const HOUR_MS: u64 = 60 * MINUTE_MS;
+const DAY_MS: u64 = 24 * HOUR_MS;
+const WEEK_MS: u64 = 7 * DAY_MS;
+//1970-01-01 은 목요일 -> 월요일 기준으로 맞추기 위한 보정
+const WEEK_OFFSET_MS: u64 = 3 * DAY_MS;
+
+/*
+캔들 시간 계산은 모두 여기서
+- 주봉 / 월봉은 고정 길이가 아니므로 달력 기준으로 계산
+*/
+impl CandleType {
+    pub const ALL: [CandleType; 15] = [
+        CandleType::Minute1,
+        CandleType::Minute3,
+        CandleType::Minute5,
+        CandleType::Minute15,
+        CandleType::Minute30,
+        CandleType::Hour1,
+        CandleType::Hour2,
+        CandleType::Hour4,
+        CandleType::Hour6,
+        CandleType::Hour8,
+        CandleType::Hour12,
+        CandleType::Day,
+        CandleType::Day3,
+        CandleType::Week,
+        CandleType::Month,
+    ];
+
+    //바이낸스 interval 표기 (저장소 키로도 사용)
+    pub fn interval(&self) -> String {
+        match self {
+            CandleType::Minute1 => "1m".to_string(),
+            CandleType::Minute3 => "3m".to_string(),
+            CandleType::Minute5 => "5m".to_string(),
+            CandleType::Minute15 => "15m".to_string(),
+            CandleType::Minute30 => "30m".to_string(),
+            CandleType::Hour1 => "1h".to_string(),
+            CandleType::Hour2 => "2h".to_string(),
+            CandleType::Hour4 => "4h".to_string(),
+            CandleType::Hour6 => "6h".to_string(),
+            CandleType::Hour8 => "8h".to_string(),
+            CandleType::Hour12 => "12h".to_string(),
+            CandleType::Day => "1d".to_string(),
+            CandleType::Day3 => "3d".to_string(),
+            CandleType::Week => "1w".to_string(),
+            CandleType::Month => "1M".to_string(),
+            CandleType::Custom(minutes) if minutes.is_multiple_of(1440) => {
+                format!("{}d", minutes / 1440)
+            }
+            CandleType::Custom(minutes) if minutes.is_multiple_of(60) => {
+                format!("{}h", minutes / 60)
+            }
+            CandleType::Custom(minutes) => format!("{}m", minutes),
+        }
+    }
+
+    /*
+    타임프레임 문자열 -> 캔들 타입 (7m, 45m, 2d 등)
+    - 거래소 interval 과 길이가 같으면 해당 interval 사용
+    */
+    pub fn parse(value: &str) -> Option<CandleType> {
+        let value = value.trim().to_lowercase();
+        let (index, unit) = value.char_indices().last()?;
+        let count: u64 = value[..index].trim().parse().ok()?;
+        let minutes = match unit {
+            'm' => Some(count),
+            'h' => count.checked_mul(60),
+            'd' => count.checked_mul(1440),
+            _ => None,
+        }
+        .filter(|minutes| *minutes > 0)?;
+
+        let duration = minutes.checked_mul(MINUTE_MS)?;
+        Some(
+            CandleType::ALL
+                .into_iter()
+                .find(|candle_type| candle_type.fixed_ms() == Some(duration))
+                .unwrap_or(CandleType::Custom(minutes)),
+        )
+    }
+
+    /*
+    리샘플링 기준 캔들 (거래소 interval 이면 None)
+    - 길이를 나누어 떨어지게 하는 가장 큰 일봉 이하 거래소 interval
+      (7m -> 1m, 45m -> 15m, 2d -> 1d, Bybit 8h -> 4h, Bybit 3d -> 1d)
+    */
+    pub fn resample_base(&self, market: MarketType) -> Option<CandleType> {
+        if market.supports(self) {
+            return None;
+        }
+        let duration = self.fixed_ms()?;
+        CandleType::ALL
+            .into_iter()
+            .filter(|candle_type| market.supports(candle_type))
+            .filter_map(|candle_type| candle_type.fixed_ms().map(|ms| (candle_type, ms)))
+            .filter(|(_, ms)| *ms <= DAY_MS && duration.is_multiple_of(*ms))
+            .max_by_key(|(_, ms)| *ms)
+            .map(|(candle_type, _)| candle_type)
+    }
+
+    //캔들 하나에 들어가는 기준 캔들 수 (거래소 interval 이면 1)
+    pub fn resample_factor(&self, market: MarketType) -> usize {
+        match (
+            self.fixed_ms(),
+            self.resample_base(market).and_then(|base| base.fixed_ms()),
+        ) {
+            (Some(duration), Some(base)) => (duration / base) as usize,
+            _ => 1,
+        }
+    }
+
+    //고정 길이 캔들의 길이 (ms), 월봉은 None
+    fn fixed_ms(&self) -> Option<u64> {
+        match self {
+            CandleType::Minute1 => Some(MINUTE_MS),
+            CandleType::Minute3 => Some(3 * MINUTE_MS),
+            CandleType::Minute5 => Some(5 * MINUTE_MS),
+            CandleType::Minute15 => Some(15 * MINUTE_MS),
+            CandleType::Minute30 => Some(30 * MINUTE_MS),
+            CandleType::Hour1 => Some(HOUR_MS),
+            CandleType::Hour2 => Some(2 * HOUR_MS),
+            CandleType::Hour4 => Some(4 * HOUR_MS),
+            CandleType::Hour6 => Some(6 * HOUR_MS),
+            CandleType::Hour8 => Some(8 * HOUR_MS),
+            CandleType::Hour12 => Some(12 * HOUR_MS),
+            CandleType::Day => Some(DAY_MS),
+            CandleType::Day3 => Some(3 * DAY_MS),
+            CandleType::Week => Some(WEEK_MS),
+            CandleType::Month => None,
+            CandleType::Custom(minutes) => minutes.checked_mul(MINUTE_MS).filter(|ms| *ms > 0),
+        }
+    }
+
+    //시각(ms)이 속한 캔들의 시작 시각
+    pub fn open_time(&self, timestamp: u64) -> u64 {
+        match self {
+            CandleType::Week => timestamp - (timestamp + WEEK_OFFSET_MS) % WEEK_MS,
+            CandleType::Month => {
+                let date = chrono::DateTime::from_timestamp_millis(timestamp as i64)
+                    .unwrap_or_default()
+                    .date_naive();
+                month_start(date.year(), date.month())
+            }
+            _ => {
+                let duration = self.fixed_ms().unwrap_or(DAY_MS);
+                timestamp - timestamp % duration
+            }
+        }
+    }
+
+    //다음 캔들의 시작 시각
+    pub fn next_open_time(&self, timestamp: u64) -> u64 {
+        let open_time = self.open_time(timestamp);
+        match self.fixed_ms() {
+            Some(duration) => open_time + duration,
+            None => {
+                let date = chrono::DateTime::from_timestamp_millis(open_time as i64)
+                    .unwrap_or_default()
+                    .date_naive();
+                match date.month() {
+                    12 => month_start(date.year() + 1, 1),
+                    month => month_start(date.year(), month + 1),
+                }
+            }
+        }
+    }
+
+    //캔들 길이 (ms, 월봉은 1년 / 12)
+    pub fn duration_ms(&self) -> u64 {
+        self.fixed_ms().unwrap_or(365 * DAY_MS / 12)
+    }
+
+    //하루 미만 캔들 여부 (시간 레이블 형식)
+    pub fn is_intraday(&self) -> bool {
+        self.fixed_ms().is_some_and(|duration| duration < DAY_MS)
+    }
+}
+
+//해당 월 1일 00:00 UTC (ms)
+fn month_start(year: i32, month: u32) -> u64 {
+    chrono::NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| datetime.and_utc().timestamp_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_timeframes() {
+        assert_eq!(CandleType::parse("45m"), Some(CandleType::Custom(45)));
+        assert_eq!(CandleType::parse("60m"), Some(CandleType::Hour1));
+        assert_eq!(CandleType::parse(" 1D "), Some(CandleType::Day));
+        // 멀티바이트 단위, 0, 오버플로 입력은 None (패닉 없음)
+        for value in ["5분", "5ｍ", "0m", "99999999999999999d", "m", ""] {
+            assert_eq!(CandleType::parse(value), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn week_starts_on_monday() {
+        // 2024-01-03 (수) 12:00, 2024-01-07 (일) 23:59:59.999 -> 2024-01-01 (월)
+        let monday = 1_704_067_200_000;
+        assert_eq!(CandleType::Week.open_time(1_704_283_200_000), monday);
+        assert_eq!(CandleType::Week.open_time(1_704_671_999_999), monday);
+        assert_eq!(
+            CandleType::Week.open_time(1_704_672_000_000),
+            1_704_672_000_000
+        );
+        assert_eq!(CandleType::Week.next_open_time(monday), 1_704_672_000_000);
+    }
+
+    #[test]
+    fn month_uses_calendar() {
+        // 2024-02-29 15:00 -> 2024-02-01, 다음 2024-03-01
+        assert_eq!(
+            CandleType::Month.open_time(1_709_218_800_000),
+            1_706_745_600_000
+        );
+        assert_eq!(
+            CandleType::Month.next_open_time(1_706_745_600_000),
+            1_709_251_200_000
+        );
+        // 2023-12-15 -> 2023-12-01, 다음 해 넘김 2024-01-01
+        assert_eq!(
+            CandleType::Month.open_time(1_702_598_400_000),
+            1_701_388_800_000
+        );
+        assert_eq!(
+            CandleType::Month.next_open_time(1_701_388_800_000),
+            1_704_067_200_000
+        );
+    }
+
+    #[test]
+    fn resample_unsupported_intervals() {
+        let custom = CandleType::Custom(45);
+        assert_eq!(
+            custom.resample_base(MarketType::Futures),
+            Some(CandleType::Minute15)
+        );
+        assert_eq!(custom.resample_factor(MarketType::Futures), 3);
+
+        // 바이낸스는 8시간 / 3일봉 제공, Bybit 은 4시간 / 일봉에서 만듦
+        assert_eq!(CandleType::Hour8.resample_base(MarketType::Futures), None);
+        assert_eq!(
+            CandleType::Hour8.resample_base(MarketType::BybitLinear),
+            Some(CandleType::Hour4)
+        );
+        assert_eq!(
+            CandleType::Day3.resample_base(MarketType::BybitLinear),
+            Some(CandleType::Day)
+        );
+        assert_eq!(CandleType::Day3.resample_factor(MarketType::BybitLinear), 3);
+        assert_eq!(
+            CandleType::Hour8.resample_base(MarketType::OkxSwap),
+            Some(CandleType::Hour4)
+        );
+        assert_eq!(CandleType::Day3.resample_base(MarketType::OkxSwap), None);
+    }
 }
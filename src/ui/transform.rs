@@ -0,0 +1,140 @@
+use crate::indicators::same_candle;
+use crate::Candlestick;
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+//캔들 표시 방식
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum ChartStyle {
+    #[default]
+    Candle, // 일반 캔들
+    HeikinAshi, // 평균 족 (값 자체가 바뀜)
+    Line,       // 종가 선
+    Hollow,     // 속 빈 캔들 (양봉은 빈 몸통, 색은 전일 종가 대비)
+    Ohlc,       // OHLC 바
+}
+
+impl ChartStyle {
+    pub const ALL: [ChartStyle; 5] = [
+        ChartStyle::Candle,
+        ChartStyle::HeikinAshi,
+        ChartStyle::Line,
+        ChartStyle::Hollow,
+        ChartStyle::Ohlc,
+    ];
+
+    //캔들 값 자체가 바뀌는 방식 (나머지는 그리는 방식만 다름)
+    pub fn transforms(&self) -> bool {
+        matches!(self, ChartStyle::HeikinAshi | ChartStyle::Line)
+    }
+}
+
+impl std::fmt::Display for ChartStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChartStyle::Candle => write!(f, "Candle"),
+            ChartStyle::HeikinAshi => write!(f, "Heikin-Ashi"),
+            ChartStyle::Line => write!(f, "Line"),
+            ChartStyle::Hollow => write!(f, "Hollow"),
+            ChartStyle::Ohlc => write!(f, "OHLC"),
+        }
+    }
+}
+
+/*
+캔들 하나 변환 (prev 는 직전 변환 캔들)
+- Heikin-Ashi
+  종가 = (시가 + 고가 + 저가 + 종가) / 4
+  시가 = (이전 HA 시가 + 이전 HA 종가) / 2 (첫 캔들은 (시가 + 종가) / 2)
+  고가 / 저가 = 원래 고가 / 저가와 HA 시가 / 종가 중 최대 / 최소
+*/
+pub fn transform_candle(
+    prev: Option<&Candlestick>,
+    candle: &Candlestick,
+    style: ChartStyle,
+) -> Candlestick {
+    match style {
+        ChartStyle::HeikinAshi => {
+            let close = (candle.open + candle.high + candle.low + candle.close) / 4.0;
+            let open = match prev {
+                Some(prev) => (prev.open + prev.close) / 2.0,
+                None => (candle.open + candle.close) / 2.0,
+            };
+            Candlestick {
+                open,
+                high: candle.high.max(open).max(close),
+                low: candle.low.min(open).min(close),
+                close,
+                ..candle.clone()
+            }
+        }
+        ChartStyle::Line => Candlestick {
+            open: candle.close,
+            high: candle.close,
+            low: candle.close,
+            ..candle.clone()
+        },
+        ChartStyle::Candle | ChartStyle::Hollow | ChartStyle::Ohlc => candle.clone(),
+    }
+}
+
+/*
+변환 캔들 캐시 (차트 표시 / 지표 / 신호 계산용)
+- 마감 캔들은 한 번만 변환하고 진행 중 캔들만 다시 변환
+- 지표 캐시와 같은 키 (첫 캔들, 마감 캔들 수, 마지막 마감 캔들) 가 바뀌면 처음부터
+*/
+#[derive(Debug, Default)]
+pub struct TransformCache {
+    style: ChartStyle,
+    first: Option<u64>,
+    closed: Option<(u64, Candlestick)>, // 마지막으로 변환한 마감 캔들 (원본)
+    count: usize,                       // 변환한 마감 캔들 수
+    pub candles: BTreeMap<u64, Candlestick>,
+}
+
+impl TransformCache {
+    pub fn sync(&mut self, candlesticks: &BTreeMap<u64, Candlestick>, style: ChartStyle) {
+        let Some((&last, last_candle)) = candlesticks.iter().next_back() else {
+            *self = Self::default();
+            return;
+        };
+
+        let first = candlesticks.keys().next().copied();
+        let stale = self.style != style
+            || self.first != first
+            || self.count >= candlesticks.len()
+            || self.closed.as_ref().is_some_and(|(timestamp, candle)| {
+                candlesticks
+                    .get(timestamp)
+                    .is_none_or(|current| !same_candle(current, candle))
+            });
+        if stale {
+            *self = Self {
+                style,
+                first,
+                ..Self::default()
+            };
+        }
+
+        // 이전 진행 중 캔들 제거 후 새로 마감된 캔들 변환
+        let from = match &self.closed {
+            Some((timestamp, _)) => {
+                self.candles.split_off(&(timestamp + 1));
+                Bound::Excluded(*timestamp)
+            }
+            None => {
+                self.candles.clear();
+                Bound::Unbounded
+            }
+        };
+        for (&timestamp, candle) in candlesticks.range((from, Bound::Excluded(last))) {
+            let transformed = transform_candle(self.candles.values().next_back(), candle, style);
+            self.candles.insert(timestamp, transformed);
+            self.closed = Some((timestamp, candle.clone()));
+            self.count += 1;
+        }
+
+        let transformed = transform_candle(self.candles.values().next_back(), last_candle, style);
+        self.candles.insert(last, transformed);
+    }
+}
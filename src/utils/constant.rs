@@ -3,16 +3,16 @@ use iced::Color;
 //Momentum
 
 pub const MOMENTUM_1MINUTE_PERIOD: usize = 8; //Period가 클수록 장기 추세 증기
-pub const MOMENTUM_1MINUTE_THRESHOLD: f32 = 0.3; //클수록 강한 변화만 포착
-pub const MOMENTUM_1MINUTE_VOLUME_THRESHOLD: f32 = 1.2; //클수록 거래량이 많은 경우만 포착
+pub const MOMENTUM_1MINUTE_THRESHOLD: f64 = 0.3; //클수록 강한 변화만 포착
+pub const MOMENTUM_1MINUTE_VOLUME_THRESHOLD: f64 = 1.2; //클수록 거래량이 많은 경우만 포착
 
 pub const MOMENTUM_3MINUTE_PERIOD: usize = 6; //Period가 클수록 장기 추세 증기
-pub const MOMENTUM_3MINUTE_THRESHOLD: f32 = 0.5; //클수록 강한 변화만 포착
-pub const MOMENTUM_3MINUTE_VOLUME_THRESHOLD: f32 = 1.25; //클수록 거래량이 많은 경우만 포착
+pub const MOMENTUM_3MINUTE_THRESHOLD: f64 = 0.5; //클수록 강한 변화만 포착
+pub const MOMENTUM_3MINUTE_VOLUME_THRESHOLD: f64 = 1.25; //클수록 거래량이 많은 경우만 포착
 
 pub const MOMENTUM_DAY_PERIOD: usize = 10; //Period가 클수록 장기 추세 증기
-pub const MOMENTUM_DAY_THRESHOLD: f32 = 2.0; //클수록 강한 변화만 포착
-pub const MOMENTUM_DAY_VOLUME_THRESHOLD: f32 = 1.2; //클수록 거래량이 많은 경우만 포착
+pub const MOMENTUM_DAY_THRESHOLD: f64 = 2.0; //클수록 강한 변화만 포착
+pub const MOMENTUM_DAY_VOLUME_THRESHOLD: f64 = 1.2; //클수록 거래량이 많은 경우만 포착
 
 //COLOR
 pub const BRIGHT_RED: Color = Color::from_rgb(0.7, 0.1, 0.1);
@@ -39,12 +39,48 @@ pub static DEFAULT_ARR: [&str; 14] = [
     "ADA",
 ];
 
+//호가 자산 (현물 / USDⓈ-M)
+pub static QUOTE_ASSETS: [&str; 2] = ["USDT", "USDC"];
+
 //address
 
 pub static BINANCE_FAPI_ADDRESS: &str = "https://fapi.binance.com/fapi/v1";
 pub static BINANCE_FWSS_ADDRESS: &str = "wss://fstream.binance.com/ws";
+pub static BINANCE_SPOT_API_ADDRESS: &str = "https://api.binance.com/api/v3";
+pub static BINANCE_SPOT_WSS_ADDRESS: &str = "wss://stream.binance.com:9443/ws";
+pub static BINANCE_DAPI_ADDRESS: &str = "https://dapi.binance.com/dapi/v1";
+pub static BINANCE_DWSS_ADDRESS: &str = "wss://dstream.binance.com/ws";
+pub static BYBIT_API_ADDRESS: &str = "https://api.bybit.com/v5";
+pub static BYBIT_LINEAR_WSS_ADDRESS: &str = "wss://stream.bybit.com/v5/public/linear";
+pub static OKX_API_ADDRESS: &str = "https://www.okx.com";
+pub static OKX_PUBLIC_WSS_ADDRESS: &str = "wss://ws.okx.com:8443/ws/v5/public";
 
 //Market Order
 
 pub const MARKET_SELL_ORDER_PRICE: f64 = 10.;
 pub const MARKET_BUY_ORDER_PRICE: f64 = 10.;
+pub const INDICATOR_CROSS_SCORE: f64 = 30.0; //지표 교차 (MACD 등) 신호 점수
+pub const STOP_ATR_PERIOD: usize = 14; //손절 거리 계산 ATR 기간
+pub const STOP_ATR_MULTIPLIER: f64 = 2.0; //손절 거리 = ATR * 배수
+pub const FOOTPRINT_MAX_BARS: usize = 200; //풋프린트를 보관할 최근 캔들 수
+
+//로컬 캔들 저장소
+pub static CANDLE_STORE_PATH: &str = "data/candles.db";
+pub static CANDLE_EXPORT_DIR: &str = "data/export";
+pub const CANDLE_VIEW_LIMIT: usize = 1000; //화면에 불러올 캔들 수
+pub const CANDLE_SYNC_MAX_PAGES: usize = 10; //한 번에 채울 최대 페이지 수
+pub const CANDLE_GAP_MAX_PAGES: usize = 100; //동기화 한 번에 빈 구간을 채울 최대 페이지 수
+pub const INDICATOR_MAX_PERIOD: usize = CANDLE_VIEW_LIMIT; //지표 기간 최대값 (화면 캔들 수)
+pub const SLEEP_DETECT_MS: u64 = 30_000; //틱 사이 간격이 이보다 길면 절전 / 멈춤으로 판단
+
+//차트별 지표 구성
+pub static INDICATOR_CONFIG_PATH: &str = "data/indicators.json";
+
+//원본 체결 기록 (tape)
+pub static TAPE_DIR: &str = "data/tape";
+pub const TAPE_ROTATE_BYTES: usize = 256 * 1024 * 1024; //압축 전 크기 기준 파일 교체
+pub const TAPE_FLUSH_LINES: usize = 1000; //flush 주기 (줄 수)
+
+//리플레이
+pub const REPLAY_MAX_GAP_MS: u64 = 5_000; //이벤트 사이 최대 대기 시간 (배속 적용 전)
+pub const REPLAY_MAX_BATCH: usize = 200; //최대 속도에서 한 번에 보내는 이벤트 수
@@ -12,6 +12,31 @@ pub fn hmac_sha256(secret: &str, message: &str) -> String {
     hex::encode(result.into_bytes())
 }
 
+//OKX 서명용 (hex 대신 base64)
+pub fn hmac_sha256_base64(secret: &str, message: &str) -> String {
+    use base64::Engine;
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    type HmacSha256 = Hmac<Sha256>;
+
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect(logs::HANMAC_ERROR);
+    mac.update(message.as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+}
+
+//"0.00100000" -> 3 (수량 / 가격 step 의 소수점 자릿수)
+pub fn step_precision(step: &str) -> u32 {
+    match step.trim_end_matches('0').split_once('.') {
+        Some((_, decimals)) => decimals.len() as u32,
+        None => 0,
+    }
+}
+
+//가격 표시 (심볼 tick size 자릿수)
+pub fn format_price(price: f64, precision: u32) -> String {
+    format!("{:.*}", precision as usize, price)
+}
+
 //거래량 조정
 pub fn adjust_precision(value: f64, precision: u32) -> f64 {
     let scale = 10f64.powi(precision as i32);